@@ -0,0 +1,23 @@
+use crate::file::file_writer::FileWriter;
+
+use super::node::{CEmitter, Expression};
+
+// Initializer of an array: { 1, 2, 3 }, only valid where an array is declared.
+pub struct Data {
+    pub values: Vec<Expression>,
+}
+
+impl Data {
+    pub(crate) fn emit<'a>(&'a self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
+        writer.append_str("{ ");
+
+        for (i, v) in self.values.iter().enumerate() {
+            v.emit(writer);
+            if i < self.values.len() - 1 {
+                writer.append_str(", ");
+            }
+        }
+
+        writer.append_str(" }")
+    }
+}
@@ -0,0 +1,70 @@
+use crate::file::file_writer::FileWriter;
+
+use super::node::{CEmitter, Expression};
+
+pub struct Data {
+    pub operator: Operator,
+    pub lhs: Box<Expression>,
+    pub rhs: Box<Expression>,
+}
+
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+    Equal,
+    NotEqual,
+    Greater,
+    Less,
+    GreaterEqual,
+    LessEqual,
+    And,
+    Or,
+}
+
+impl CEmitter for Data {
+    fn emit<'a>(&'a self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
+        emit_operand(&self.lhs, writer)
+            .append_space()
+            .append_str(self.operator.as_str())
+            .append_space();
+
+        emit_operand(&self.rhs, writer)
+    }
+}
+
+impl Operator {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Operator::Add => "+",
+            Operator::Subtract => "-",
+            Operator::Multiply => "*",
+            Operator::Divide => "/",
+            Operator::Modulo => "%",
+            Operator::Equal => "==",
+            Operator::Greater => ">",
+            Operator::Less => "<",
+            Operator::NotEqual => "!=",
+            Operator::GreaterEqual => ">=",
+            Operator::LessEqual => "<=",
+            Operator::And => "&&",
+            Operator::Or => "||",
+        }
+    }
+}
+
+// Nested operations are always parenthesized so the C precedence rules never apply.
+pub(crate) fn emit_operand<'a>(
+    operand: &'a Expression,
+    writer: &'a mut FileWriter,
+) -> &'a mut FileWriter {
+    match operand {
+        Expression::BinaryOperation(_) | Expression::Ternary(_) => {
+            writer.append(&'(');
+            operand.emit(writer).append(&')')
+        }
+        _ => operand.emit(writer),
+    }
+}
@@ -0,0 +1,45 @@
+use crate::file::file_writer::FileWriter;
+
+// Functions checking the indices of arrays and slices. Like assert, the checks are left out
+// when NDEBUG is defined. Every header using them defines them behind the same guard. They
+// declare the few C library functions they call themselves rather than including stdio.h and
+// stdlib.h, which would clash with the extern blocks declaring them another way.
+const BOUNDS_CHECK: &str = r#"#ifndef LLC_BOUNDS_CHECK
+#define LLC_BOUNDS_CHECK
+static inline size_t llc_check_index(size_t index, size_t len)
+{
+#ifndef NDEBUG
+	if (index >= len)
+	{
+		extern int dprintf(int, const char *, ...);
+		extern void abort(void);
+		dprintf(2, "Index %zu is out of bounds for a length of %zu.\n", index, len);
+		abort();
+	}
+#else
+	(void)len;
+#endif
+	return index;
+}
+
+static inline void llc_check_range(size_t start, size_t end, size_t len)
+{
+#ifndef NDEBUG
+	if (start > end || end > len)
+	{
+		extern int dprintf(int, const char *, ...);
+		extern void abort(void);
+		dprintf(2, "Range %zu..%zu is out of bounds for a length of %zu.\n", start, end, len);
+		abort();
+	}
+#else
+	(void)start;
+	(void)end;
+	(void)len;
+#endif
+}
+#endif"#;
+
+pub(crate) fn emit(writer: &mut FileWriter) -> &mut FileWriter {
+    writer.append_str(BOUNDS_CHECK)
+}
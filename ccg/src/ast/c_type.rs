@@ -0,0 +1,68 @@
+use std::fmt::Display;
+
+// C writes types around the declared name: int32_t* p, int32_t a[4], int32_t (*f)(int32_t)
+// or int32_t (*f(int32_t x))(int32_t) for a function returning a function pointer.
+#[derive(Clone)]
+pub enum CType {
+    Named(Box<str>),
+    Pointer(Box<CType>),
+    // Variadic functions take any values after their parameters: int (*)(char*, ...)
+    Function {
+        output: Box<CType>,
+        params: Vec<CType>,
+        variadic: bool,
+    },
+    Array {
+        element: Box<CType>,
+        size: usize,
+    },
+}
+
+impl CType {
+    // Text written before and after the name in a declaration of this type.
+    pub fn split(&self) -> (String, String) {
+        match self {
+            CType::Named(name) => (format!("{} ", name), String::new()),
+            CType::Pointer(target) => {
+                let (before, after) = target.split();
+                match (&**target, before.ends_with(' ')) {
+                    (CType::Function { .. } | CType::Array { .. }, _) => {
+                        (format!("{}(*", before), format!("){}", after))
+                    }
+                    (_, true) => (format!("{}* ", before.trim_end()), after),
+                    (_, false) => (format!("{}*", before), after),
+                }
+            }
+            CType::Function {
+                output,
+                params,
+                variadic,
+            } => {
+                let (before, after) = output.split();
+                let mut params: Vec<String> = params.iter().map(|p| p.to_string()).collect();
+                match *variadic {
+                    true => params.push(String::from("...")),
+                    false if params.is_empty() => params.push(String::from("void")),
+                    false => {}
+                }
+                (before, format!("({}){}", params.join(", "), after))
+            }
+            CType::Array { element, size } => {
+                let (before, after) = element.split();
+                (before, format!("[{}]{}", size, after))
+            }
+        }
+    }
+
+    pub fn declare(&self, identifier: &str) -> String {
+        let (before, after) = self.split();
+        format!("{}{}{}", before, identifier, after)
+    }
+}
+
+// Without a name the declaration is the type alone, as written in casts and prototypes.
+impl Display for CType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.declare("").trim_end())
+    }
+}
@@ -0,0 +1,28 @@
+use crate::file::file_writer::FileWriter;
+
+use super::{
+    binary_op,
+    node::{CEmitter, Expression},
+};
+
+pub struct Data {
+    pub object: Box<Expression>,
+    pub field: Box<str>,
+    // Fields of a struct behind a pointer are accessed with ->.
+    pub through_pointer: bool,
+}
+
+impl Data {
+    pub(crate) fn emit<'a>(&'a self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
+        // Prefix operators bind looser than field accesses in C: (*p).x
+        match &*self.object {
+            Expression::UnaryOperation(_) => self.object.emit(writer.append(&'(')).append(&')'),
+            _ => binary_op::emit_operand(&self.object, writer),
+        }
+        .append_str(match self.through_pointer {
+            true => "->",
+            false => ".",
+        })
+        .append_str(&self.field)
+    }
+}
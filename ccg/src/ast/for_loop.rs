@@ -0,0 +1,40 @@
+use crate::file::file_writer::FileWriter;
+
+use super::{
+    c_type::CType,
+    func_def::emit_body,
+    node::{AstNode, CEmitter, Expression},
+};
+
+// Counting loop over the half open range [start, end).
+pub struct Data {
+    pub type_identifier: CType,
+    pub identifier: Box<str>,
+    pub start: Expression,
+    pub end: Expression,
+    pub body: Vec<AstNode>,
+}
+
+impl CEmitter for Data {
+    fn emit<'a>(&'a self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
+        writer
+            .append_str("for (")
+            .append_str(&self.type_identifier.declare(&self.identifier))
+            .append_str(" = ");
+
+        self.start.emit(writer);
+        writer
+            .append_str("; ")
+            .append_str(&self.identifier)
+            .append_str(" < ");
+
+        self.end.emit(writer);
+        writer
+            .append_str("; ")
+            .append_str(&self.identifier)
+            .append_str("++)")
+            .append_line();
+
+        emit_body(&self.body, writer)
+    }
+}
@@ -0,0 +1,29 @@
+use crate::file::file_writer::FileWriter;
+
+use super::{
+    c_type::CType,
+    func_def::{self, FuncParam},
+    node::CEmitter,
+};
+
+// Prototype of a function defined in another translation unit or later in the file.
+pub struct Data {
+    pub type_identifier: CType,
+    pub identifier: Box<str>,
+    pub params: Vec<FuncParam>,
+    // Externs like printf take any values after their parameters.
+    pub variadic: bool,
+}
+
+impl CEmitter for Data {
+    fn emit<'a>(&'a self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
+        func_def::emit_signature(
+            &self.type_identifier,
+            &self.identifier,
+            &self.params,
+            self.variadic,
+            writer,
+        )
+        .append(&';')
+    }
+}
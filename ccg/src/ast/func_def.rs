@@ -1,49 +1,77 @@
 use crate::file::file_writer::FileWriter;
 
-use super::node::{AstNode, CEmitter};
+use super::{
+    c_type::CType,
+    node::{AstNode, CEmitter},
+};
 
 pub struct Data {
-    pub type_identifier: Box<str>,
+    pub type_identifier: CType,
     pub identifier: Box<str>,
     pub params: Vec<FuncParam>,
     pub body: Vec<AstNode>,
 }
 
 pub struct FuncParam {
-    pub type_identifier: Box<str>,
+    pub type_identifier: CType,
     pub identifier: Box<str>,
 }
 
 impl CEmitter for Data {
     fn emit<'a>(&'a self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
-        writer
-            .append_str(&self.type_identifier)
-            .append_space()
-            .append_str(&self.identifier)
-            .append(&'(');
-
-        for (i, p) in self.params.iter().enumerate() {
-            p.emit(writer);
-            if i < self.params.len() - 1 {
-                writer.append_str(", ");
-            }
-        }
-
-        writer.append(&')').append_line();
+        emit_signature(
+            &self.type_identifier,
+            &self.identifier,
+            &self.params,
+            false,
+            writer,
+        )
+        .append_line();
         emit_body(&self.body, writer)
     }
 }
 
 impl CEmitter for FuncParam {
     fn emit<'a>(&'a self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
-        writer
-            .append_str(&self.type_identifier)
-            .append_space()
-            .append_str(&self.identifier)
+        writer.append_str(&self.type_identifier.declare(&self.identifier))
+    }
+}
+
+// The name and parameters of a function are declared by its return type, which wraps them
+// when a function pointer is returned.
+pub fn emit_signature<'a>(
+    type_identifier: &CType,
+    identifier: &str,
+    params: &'a [FuncParam],
+    variadic: bool,
+    writer: &'a mut FileWriter,
+) -> &'a mut FileWriter {
+    let (before, after) = type_identifier.split();
+    writer
+        .append_str(&before)
+        .append_str(identifier)
+        .append(&'(');
+
+    // An empty list would declare a function taking any arguments, like in K&R C.
+    if params.is_empty() && !variadic {
+        writer.append_str("void");
     }
+
+    for (i, p) in params.iter().enumerate() {
+        p.emit(writer);
+        if i < params.len() - 1 {
+            writer.append_str(", ");
+        }
+    }
+
+    if variadic {
+        writer.append_str(", ...");
+    }
+
+    writer.append(&')').append_str(&after)
 }
 
-pub fn emit_body<'a>(nodes: &'a Vec<AstNode>, writer: &'a mut FileWriter) -> &'a mut FileWriter {
+pub fn emit_body<'a>(nodes: &'a [AstNode], writer: &'a mut FileWriter) -> &'a mut FileWriter {
     writer.append(&'{').indent_right().append_line();
 
     for (i, s) in nodes.iter().enumerate() {
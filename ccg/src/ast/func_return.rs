@@ -0,0 +1,20 @@
+use crate::file::file_writer::FileWriter;
+
+use super::node::{CEmitter, Expression};
+
+pub struct Data {
+    pub value: Option<Expression>,
+}
+
+impl Data {
+    pub(crate) fn emit<'a>(&self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
+        writer.append_str("return");
+
+        if let Some(value) = &self.value {
+            writer.append_space();
+            value.emit(writer);
+        }
+
+        writer.append(&';')
+    }
+}
@@ -0,0 +1,33 @@
+use crate::file::file_writer::FileWriter;
+
+use super::{
+    func_def::emit_body,
+    node::{AstNode, CEmitter, Expression},
+};
+
+pub struct Data {
+    pub condition: Expression,
+    pub body: Vec<AstNode>,
+    pub else_body: Option<Vec<AstNode>>,
+}
+
+impl CEmitter for Data {
+    fn emit<'a>(&'a self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
+        writer.append_str("if (");
+        self.condition.emit(writer).append(&')').append_line();
+        emit_body(&self.body, writer);
+
+        match &self.else_body {
+            // A lone if in the else body is emitted as an else if chain.
+            Some(nodes) if matches!(nodes.as_slice(), [AstNode::IfElse(_)]) => {
+                writer.append_line().append_str("else ");
+                nodes[0].emit(writer)
+            }
+            Some(nodes) => {
+                writer.append_line().append_str("else").append_line();
+                emit_body(nodes, writer)
+            }
+            None => writer,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use crate::file::file_writer::FileWriter;
+
+use super::{
+    binary_op,
+    node::{CEmitter, Expression},
+};
+
+// Subscript of an array: a[i]
+pub struct Data {
+    pub object: Box<Expression>,
+    pub index: Box<Expression>,
+}
+
+impl Data {
+    pub(crate) fn emit<'a>(&'a self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
+        // Prefix operators bind looser than subscripts in C: (*p)[i]
+        match &*self.object {
+            Expression::UnaryOperation(_) => self.object.emit(writer.append(&'(')).append(&')'),
+            _ => binary_op::emit_operand(&self.object, writer),
+        }
+        .append(&'[');
+
+        self.index.emit(writer).append(&']')
+    }
+}
@@ -0,0 +1,19 @@
+use crate::file::file_writer::FileWriter;
+
+// #line 12 "main.llc", the C compiler reports the code after it as written at this line of
+// the source file.
+pub struct Data {
+    pub line: usize,
+    pub file: Box<str>,
+}
+
+impl Data {
+    pub(crate) fn emit<'a>(&self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
+        writer
+            .append_str("#line ")
+            .append_str(&self.line.to_string())
+            .append_str(" \"")
+            .append_str(&self.file.replace('\\', "\\\\").replace('"', "\\\""))
+            .append(&'"')
+    }
+}
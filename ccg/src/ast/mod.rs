@@ -1,6 +1,27 @@
+pub mod array_literal;
+pub mod binary_op;
+pub mod bounds_check;
+pub mod c_type;
+pub mod field_access;
+pub mod for_loop;
 pub mod func_call;
+pub mod func_decl;
 pub mod func_def;
+pub mod func_return;
+pub mod if_else;
+pub mod index;
+pub mod line_directive;
 pub mod node;
+pub mod slice;
+pub mod struct_decl;
+pub mod struct_def;
+pub mod struct_literal;
+pub mod switch;
+pub mod tagged_union;
+pub mod ternary;
+pub mod unary_op;
 pub mod var_assign;
 pub mod var_decl;
 pub mod var_declassign;
+pub mod variant_literal;
+pub mod while_loop;
@@ -1,8 +1,28 @@
+use super::array_literal;
+use super::binary_op;
+use super::bounds_check;
+use super::field_access;
+use super::for_loop;
 use super::func_call;
+use super::func_decl;
 use super::func_def;
+use super::func_return;
+use super::if_else;
+use super::index;
+use super::line_directive;
+use super::slice;
+use super::struct_decl;
+use super::struct_def;
+use super::struct_literal;
+use super::switch;
+use super::tagged_union;
+use super::ternary;
+use super::unary_op;
 use super::var_assign;
 use super::var_decl;
 use super::var_declassign;
+use super::variant_literal;
+use super::while_loop;
 use crate::file::file_writer::FileWriter;
 
 pub(crate) trait CEmitter {
@@ -13,22 +33,67 @@ pub enum AstNode {
     VarDeclaration(var_decl::Data),
     VarAssignment(var_assign::Data),
     VarDeclAssignment(var_declassign::Data),
+    // Declaration of a global variable defined in another translation unit.
+    ExternVarDeclaration(var_decl::Data),
     FuncDefinition(func_def::Data),
+    FuncDeclaration(func_decl::Data),
+    StructDeclaration(struct_decl::Data),
+    StructDefinition(struct_def::Data),
+    TaggedUnion(tagged_union::Data),
+    SliceDefinition(slice::Data),
+    SliceAccessors(slice::Accessors),
+    // Functions checking indices, needed by the slice definitions and array subscripts.
+    BoundsCheck,
+    // Maps the following code back to the llc source in compiler diagnostics.
+    LineDirective(line_directive::Data),
+    FuncReturn(func_return::Data),
+    IfElse(if_else::Data),
+    WhileLoop(while_loop::Data),
+    ForLoop(for_loop::Data),
+    Switch(switch::Data),
+    Break,
+    Continue,
+    Expression(Expression),
 }
 
 pub enum Expression {
     Variable(Box<str>),
     Litteral(Box<str>),
     FunctionCall(func_call::Data),
+    BinaryOperation(binary_op::Data),
+    UnaryOperation(unary_op::Data),
+    Ternary(ternary::Data),
+    StructLiteral(struct_literal::Data),
+    FieldAccess(field_access::Data),
+    VariantLiteral(variant_literal::Data),
+    ArrayLiteral(array_literal::Data),
+    Index(index::Data),
 }
 
 impl CEmitter for AstNode {
-    fn emit<'a>(&'a self, writer: &'a mut FileWriter) -> &mut FileWriter {
+    fn emit<'a>(&'a self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
         match self {
             AstNode::VarAssignment(data) => data.emit(writer),
             AstNode::VarDeclaration(data) => data.emit(writer),
             AstNode::VarDeclAssignment(data) => data.emit(writer),
+            AstNode::ExternVarDeclaration(data) => data.emit(writer.append_str("extern ")),
             AstNode::FuncDefinition(data) => data.emit(writer),
+            AstNode::FuncDeclaration(data) => data.emit(writer),
+            AstNode::StructDeclaration(data) => data.emit(writer),
+            AstNode::StructDefinition(data) => data.emit(writer),
+            AstNode::TaggedUnion(data) => data.emit(writer),
+            AstNode::SliceDefinition(data) => data.emit(writer),
+            AstNode::SliceAccessors(data) => data.emit(writer),
+            AstNode::BoundsCheck => bounds_check::emit(writer),
+            AstNode::LineDirective(data) => data.emit(writer),
+            AstNode::FuncReturn(data) => data.emit(writer),
+            AstNode::IfElse(data) => data.emit(writer),
+            AstNode::WhileLoop(data) => data.emit(writer),
+            AstNode::ForLoop(data) => data.emit(writer),
+            AstNode::Switch(data) => data.emit(writer),
+            AstNode::Break => writer.append_str("break;"),
+            AstNode::Continue => writer.append_str("continue;"),
+            AstNode::Expression(exp) => exp.emit(writer).append(&';'),
         }
     }
 }
@@ -39,6 +104,14 @@ impl CEmitter for Expression {
             Expression::Variable(id) => writer.append_str(id),
             Expression::Litteral(value) => writer.append_str(value),
             Expression::FunctionCall(data) => data.emit(writer),
+            Expression::BinaryOperation(data) => data.emit(writer),
+            Expression::UnaryOperation(data) => data.emit(writer),
+            Expression::Ternary(data) => data.emit(writer),
+            Expression::StructLiteral(data) => data.emit(writer),
+            Expression::FieldAccess(data) => data.emit(writer),
+            Expression::VariantLiteral(data) => data.emit(writer),
+            Expression::ArrayLiteral(data) => data.emit(writer),
+            Expression::Index(data) => data.emit(writer),
         }
     }
 }
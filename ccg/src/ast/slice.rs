@@ -0,0 +1,157 @@
+use crate::file::file_writer::FileWriter;
+
+use super::{c_type::CType, node::CEmitter};
+
+// A slice is a pointer to its first element and a length. Every module using a slice type
+// defines it, the guard keeps a single definition per translation unit.
+pub struct Data {
+    pub element: CType,
+}
+
+// Elements are read through functions checking the bounds in debug builds. They are defined
+// apart from the slice, once the struct of the elements is complete.
+pub struct Accessors {
+    pub element: CType,
+}
+
+// Name of the slice struct from the C type of its elements: int32_t_Slice, char_ptr_Slice.
+pub fn identifier(element: &CType) -> Box<str> {
+    let mut name = String::new();
+    for c in element.to_string().chars() {
+        match c {
+            '*' => name.push_str("_ptr"),
+            c if c.is_ascii_alphanumeric() || c == '_' => name.push(c),
+            _ => name.push('_'),
+        }
+    }
+
+    let words: Vec<&str> = name.split('_').filter(|w| !w.is_empty()).collect();
+    Box::from(format!("{}_Slice", words.join("_")))
+}
+
+// Pointer to an element: *int32_t_Slice_at(s, i)
+pub fn at_identifier(slice: &str) -> Box<str> {
+    Box::from(format!("{}_at", slice))
+}
+
+// Elements start to end: int32_t_Slice_range(s, start, end)
+pub fn range_identifier(slice: &str) -> Box<str> {
+    Box::from(format!("{}_range", slice))
+}
+
+// Elements from start to the end of the slice: int32_t_Slice_from(s, start)
+pub fn from_identifier(slice: &str) -> Box<str> {
+    Box::from(format!("{}_from", slice))
+}
+
+impl CEmitter for Data {
+    fn emit<'a>(&'a self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
+        let slice = identifier(&self.element);
+        let pointer = CType::Pointer(Box::new(self.element.clone()));
+        let guard = format!("LLC_{}", slice.to_uppercase());
+
+        writer
+            .append_str("#ifndef ")
+            .append_str(&guard)
+            .append_line()
+            .append_str("#define ")
+            .append_str(&guard)
+            .append_line()
+            .append_str("typedef struct ")
+            .append_str(&slice)
+            .append_line()
+            .append(&'{')
+            .indent_right()
+            .append_line()
+            .append_str(&pointer.declare("ptr"))
+            .append(&';')
+            .append_line()
+            .append_str("size_t len;")
+            .indent_left()
+            .append_line()
+            .append_str("} ")
+            .append_str(&slice)
+            .append(&';')
+            .append_line()
+            .append_str("#endif")
+    }
+}
+
+impl CEmitter for Accessors {
+    fn emit<'a>(&'a self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
+        let slice = identifier(&self.element);
+        let pointer = CType::Pointer(Box::new(self.element.clone()));
+        let guard = format!("LLC_{}_ACCESSORS", slice.to_uppercase());
+
+        writer
+            .append_str("#ifndef ")
+            .append_str(&guard)
+            .append_line()
+            .append_str("#define ")
+            .append_str(&guard);
+
+        // The element may be a function pointer or an array, the pointer to it wraps the name.
+        let (before, after) = pointer.split();
+        emit_function(
+            writer,
+            &format!(
+                "{}{}({} slice, size_t index){}",
+                before,
+                at_identifier(&slice),
+                slice,
+                after
+            ),
+            &["return slice.ptr + llc_check_index(index, slice.len);"],
+        );
+
+        emit_function(
+            writer,
+            &format!(
+                "{} {}({} slice, size_t start, size_t end)",
+                slice,
+                range_identifier(&slice),
+                slice
+            ),
+            &[
+                "llc_check_range(start, end, slice.len);",
+                &format!(
+                    "return ({}){{ .ptr = slice.ptr + start, .len = end - start }};",
+                    slice
+                ),
+            ],
+        );
+
+        emit_function(
+            writer,
+            &format!(
+                "{} {}({} slice, size_t start)",
+                slice,
+                from_identifier(&slice),
+                slice
+            ),
+            &[&format!(
+                "return {}(slice, start, slice.len);",
+                range_identifier(&slice)
+            )],
+        );
+
+        writer.append_line().append_str("#endif")
+    }
+}
+
+fn emit_function(writer: &mut FileWriter, signature: &str, body: &[&str]) {
+    writer
+        .append_line()
+        .append_line()
+        .append_str("static inline ")
+        .append_str(signature)
+        .append_line()
+        .append(&'{')
+        .indent_right();
+
+    for line in body {
+        writer.append_line().append_str(line);
+    }
+
+    writer.indent_left().append_line().append(&'}');
+}
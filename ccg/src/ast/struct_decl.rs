@@ -0,0 +1,20 @@
+use crate::file::file_writer::FileWriter;
+
+use super::node::CEmitter;
+
+// Declares the name of a struct ahead of its definition, so structs can refer to each other
+// and to themselves through pointers.
+pub struct Data {
+    pub identifier: Box<str>,
+}
+
+impl CEmitter for Data {
+    fn emit<'a>(&'a self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
+        writer
+            .append_str("typedef struct ")
+            .append_str(&self.identifier)
+            .append_space()
+            .append_str(&self.identifier)
+            .append(&';')
+    }
+}
@@ -0,0 +1,38 @@
+use crate::file::file_writer::FileWriter;
+
+use super::{c_type::CType, node::CEmitter};
+
+// The name of the struct is declared ahead by a typedef, see struct_decl.
+pub struct Data {
+    pub identifier: Box<str>,
+    pub fields: Vec<StructField>,
+}
+
+pub struct StructField {
+    pub type_identifier: CType,
+    pub identifier: Box<str>,
+}
+
+impl CEmitter for Data {
+    fn emit<'a>(&'a self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
+        writer
+            .append_str("struct ")
+            .append_str(&self.identifier)
+            .append_line()
+            .append(&'{')
+            .indent_right();
+
+        for f in self.fields.iter() {
+            writer.append_line();
+            f.emit(writer).append(&';');
+        }
+
+        writer.indent_left().append_line().append_str("};")
+    }
+}
+
+impl CEmitter for StructField {
+    fn emit<'a>(&'a self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
+        writer.append_str(&self.type_identifier.declare(&self.identifier))
+    }
+}
@@ -0,0 +1,36 @@
+use crate::file::file_writer::FileWriter;
+
+use super::node::{CEmitter, Expression};
+
+// Compound literal with designated initializers: (Point){ .x = 1, .y = 2 }
+pub struct Data {
+    pub type_identifier: Box<str>,
+    pub fields: Vec<FieldValue>,
+}
+
+pub struct FieldValue {
+    pub identifier: Box<str>,
+    pub value: Expression,
+}
+
+impl Data {
+    pub(crate) fn emit<'a>(&'a self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
+        writer
+            .append(&'(')
+            .append_str(&self.type_identifier)
+            .append_str("){ ");
+
+        for (i, f) in self.fields.iter().enumerate() {
+            writer
+                .append(&'.')
+                .append_str(&f.identifier)
+                .append_str(" = ");
+            f.value.emit(writer);
+            if i < self.fields.len() - 1 {
+                writer.append_str(", ");
+            }
+        }
+
+        writer.append_str(" }")
+    }
+}
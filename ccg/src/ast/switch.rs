@@ -0,0 +1,57 @@
+use crate::file::file_writer::FileWriter;
+
+use super::node::{AstNode, CEmitter, Expression};
+
+// Cases never fall through, each of them ends with a break.
+pub struct Data {
+    pub value: Expression,
+    pub cases: Vec<Case>,
+}
+
+pub struct Case {
+    // The default case has no label.
+    pub label: Option<Box<str>>,
+    pub body: Vec<AstNode>,
+}
+
+impl CEmitter for Data {
+    fn emit<'a>(&'a self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
+        writer.append_str("switch (");
+        self.value
+            .emit(writer)
+            .append(&')')
+            .append_line()
+            .append(&'{')
+            .indent_right();
+
+        for c in self.cases.iter() {
+            writer.append_line();
+            c.emit(writer);
+        }
+
+        writer.indent_left().append_line().append(&'}')
+    }
+}
+
+impl CEmitter for Case {
+    fn emit<'a>(&'a self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
+        match &self.label {
+            Some(label) => writer.append_str("case ").append_str(label).append(&':'),
+            None => writer.append_str("default:"),
+        };
+
+        writer.append_line().append(&'{').indent_right();
+
+        for s in self.body.iter() {
+            writer.append_line();
+            s.emit(writer);
+        }
+
+        writer
+            .append_line()
+            .append_str("break;")
+            .indent_left()
+            .append_line()
+            .append(&'}')
+    }
+}
@@ -0,0 +1,106 @@
+use crate::file::file_writer::FileWriter;
+
+use super::{c_type::CType, node::CEmitter};
+
+// Sum types are emitted as an enum of tags and a struct holding the tag next to a union of
+// the variant payloads, payload values are named after their position: _0, _1, ... The
+// name of the struct is declared ahead by a typedef, see struct_decl.
+pub struct Data {
+    pub identifier: Box<str>,
+    pub variants: Vec<Variant>,
+}
+
+pub struct Variant {
+    pub identifier: Box<str>,
+    pub type_identifiers: Vec<CType>,
+}
+
+// Tags are prefixed with the name of the union, C enumerators share a single namespace.
+pub fn tag_identifier(identifier: &str, variant: &str) -> Box<str> {
+    Box::from(format!("{}_{}", identifier, variant))
+}
+
+impl CEmitter for Data {
+    fn emit<'a>(&'a self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
+        writer
+            .append_str("typedef enum ")
+            .append_str(&self.identifier)
+            .append_str("_Tag")
+            .append_line()
+            .append(&'{')
+            .indent_right();
+
+        for (i, v) in self.variants.iter().enumerate() {
+            writer
+                .append_line()
+                .append_str(&tag_identifier(&self.identifier, &v.identifier));
+            if i < self.variants.len() - 1 {
+                writer.append(&',');
+            }
+        }
+
+        writer
+            .indent_left()
+            .append_line()
+            .append_str("} ")
+            .append_str(&self.identifier)
+            .append_str("_Tag;")
+            .append_line()
+            .append_line()
+            .append_str("struct ")
+            .append_str(&self.identifier)
+            .append_line()
+            .append(&'{')
+            .indent_right()
+            .append_line()
+            .append_str(&self.identifier)
+            .append_str("_Tag tag;");
+
+        // C does not allow empty structs, variants without payload have no member.
+        if self.variants.iter().any(|v| !v.type_identifiers.is_empty()) {
+            writer
+                .append_line()
+                .append_str("union")
+                .append_line()
+                .append(&'{')
+                .indent_right();
+
+            for v in self
+                .variants
+                .iter()
+                .filter(|v| !v.type_identifiers.is_empty())
+            {
+                v.emit(writer);
+            }
+
+            writer.indent_left().append_line().append_str("} as;");
+        }
+
+        writer.indent_left().append_line().append_str("};")
+    }
+}
+
+impl CEmitter for Variant {
+    fn emit<'a>(&'a self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
+        writer
+            .append_line()
+            .append_str("struct")
+            .append_line()
+            .append(&'{')
+            .indent_right();
+
+        for (i, t) in self.type_identifiers.iter().enumerate() {
+            writer
+                .append_line()
+                .append_str(&t.declare(&format!("_{}", i)))
+                .append(&';');
+        }
+
+        writer
+            .indent_left()
+            .append_line()
+            .append_str("} ")
+            .append_str(&self.identifier)
+            .append(&';')
+    }
+}
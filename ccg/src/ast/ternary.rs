@@ -0,0 +1,17 @@
+use crate::file::file_writer::FileWriter;
+
+use super::{binary_op::emit_operand, node::Expression};
+
+pub struct Data {
+    pub condition: Box<Expression>,
+    pub then_value: Box<Expression>,
+    pub else_value: Box<Expression>,
+}
+
+impl Data {
+    pub(crate) fn emit<'a>(&'a self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
+        emit_operand(&self.condition, writer).append_str(" ? ");
+        emit_operand(&self.then_value, writer).append_str(" : ");
+        emit_operand(&self.else_value, writer)
+    }
+}
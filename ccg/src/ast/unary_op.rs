@@ -0,0 +1,33 @@
+use crate::file::file_writer::FileWriter;
+
+use super::{binary_op, node::Expression};
+
+pub struct Data {
+    pub operator: Operator,
+    pub operand: Box<Expression>,
+}
+
+pub enum Operator {
+    Negate,
+    Not,
+    AddressOf,
+    Dereference,
+}
+
+impl Data {
+    pub(crate) fn emit<'a>(&'a self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
+        writer.append_str(self.operator.as_str());
+        binary_op::emit_operand(&self.operand, writer)
+    }
+}
+
+impl Operator {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Operator::Negate => "-",
+            Operator::Not => "!",
+            Operator::AddressOf => "&",
+            Operator::Dereference => "*",
+        }
+    }
+}
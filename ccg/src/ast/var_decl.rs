@@ -1,16 +1,16 @@
 use crate::file::file_writer::FileWriter;
 
+use super::c_type::CType;
+
 pub struct Data {
-    pub type_identifier: Box<str>,
+    pub type_identifier: CType,
     pub identifier: Box<str>,
 }
 
 impl Data {
     pub(crate) fn emit<'a>(&self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
         writer
-            .append_str(&self.type_identifier)
-            .append(&' ')
-            .append_str(&self.identifier)
+            .append_str(&self.type_identifier.declare(&self.identifier))
             .append(&';')
     }
 }
@@ -1,9 +1,12 @@
 use crate::file::file_writer::FileWriter;
 
-use super::node::{CEmitter, Expression};
+use super::{
+    c_type::CType,
+    node::{CEmitter, Expression},
+};
 
 pub struct Data {
-    pub type_identifier: Box<str>,
+    pub type_identifier: CType,
     pub identifier: Box<str>,
     pub value: Expression,
 }
@@ -11,9 +14,7 @@ pub struct Data {
 impl Data {
     pub(crate) fn emit<'a>(&self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
         writer
-            .append_str(&self.type_identifier)
-            .append_space()
-            .append_str(&self.identifier)
+            .append_str(&self.type_identifier.declare(&self.identifier))
             .append_str(" = ");
 
         self.value.emit(writer);
@@ -0,0 +1,44 @@
+use crate::file::file_writer::FileWriter;
+
+use super::{
+    node::{CEmitter, Expression},
+    tagged_union,
+};
+
+// Compound literal of a tagged union: (Shape){ .tag = Shape_Circle, .as.Circle = { 1.0 } }
+pub struct Data {
+    pub type_identifier: Box<str>,
+    pub variant: Box<str>,
+    pub values: Vec<Expression>,
+}
+
+impl Data {
+    pub(crate) fn emit<'a>(&'a self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
+        writer
+            .append(&'(')
+            .append_str(&self.type_identifier)
+            .append_str("){ .tag = ")
+            .append_str(&tagged_union::tag_identifier(
+                &self.type_identifier,
+                &self.variant,
+            ));
+
+        if !self.values.is_empty() {
+            writer
+                .append_str(", .as.")
+                .append_str(&self.variant)
+                .append_str(" = { ");
+
+            for (i, v) in self.values.iter().enumerate() {
+                v.emit(writer);
+                if i < self.values.len() - 1 {
+                    writer.append_str(", ");
+                }
+            }
+
+            writer.append_str(" }");
+        }
+
+        writer.append_str(" }")
+    }
+}
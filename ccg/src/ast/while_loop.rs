@@ -0,0 +1,19 @@
+use crate::file::file_writer::FileWriter;
+
+use super::{
+    func_def::emit_body,
+    node::{AstNode, CEmitter, Expression},
+};
+
+pub struct Data {
+    pub condition: Expression,
+    pub body: Vec<AstNode>,
+}
+
+impl CEmitter for Data {
+    fn emit<'a>(&'a self, writer: &'a mut FileWriter) -> &'a mut FileWriter {
+        writer.append_str("while (");
+        self.condition.emit(writer).append(&')').append_line();
+        emit_body(&self.body, writer)
+    }
+}
@@ -1,21 +1,97 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
 use crate::{
     ast::node::{AstNode, CEmitter},
     file::file_writer,
 };
 
 pub struct CFile {
+    // Source file named by the banner at the top of the file.
+    pub generated_from: Option<Box<str>>,
+    pub includes: Vec<Box<str>>,
+    // Headers of the project, included with quotes: #include "foo/bar.h"
+    pub local_includes: Vec<Box<str>>,
+    // Headers are wrapped in a guard, so a header used by several modules is only read once.
+    pub include_guard: Option<Box<str>>,
     pub nodes: Vec<AstNode>,
 }
 
 impl CFile {
     pub fn print(&self) {
+        print!("{}", self.content());
+    }
+
+    pub fn write(&self, output: &mut impl Write) -> io::Result<()> {
+        output.write_all(self.content().as_bytes())
+    }
+
+    // Writes the file at the path, creating its missing directories.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = io::BufWriter::new(fs::File::create(path)?);
+        self.write(&mut file)?;
+        file.flush()
+    }
+
+    pub fn content(&self) -> String {
         let mut writer = file_writer::FileWriter::new();
 
+        writer.append_str("// Generated by llc");
+        if let Some(source) = &self.generated_from {
+            writer.append_str(" from ").append_str(source);
+        }
+        writer
+            .append_str(", do not edit.")
+            .append_line()
+            .append_line();
+
+        if let Some(guard) = &self.include_guard {
+            writer
+                .append_str("#ifndef ")
+                .append_str(guard)
+                .append_line()
+                .append_str("#define ")
+                .append_str(guard)
+                .append_line()
+                .append_line();
+        }
+
+        for i in self.includes.as_slice() {
+            writer
+                .append_str("#include <")
+                .append_str(i)
+                .append(&'>')
+                .append_line();
+        }
+
+        for i in self.local_includes.as_slice() {
+            writer
+                .append_str("#include \"")
+                .append_str(i)
+                .append(&'"')
+                .append_line();
+        }
+
+        if !self.includes.is_empty() || !self.local_includes.is_empty() {
+            writer.append_line();
+        }
+
         for n in self.nodes.as_slice() {
             n.emit(&mut writer);
             writer.append_line();
         }
 
-        println!("{}", writer.get_content());
+        if self.include_guard.is_some() {
+            writer.append_line().append_str("#endif").append_line();
+        }
+
+        writer.content
     }
 }
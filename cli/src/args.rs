@@ -0,0 +1,387 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    build::BuildOptions,
+    report::{ColorChoice, ErrorFormat},
+};
+
+pub const USAGE: &str = "\
+Usage: llc <command> <file.llc> [options]
+
+The file is read from the standard input when it is -, under the name stdin.llc.
+
+Commands:
+    check     Parse and type check the file and the modules it uses
+    build     Compile the file to an executable with the system C compiler
+    run       Build the file and run the executable
+    emit-c    Write the C generated for the file and its modules
+    tokens    Print the tokens of the file
+    ast       Print the syntax tree of the file
+    help      Print this message
+
+Build options, also taken by run:
+    -o <file>            Path of the executable, the name of the file by default
+    -O<level>            Optimization level given to the C compiler: 0, 1, 2, 3 or s
+    --release            Optimize and leave out the bounds checks
+    --cc <compiler>      C compiler to use, LLC_CC, CC or the first of cc, gcc and clang otherwise
+    --build-dir <dir>    Directory receiving the generated C, in the temporary one by default
+    <file>.c/.o/.a       C code and objects to link, like the definitions of extern blocks
+    -l<lib>, -L<dir>     Libraries to link and where to find them
+
+Run options:
+    -- <args>            Arguments of the executable
+
+Tokens options:
+    --json               Print the tokens as JSON
+
+Ast options:
+    --json               Print the tree as JSON
+    --sexpr              Print the tree as S-expressions
+
+Emit-c options:
+    -o <dir>             Directory receiving the .c and .h files, printed otherwise
+
+Options:
+    --color <when>       Color the diagnostics: auto, always or never, auto by default
+    --error-format <f>   Write the diagnostics as human, json lines or sarif, human by default
+    --error-output <f>   Write the diagnostics to the file rather than to stderr
+    -h, --help           Print this message
+    -V, --version        Print the version";
+
+pub const STDIN_FILE: &str = "stdin.llc";
+
+pub enum AstFormat {
+    Text,
+    Json,
+    Sexpr,
+}
+
+pub enum Command {
+    Check {
+        path: String,
+    },
+    Build {
+        path: String,
+        options: BuildOptions,
+    },
+    Run {
+        path: String,
+        options: BuildOptions,
+        program_args: Vec<String>,
+    },
+    EmitC {
+        path: String,
+        output_dir: Option<PathBuf>,
+    },
+    Tokens {
+        path: String,
+        json: bool,
+    },
+    Ast {
+        path: String,
+        format: AstFormat,
+    },
+    Help,
+    Version,
+}
+
+// Errors are described by the returned message, which is written with the usage.
+pub fn parse(args: &[String]) -> Result<Command, String> {
+    let (command, args) = match args.split_first() {
+        Some((command, args)) => (command.as_str(), args),
+        None => return Err(String::from("Expected a command.")),
+    };
+
+    // The arguments of the executable are not options of llc.
+    if args
+        .iter()
+        .take_while(|a| *a != "--")
+        .any(|a| a == "-h" || a == "--help")
+    {
+        return Ok(Command::Help);
+    }
+
+    match command {
+        "check" => Ok(Command::Check {
+            path: parse_path(args)?,
+        }),
+        "build" | "run" => parse_build(command == "run", args),
+        "emit-c" => parse_emit_c(args),
+        "tokens" => {
+            let (path, flags) = parse_flags(args, &["--json"])?;
+            Ok(Command::Tokens {
+                path,
+                json: flags.contains(&"--json"),
+            })
+        }
+        "ast" => {
+            let (path, flags) = parse_flags(args, &["--json", "--sexpr"])?;
+            let format = match flags.last() {
+                Some(&"--json") => AstFormat::Json,
+                Some(_) => AstFormat::Sexpr,
+                None => AstFormat::Text,
+            };
+            Ok(Command::Ast { path, format })
+        }
+        "help" | "-h" | "--help" => Ok(Command::Help),
+        "-V" | "--version" => Ok(Command::Version),
+        c => Err(format!("Unknown command {}.", c)),
+    }
+}
+
+// Options taken by every command, they are removed from the arguments before the command is
+// parsed.
+pub fn take_globals(
+    args: &mut Vec<String>,
+) -> Result<(ColorChoice, ErrorFormat, Option<PathBuf>), String> {
+    let color = match take_option(args, "--color")? {
+        Some(value) => ColorChoice::parse(&value)
+            .ok_or_else(|| format!("Unknown color {}, expected auto, always or never.", value))?,
+        None => ColorChoice::Auto,
+    };
+
+    let format = match take_option(args, "--error-format")? {
+        Some(value) => ErrorFormat::parse(&value).ok_or_else(|| {
+            format!(
+                "Unknown error format {}, expected human, json or sarif.",
+                value
+            )
+        })?,
+        None => ErrorFormat::Human,
+    };
+
+    let output = take_option(args, "--error-output")?.map(PathBuf::from);
+
+    Ok((color, format, output))
+}
+
+// Takes --option value or --option=value out of the arguments of llc.
+fn take_option(args: &mut Vec<String>, option: &str) -> Result<Option<String>, String> {
+    let end = args.iter().position(|a| a == "--").unwrap_or(args.len());
+    let index = match args[..end]
+        .iter()
+        .position(|a| a == option || a.starts_with(&format!("{}=", option)))
+    {
+        Some(i) => i,
+        None => return Ok(None),
+    };
+
+    let value = match args[index].split_once('=') {
+        Some((_, value)) => value.to_string(),
+        None if index + 1 < end => args.remove(index + 1),
+        None => return Err(format!("Expected a value after {}.", option)),
+    };
+    args.remove(index);
+
+    Ok(Some(value))
+}
+
+// Commands only taking the file.
+fn parse_path(args: &[String]) -> Result<String, String> {
+    match args {
+        [path] if !is_option(path) => Ok(path.clone()),
+        [] => Err(String::from("Expected the llc file.")),
+        [arg] => Err(format!("Unknown option {}.", arg)),
+        [_, arg, ..] => Err(format!("Unexpected argument {}.", arg)),
+    }
+}
+
+// Commands taking the file and flags without value.
+fn parse_flags<'a>(args: &'a [String], allowed: &[&str]) -> Result<(String, Vec<&'a str>), String> {
+    let mut path = None;
+    let mut flags = vec![];
+
+    for arg in args.iter() {
+        match arg.as_str() {
+            a if allowed.contains(&a) => flags.push(a),
+            a if is_option(a) => return Err(format!("Unknown option {}.", a)),
+            a if path.is_none() => path = Some(a.to_string()),
+            a => return Err(format!("Unexpected argument {}.", a)),
+        }
+    }
+
+    match path {
+        Some(path) => Ok((path, flags)),
+        None => Err(String::from("Expected the llc file.")),
+    }
+}
+
+fn parse_build(run: bool, args: &[String]) -> Result<Command, String> {
+    let mut path = None;
+    let mut output = None;
+    let mut options = BuildOptions {
+        compiler: None,
+        build_dir: PathBuf::new(),
+        output: PathBuf::new(),
+        optimization: None,
+        release: false,
+        inputs: vec![],
+    };
+    let mut build_dir = None;
+    let mut program_args = vec![];
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" => output = Some(PathBuf::from(option_value(args.next(), arg)?)),
+            "--cc" => match option_value(args.next(), arg)? {
+                c if c.trim().is_empty() => {
+                    return Err(String::from("Expected a C compiler after --cc."))
+                }
+                c => options.compiler = Some(c.to_string()),
+            },
+            "--build-dir" => build_dir = Some(PathBuf::from(option_value(args.next(), arg)?)),
+            "--release" => options.release = true,
+            "-O0" | "-O1" | "-O2" | "-O3" | "-Os" => {
+                options.optimization = Some(Box::from(&arg[2..]))
+            }
+            "--" if run => {
+                program_args = args.cloned().collect();
+                break;
+            }
+            a if a.starts_with("-l") || a.starts_with("-L") => options.inputs.push(a.to_string()),
+            a if [".c", ".o", ".a"].iter().any(|e| a.ends_with(e)) => {
+                options.inputs.push(a.to_string())
+            }
+            a if is_option(a) => return Err(format!("Unknown option {}.", a)),
+            a if path.is_none() => path = Some(a.to_string()),
+            a => return Err(format!("Unexpected argument {}.", a)),
+        }
+    }
+
+    let path = path.ok_or_else(|| String::from("Expected the llc file."))?;
+    let stem = PathBuf::from(source_name(&path))
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+
+    options.build_dir = build_dir.unwrap_or_else(|| default_build_dir(&path, &stem));
+    options.output = match (output, run) {
+        (Some(o), _) => o,
+        (None, true) => options.build_dir.join(&stem),
+        (None, false) => PathBuf::from(&stem),
+    };
+
+    Ok(match run {
+        true => Command::Run {
+            path,
+            options,
+            program_args,
+        },
+        false => Command::Build { path, options },
+    })
+}
+
+// Files of the same name in different projects get their own directory, named after the file and
+// a hash of its canonical path. The standard input is a file of the current directory. The hash
+// must not change between builds of llc, or each new llc would rebuild in a new directory.
+fn default_build_dir(path: &str, stem: &str) -> PathBuf {
+    let file = Path::new(source_name(path));
+    let dir = match file.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let canonical = match fs::canonicalize(dir) {
+        Ok(dir) => dir.join(file.file_name().unwrap_or_default()),
+        Err(_) => std::env::current_dir().unwrap_or_default().join(file),
+    };
+
+    std::env::temp_dir().join("llc").join(format!(
+        "{}-{:016x}",
+        stem,
+        fnv1a(canonical.as_os_str().as_encoded_bytes())
+    ))
+}
+
+// 64 bit FNV-1a, a hash defined by its constants alone.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn parse_emit_c(args: &[String]) -> Result<Command, String> {
+    let mut path = None;
+    let mut output_dir = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" => output_dir = Some(PathBuf::from(option_value(args.next(), arg)?)),
+            a if is_option(a) => return Err(format!("Unknown option {}.", a)),
+            a if path.is_none() => path = Some(a.to_string()),
+            a => return Err(format!("Unexpected argument {}.", a)),
+        }
+    }
+
+    Ok(Command::EmitC {
+        path: path.ok_or_else(|| String::from("Expected the llc file."))?,
+        output_dir,
+    })
+}
+
+// - names the standard input, not an option.
+fn is_option(arg: &str) -> bool {
+    arg.starts_with('-') && arg != "-"
+}
+
+// Name the source of the file is compiled under.
+pub fn source_name(path: &str) -> &str {
+    match path {
+        "-" => STDIN_FILE,
+        path => path,
+    }
+}
+
+fn option_value<'a>(value: Option<&'a String>, option: &str) -> Result<&'a str, String> {
+    match value {
+        Some(v) => Ok(v),
+        None => Err(format!("Expected a value after {}.", option)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_dir(args: &[&str]) -> PathBuf {
+        let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        match parse(&args) {
+            Ok(Command::Build { options, .. }) => options.build_dir,
+            _ => panic!("Expected a build command"),
+        }
+    }
+
+    #[test]
+    fn builds_files_of_the_same_name_in_their_own_directory() {
+        let dir = build_dir(&["build", "main.llc"]);
+        let other = build_dir(&["build", "examples/main.llc"]);
+
+        assert_ne!(dir, other);
+        assert_eq!(dir, build_dir(&["build", "./main.llc"]));
+        assert!(dir
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("main-"));
+    }
+
+    #[test]
+    fn hashes_paths_the_same_way_across_builds() {
+        assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(b"foobar"), 0x85944171f73967e8);
+        assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn keeps_the_given_build_directory() {
+        assert_eq!(
+            build_dir(&["build", "main.llc", "--build-dir", "out"]),
+            PathBuf::from("out")
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use llc_core::{
+    common::position::FileSpan,
+    parser::{
+        ast_node::{AstNode, AstNodeKind, NodeParent},
+        expression::Expression,
+        match_expression::Pattern,
+        parser::FileAst,
+        statement::Statement,
+    },
+};
+
+use crate::json;
+
+// The tree is written from the root nodes, every node under its parent:
+// Declaration [1:1-1:10]
+//     Identifier name=x [1:1-1:1]
+//     Literal value=3 type=i32 [1:6-1:6]
+pub fn text(file_ast: &FileAst) -> String {
+    let mut lines = vec![];
+    for index in file_ast.root_nodes.iter() {
+        write_text(file_ast, *index, 0, &mut lines);
+    }
+    lines.join("\n")
+}
+
+fn write_text(file_ast: &FileAst, index: usize, depth: usize, lines: &mut Vec<String>) {
+    let node = &file_ast.nodes[index];
+    let mut line = format!("{}{}", "    ".repeat(depth), node.name());
+    for (key, value) in attributes(file_ast, node) {
+        line.push_str(&format!(" {}={}", key, value));
+    }
+    line.push_str(&format!(" [{}]", span(&node.position)));
+    lines.push(line);
+
+    for child in node.children() {
+        write_text(file_ast, child, depth + 1, lines);
+    }
+}
+
+// {"file": "main.llc", "nodes": [{"index": 2, "kind": "Declaration", "span": ..., "parent": null,
+// "children": [...]}], "errors": [...]}, with the attributes of the nodes as their other keys.
+pub fn json(file_ast: &FileAst) -> String {
+    let nodes: Vec<String> = file_ast
+        .root_nodes
+        .iter()
+        .map(|i| node_json(file_ast, *i))
+        .collect();
+    let errors: Vec<String> = file_ast
+        .errors
+        .iter()
+        .map(|e| {
+            format!(
+                "{{\"message\":{},\"span\":{}}}",
+                json::string(&e.reason),
+                json::span(&e.position)
+            )
+        })
+        .collect();
+
+    format!(
+        "{{\"file\":{},\"nodes\":{},\"errors\":{}}}",
+        json::string(&file_ast.file_name),
+        json::array(&nodes),
+        json::array(&errors)
+    )
+}
+
+fn node_json(file_ast: &FileAst, index: usize) -> String {
+    let node = &file_ast.nodes[index];
+    let mut json = format!(
+        "{{\"index\":{},\"kind\":{}",
+        index,
+        json::string(node.name())
+    );
+
+    for (key, value) in attributes(file_ast, node) {
+        json.push_str(&format!(",{}:{}", json::string(key), json::string(&value)));
+    }
+
+    let parent = match node.parent {
+        NodeParent::Node { index } => index.to_string(),
+        _ => String::from("null"),
+    };
+    let children: Vec<String> = node
+        .children()
+        .iter()
+        .map(|c| node_json(file_ast, *c))
+        .collect();
+
+    json.push_str(&format!(
+        ",\"span\":{},\"parent\":{},\"children\":[{}]}}",
+        json::span(&node.position),
+        parent,
+        children.join(",")
+    ));
+    json
+}
+
+// (Declaration :span "1:1-1:10" (Identifier :name "x" :span "1:1-1:1") ...), a root node per line.
+pub fn sexpr(file_ast: &FileAst) -> String {
+    let nodes: Vec<String> = file_ast
+        .root_nodes
+        .iter()
+        .map(|i| node_sexpr(file_ast, *i))
+        .collect();
+    nodes.join("\n")
+}
+
+fn node_sexpr(file_ast: &FileAst, index: usize) -> String {
+    let node = &file_ast.nodes[index];
+    let mut sexpr = format!("({}", node.name());
+
+    for (key, value) in attributes(file_ast, node) {
+        sexpr.push_str(&format!(" :{} {}", key, json::string(&value)));
+    }
+    sexpr.push_str(&format!(" :span \"{}\"", span(&node.position)));
+
+    for child in node.children() {
+        sexpr.push(' ');
+        sexpr.push_str(&node_sexpr(file_ast, child));
+    }
+
+    sexpr.push(')');
+    sexpr
+}
+
+fn span(span: &FileSpan) -> String {
+    format!(
+        "{}:{}-{}:{}",
+        span.begin.row, span.begin.col, span.end.row, span.end.col
+    )
+}
+
+// What a node holds besides its children: names, literal values, operators and types.
+fn attributes(file_ast: &FileAst, node: &AstNode) -> Vec<(&'static str, String)> {
+    match &node.kind {
+        AstNodeKind::Expression(Expression::Identifier { index }) => {
+            vec![("name", file_ast.identifiers[*index].name.to_string())]
+        }
+        AstNodeKind::Expression(Expression::Literal(lit)) => vec![
+            ("value", lit.value.value.to_string()),
+            ("type", lit.value.llc_type.to_string()),
+        ],
+        AstNodeKind::Expression(
+            Expression::Binary { operator, .. } | Expression::Unary { operator, .. },
+        ) => vec![("operator", operator.symbol())],
+        AstNodeKind::Expression(Expression::Block {
+            signature: Some(signature),
+            ..
+        }) => signature
+            .output
+            .iter()
+            .map(|t| ("output", t.to_string()))
+            .collect(),
+        AstNodeKind::Expression(Expression::Extern { signature, header }) => {
+            let mut attributes: Vec<(&'static str, String)> =
+                header.iter().map(|h| ("header", h.to_string())).collect();
+            attributes.extend(signature.output.iter().map(|t| ("output", t.to_string())));
+            if signature.variadic {
+                attributes.push(("variadic", String::from("true")));
+            }
+            attributes
+        }
+        AstNodeKind::Expression(
+            Expression::Index {
+                sequence: Some(t), ..
+            }
+            | Expression::Slicing {
+                sequence: Some(t), ..
+            },
+        ) => vec![("sequence", t.to_string())],
+        AstNodeKind::Statement(Statement::Declaration {
+            annotation: Some(t),
+            ..
+        })
+        | AstNodeKind::Statement(
+            Statement::Parameter { llc_type: t, .. } | Statement::Field { llc_type: t, .. },
+        ) => vec![("type", t.to_string())],
+        AstNodeKind::Statement(Statement::Variant { payload, .. }) if !payload.is_empty() => {
+            let types: Vec<String> = payload.iter().map(|t| t.to_string()).collect();
+            vec![("payload", types.join(", "))]
+        }
+        AstNodeKind::Statement(Statement::MatchArm {
+            pattern: Pattern::Wildcard,
+            ..
+        }) => vec![("pattern", String::from("_"))],
+        _ => vec![],
+    }
+}
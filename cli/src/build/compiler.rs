@@ -0,0 +1,73 @@
+use std::{
+    env, io,
+    path::PathBuf,
+    process::{Command, Output, Stdio},
+};
+
+use super::BuildOptions;
+
+// Compilers tried in order when none is configured.
+const COMPILERS: [&str; 3] = ["cc", "gcc", "clang"];
+
+// Environment variables naming the C compiler, the first one set is used.
+pub const COMPILER_VARIABLES: [&str; 2] = ["LLC_CC", "CC"];
+
+// The compiler given on the command line wins over the environment, the first compiler found
+// in the PATH is used otherwise. Like make, the compiler may come with arguments: "gcc -m32".
+// Blank commands are skipped, the returned command is never empty.
+pub fn find(flag: Option<&str>) -> Option<Vec<String>> {
+    let command = flag
+        .map(String::from)
+        .into_iter()
+        .chain(COMPILER_VARIABLES.iter().filter_map(|v| env::var(v).ok()))
+        .find(|c| !c.trim().is_empty());
+
+    match command {
+        Some(c) => Some(c.split_whitespace().map(String::from).collect()),
+        None => COMPILERS
+            .iter()
+            .find(|c| is_available(c))
+            .map(|c| vec![c.to_string()]),
+    }
+}
+
+fn is_available(compiler: &str) -> bool {
+    Command::new(compiler)
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+// Release builds leave out the bounds checks, like C's assert, and are optimized unless another
+// level is given.
+pub fn compile(
+    compiler: &[String],
+    sources: &[PathBuf],
+    options: &BuildOptions,
+) -> io::Result<Output> {
+    let (program, args) = compiler
+        .split_first()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no C compiler was given"))?;
+    let mut command = Command::new(program);
+    command.args(args).arg("-std=c11");
+
+    match (&options.optimization, options.release) {
+        (Some(level), _) => command.arg(format!("-O{}", level)),
+        (None, true) => command.arg("-O2"),
+        (None, false) => &mut command,
+    };
+
+    match options.release {
+        true => command.arg("-DNDEBUG"),
+        false => command.arg("-g"),
+    };
+
+    command
+        .args(sources)
+        .args(&options.inputs)
+        .arg("-o")
+        .arg(&options.output)
+        .output()
+}
@@ -0,0 +1,81 @@
+use std::fmt::Display;
+
+use llc_core::common::{
+    diagnostic::{self, Diagnostic, Severity},
+    position::{FilePosition, FileSpan},
+    source_map::{SourceMap, SourceSpan},
+};
+
+// Diagnostic of the C compiler. The generated C has line directives, so the file and line are
+// the ones of the llc source when the diagnostic is about code lowered from it.
+pub struct CompilerError {
+    pub file_name: Box<str>,
+    pub line: usize,
+    pub severity: Box<str>,
+    pub reason: Box<str>,
+}
+
+// gcc and clang write their diagnostics the same way: main.llc:12:5: error: reason
+const SEVERITIES: [&str; 4] = ["fatal error", "error", "warning", "note"];
+
+impl CompilerError {
+    pub fn parse(line: &str) -> Option<Self> {
+        let (location, severity, reason) = SEVERITIES.iter().find_map(|s| {
+            let (location, reason) = line.split_once(&format!(": {}: ", s))?;
+            Some((location, *s, reason))
+        })?;
+
+        // The line may be followed by a column, and the file name may hold colons.
+        let mut file_name = location;
+        let mut numbers = vec![];
+        while let Some((rest, n)) = file_name.rsplit_once(':') {
+            match n.parse::<usize>() {
+                Ok(n) if numbers.len() < 2 => numbers.push(n),
+                _ => break,
+            }
+            file_name = rest;
+        }
+
+        Some(CompilerError {
+            file_name: Box::from(file_name),
+            line: *numbers.last()?,
+            severity: Box::from(severity),
+            reason: Box::from(reason),
+        })
+    }
+
+    // Only the line is known, the columns are the ones of the generated code. Diagnostics about
+    // files which are not llc sources, like the C code of extern blocks, add them to the sources.
+    pub fn diagnostic(&self, sources: &mut SourceMap) -> Diagnostic {
+        let (severity, code) = match self.severity.as_ref() {
+            "warning" => (Severity::Warning, diagnostic::C_COMPILER_WARNING),
+            "note" => (Severity::Note, diagnostic::C_COMPILER_ERROR),
+            _ => (Severity::Error, diagnostic::C_COMPILER_ERROR),
+        };
+        let file = sources
+            .load(&self.file_name)
+            .unwrap_or_else(|error| error.file());
+        let position = FilePosition::new(self.line, 0);
+        Diagnostic::new(
+            severity,
+            code,
+            &self.reason,
+            SourceSpan::new(file, FileSpan::new(position, position)),
+        )
+    }
+}
+
+impl Display for CompilerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.severity.as_ref() {
+            "warning" => "CompilerWarning",
+            "note" => "CompilerNote",
+            _ => "CompilerError",
+        };
+        write!(
+            f,
+            "At line {0} of {1}. {2}: {3}",
+            self.line, self.file_name, kind, self.reason
+        )
+    }
+}
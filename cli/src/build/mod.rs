@@ -0,0 +1,149 @@
+pub mod compiler;
+pub mod compiler_error;
+
+use std::{
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+};
+
+use ccg::file::c_file::CFile;
+use llc_core::{
+    common::{compile_error::CompileError, source_map::FileId},
+    project::Project,
+};
+
+use crate::lowering::{self, lowering_error::LoweringError};
+use compiler_error::CompilerError;
+
+pub struct BuildOptions {
+    // C compiler given on the command line, the environment is looked up otherwise.
+    pub compiler: Option<String>,
+    // Directory receiving the generated C files.
+    pub build_dir: PathBuf,
+    pub output: PathBuf,
+    // Level of the -O option of the C compiler: 0, 1, 2, 3 or s.
+    pub optimization: Option<Box<str>>,
+    pub release: bool,
+    // Other files and libraries given to the C compiler, like the C code of extern blocks.
+    pub inputs: Vec<String>,
+}
+
+pub enum BuildError {
+    // The entry module has no main block the program can start with.
+    EntryPoint(CompileError),
+    Lowering {
+        file: FileId,
+        errors: Vec<LoweringError>,
+    },
+    Io {
+        path: PathBuf,
+        error: io::Error,
+    },
+    NoCompiler,
+    // Lines of the compiler output which are not diagnostics are kept, like the linker errors.
+    Compiler {
+        diagnostics: Vec<CompilerError>,
+        other: Vec<String>,
+    },
+}
+
+// Writes the C files of every module to the directory, the source files are returned. Line
+// directives are written when the files are to be compiled.
+pub fn emit_c(
+    project: &Project,
+    output_dir: &Path,
+    line_directives: bool,
+) -> Result<Vec<PathBuf>, BuildError> {
+    let mut sources = vec![];
+
+    for index in 0..project.modules.len() {
+        let module = &project.modules[index];
+        let c_module =
+            lowering::lower_module(project, index, line_directives).map_err(|errors| {
+                BuildError::Lowering {
+                    file: module.file_ast.file_id,
+                    errors,
+                }
+            })?;
+
+        let stem = output_dir.join(module.file_stem());
+        save(&c_module.header, &stem.with_extension("h"))?;
+        save(&c_module.source, &stem.with_extension("c"))?;
+        sources.push(stem.with_extension("c"));
+    }
+
+    Ok(sources)
+}
+
+// Writes the C files of every module to the build directory and compiles them to the output,
+// the warnings of the C compiler are returned.
+pub fn build(project: &Project, options: &BuildOptions) -> Result<Vec<CompilerError>, BuildError> {
+    project
+        .check_entry_point()
+        .map_err(BuildError::EntryPoint)?;
+    let compiler = compiler::find(options.compiler.as_deref()).ok_or(BuildError::NoCompiler)?;
+    let sources = emit_c(project, &options.build_dir, true)?;
+
+    let output =
+        compiler::compile(&compiler, &sources, options).map_err(|error| BuildError::Io {
+            path: PathBuf::from(compiler.join(" ")),
+            error,
+        })?;
+
+    let mut diagnostics = vec![];
+    let mut other = vec![];
+    for line in String::from_utf8_lossy(&output.stderr).lines() {
+        match CompilerError::parse(line) {
+            Some(d) => diagnostics.push(d),
+            // Source snippets are left out, their columns are the ones of the generated code.
+            None if line.is_empty() || line.starts_with(' ') => {}
+            None => other.push(line.to_string()),
+        }
+    }
+
+    match output.status.success() {
+        true => Ok(diagnostics),
+        false => Err(BuildError::Compiler { diagnostics, other }),
+    }
+}
+
+fn save(file: &CFile, path: &Path) -> Result<(), BuildError> {
+    file.save(path).map_err(|error| BuildError::Io {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildError::EntryPoint(error) => write!(f, "Build failed: {}", error),
+            BuildError::Lowering { errors, .. } => {
+                write!(f, "Build failed: Errors happened while generating C.")?;
+                for e in errors.iter() {
+                    write!(f, "\n{}", e)?;
+                }
+                Ok(())
+            }
+            BuildError::Io { path, error } => {
+                write!(f, "Build failed: Could not use {}: {}.", path.display(), error)
+            }
+            BuildError::NoCompiler => write!(
+                f,
+                "Build failed: No C compiler was found, install cc, gcc or clang or name one with --cc or {}.",
+                compiler::COMPILER_VARIABLES[0]
+            ),
+            BuildError::Compiler { diagnostics, other } => {
+                write!(f, "Build failed: The C compiler rejected the generated code.")?;
+                for d in diagnostics.iter() {
+                    write!(f, "\n{}", d)?;
+                }
+                for line in other.iter() {
+                    write!(f, "\n{}", line)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
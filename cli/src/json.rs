@@ -0,0 +1,73 @@
+use llc_core::common::{diagnostic::Diagnostic, position::FileSpan, source_map::SourceMap};
+
+// The machine readable outputs are written by hand, they only need strings, numbers and spans.
+pub fn string(value: &str) -> String {
+    let mut json = String::from('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+
+    json.push('"');
+    json
+}
+
+pub fn span(span: &FileSpan) -> String {
+    format!(
+        "{{\"begin\":{{\"row\":{},\"col\":{}}},\"end\":{{\"row\":{},\"col\":{}}}}}",
+        span.begin.row, span.begin.col, span.end.row, span.end.col
+    )
+}
+
+// Writes the values one per line, so the output stays readable.
+pub fn array(values: &[String]) -> String {
+    match values.is_empty() {
+        true => String::from("[]"),
+        false => format!("[\n{}\n]", values.join(",\n")),
+    }
+}
+
+// A diagnostic on a single line, for the tools reading them line by line:
+// {"file": "main.llc", "severity": "error", "code": "E0002", "message": "...", "span": ...,
+//  "labels": [{"message": "...", "span": ...}], "fixes": [{"message": "...", "span": ..., "replacement": ";"}]}
+// Spans end on their last column, except those of fixes which end after it, an insertion has
+// the same beginning and end.
+pub fn diagnostic(diagnostic: &Diagnostic, sources: &SourceMap) -> String {
+    let labels: Vec<String> = diagnostic
+        .labels
+        .iter()
+        .map(|l| format!("{{\"message\":{},\"span\":{}}}", string(&l.message), span(&l.position)))
+        .collect();
+
+    let fixes: Vec<String> = diagnostic
+        .fixes
+        .iter()
+        .map(|f| {
+            format!(
+                "{{\"message\":{},\"span\":{},\"replacement\":{}}}",
+                string(&f.message),
+                span(&f.position),
+                string(&f.replacement)
+            )
+        })
+        .collect();
+
+    format!(
+        "{{\"file\":{},\"severity\":{},\"code\":{},\"message\":{},\"span\":{},\"labels\":[{}],\"fixes\":[{}]}}",
+        string(sources.name(diagnostic.position.file)),
+        string(&diagnostic.severity.to_string()),
+        string(diagnostic.code),
+        string(&diagnostic.message),
+        span(&diagnostic.position.position),
+        labels.join(","),
+        fixes.join(",")
+    )
+}
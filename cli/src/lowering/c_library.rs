@@ -0,0 +1,51 @@
+// Functions of the C standard library, by the header declaring them. Only the headers named by
+// extern blocks matter, a prototype written for one of their functions may not match theirs.
+const DECLARATIONS: [(&str, &[&str]); 6] = [
+    (
+        "stdio.h",
+        &[
+            "printf", "fprintf", "sprintf", "snprintf", "scanf", "fscanf", "sscanf", "puts",
+            "putchar", "getchar", "fputs", "fgets", "fputc", "fgetc", "fopen", "fclose", "fread",
+            "fwrite", "fflush", "perror", "remove", "rename",
+        ],
+    ),
+    (
+        "stdlib.h",
+        &[
+            "malloc", "calloc", "realloc", "free", "exit", "abort", "atexit", "atoi", "atol",
+            "atof", "strtol", "strtoul", "strtod", "rand", "srand", "getenv", "system", "qsort",
+            "bsearch", "abs", "labs",
+        ],
+    ),
+    (
+        "string.h",
+        &[
+            "memcpy", "memmove", "memset", "memcmp", "memchr", "strlen", "strcmp", "strncmp",
+            "strcpy", "strncpy", "strcat", "strncat", "strchr", "strrchr", "strstr", "strerror",
+        ],
+    ),
+    (
+        "math.h",
+        &[
+            "sqrt", "pow", "exp", "log", "log10", "log2", "sin", "cos", "tan", "asin", "acos",
+            "atan", "atan2", "floor", "ceil", "round", "trunc", "fabs", "fmod", "sqrtf", "powf",
+            "fabsf", "floorf", "ceilf",
+        ],
+    ),
+    (
+        "ctype.h",
+        &[
+            "isalpha", "isdigit", "isalnum", "isspace", "isupper", "islower", "ispunct",
+            "isxdigit", "toupper", "tolower",
+        ],
+    ),
+    ("time.h", &["time", "clock", "difftime"]),
+];
+
+// Header of the C standard library declaring the function, when there is one.
+pub fn header(name: &str) -> Option<&'static str> {
+    DECLARATIONS
+        .iter()
+        .find(|(_, names)| names.contains(&name))
+        .map(|(header, _)| *header)
+}
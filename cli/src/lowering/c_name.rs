@@ -0,0 +1,103 @@
+// Keywords of C up to C23, along with those of the GNU dialects compilers default to.
+const KEYWORDS: [&str; 60] = [
+    "alignas",
+    "alignof",
+    "asm",
+    "auto",
+    "bool",
+    "break",
+    "case",
+    "char",
+    "const",
+    "constexpr",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extern",
+    "false",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "nullptr",
+    "register",
+    "restrict",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "static_assert",
+    "struct",
+    "switch",
+    "thread_local",
+    "true",
+    "typedef",
+    "typeof",
+    "typeof_unqual",
+    "union",
+    "unsigned",
+    "void",
+    "volatile",
+    "while",
+    "_Alignas",
+    "_Alignof",
+    "_Atomic",
+    "_BitInt",
+    "_Bool",
+    "_Complex",
+    "_Generic",
+    "_Imaginary",
+    "_Noreturn",
+    "_Static_assert",
+    "_Thread_local",
+    "_Decimal32",
+    "_Decimal64",
+    "_Decimal128",
+];
+
+// Names declared by the headers every C file includes, stdbool.h, stddef.h and stdint.h.
+const HEADER_NAMES: [&str; 14] = [
+    "NULL",
+    "offsetof",
+    "size_t",
+    "ptrdiff_t",
+    "intptr_t",
+    "uintptr_t",
+    "int8_t",
+    "int16_t",
+    "int32_t",
+    "int64_t",
+    "uint8_t",
+    "uint16_t",
+    "uint32_t",
+    "uint64_t",
+];
+
+// Name of an llc declaration, field or variant in C. Names which C reserves get the llc_ prefix
+// of the names generated by llc.
+pub fn mangle(name: &str) -> Box<str> {
+    match is_reserved(name) {
+        true => Box::from(format!("llc_{}", name)),
+        false => Box::from(name),
+    }
+}
+
+fn is_reserved(name: &str) -> bool {
+    KEYWORDS.contains(&name) || HEADER_NAMES.contains(&name)
+}
+
+// Name in C of a name qualified by the path of its module, shapes::Point is shapes__Point. Such
+// names cannot be reserved by C.
+pub fn qualified(name: &str) -> Box<str> {
+    match name.contains("::") {
+        true => Box::from(name.replace("::", "__")),
+        false => mangle(name),
+    }
+}
@@ -0,0 +1,83 @@
+use ccg::ast::{c_type::CType, slice};
+use llc_core::{common::literal::LiteralValue, type_system::llc_type::Type};
+
+use super::c_name;
+
+pub fn from_type(llc_type: &Type) -> Option<CType> {
+    match llc_type {
+        Type::String => Some(named("char*")),
+        Type::Char => Some(named("char")),
+        Type::Bool => Some(named("bool")),
+        Type::Integer { signed, size } => Some(CType::Named(Box::from(format!(
+            "{}int{}_t",
+            match signed {
+                true => "",
+                false => "u",
+            },
+            size
+        )))),
+        Type::Float { size: 32, .. } => Some(named("float")),
+        Type::Float { size: 64, .. } => Some(named("double")),
+        Type::Float { .. } => None,
+        Type::Block { .. } => None,
+        Type::Struct { name } | Type::Enum { name } => Some(CType::Named(c_name::qualified(name))),
+        // Blocks are functions, a reference to a block is a function pointer.
+        Type::Pointer { target, .. } => match &**target {
+            Type::Block {
+                input,
+                output,
+                variadic,
+            } => Some(CType::Pointer(Box::new(CType::Function {
+                output: Box::new(output_type(output.as_deref())?),
+                params: input
+                    .iter()
+                    .flatten()
+                    .map(value_type)
+                    .collect::<Option<Vec<CType>>>()?,
+                variadic: *variadic,
+            }))),
+            t => Some(CType::Pointer(Box::new(from_type(t)?))),
+        },
+        Type::Null => None,
+        Type::Array { element, size } => Some(CType::Array {
+            element: Box::new(from_type(element)?),
+            size: *size,
+        }),
+        Type::Slice { element } => Some(CType::Named(slice_identifier(element)?)),
+    }
+}
+
+// C arrays cannot be passed to or returned by functions, they decay to pointers.
+pub fn value_type(llc_type: &Type) -> Option<CType> {
+    match llc_type {
+        Type::Array { .. } => None,
+        t => from_type(t),
+    }
+}
+
+// Name of the struct generated for the slices of the given element type.
+pub fn slice_identifier(element: &Type) -> Option<Box<str>> {
+    Some(slice::identifier(&from_type(element)?))
+}
+
+// Blocks without output return void.
+pub fn output_type(output: Option<&Type>) -> Option<CType> {
+    match output {
+        Some(t) => value_type(t),
+        None => Some(named("void")),
+    }
+}
+
+fn named(name: &str) -> CType {
+    CType::Named(Box::from(name))
+}
+
+pub fn literal(lit: &LiteralValue) -> Box<str> {
+    match lit.llc_type {
+        Type::String => Box::from(format!("\"{}\"", lit.value)),
+        Type::Char => Box::from(format!("'{}'", lit.value)),
+        Type::Float { size: 32, .. } => Box::from(format!("{}f", lit.value)),
+        Type::Null => Box::from("NULL"),
+        _ => lit.value.clone(),
+    }
+}
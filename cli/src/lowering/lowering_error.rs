@@ -0,0 +1,31 @@
+use std::fmt::Display;
+
+use llc_core::common::{compile_error::CompileError, position::FileSpan, source_map::FileId};
+
+pub struct LoweringError {
+    pub position: FileSpan,
+    pub reason: Box<str>,
+}
+
+impl LoweringError {
+    pub fn new(position: FileSpan, reason: &str) -> Self {
+        LoweringError {
+            position,
+            reason: Box::from(reason),
+        }
+    }
+
+    pub fn compile_error(&self, file: FileId) -> CompileError {
+        CompileError::Codegen {
+            file,
+            position: self.position,
+            reason: self.reason.clone(),
+        }
+    }
+}
+
+impl Display for LoweringError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{0}. CodegenError: {1}", self.position, self.reason)
+    }
+}
@@ -0,0 +1,1795 @@
+pub mod c_library;
+pub mod c_name;
+pub mod c_type;
+pub mod lowering_error;
+pub mod operator;
+
+use std::collections::HashMap;
+
+use ccg::{
+    ast::{
+        array_literal, binary_op,
+        c_type::CType,
+        field_access, for_loop, func_call, func_decl,
+        func_def::{self, FuncParam},
+        func_return, if_else, index, line_directive,
+        node::{self, AstNode as CNode},
+        slice, struct_decl,
+        struct_def::{self, StructField},
+        struct_literal::{self, FieldValue},
+        switch::{self, Case},
+        tagged_union::{self, Variant},
+        ternary, unary_op, var_assign, var_decl, var_declassign, variant_literal, while_loop,
+    },
+    file::c_file::CFile,
+};
+use llc_core::{
+    common::{identifier::TypeState, operator::Operator},
+    parser::{
+        ast_node::AstNodeKind, block::Signature, expression::Expression, match_expression::Pattern,
+        parser::FileAst, statement::Statement,
+    },
+    project::Project,
+    type_system::{exports, llc_type::Type},
+};
+
+use lowering_error::LoweringError;
+
+// What the last expression of a branch is used for when an if is lowered to a statement.
+#[derive(Clone, Copy)]
+enum BranchValue {
+    Discard,
+    Assign(usize),
+    Return,
+}
+
+pub struct Lowering<'a> {
+    project: &'a Project,
+    // Index of the lowered module in the project.
+    module: usize,
+    file_ast: &'a FileAst,
+    // Source file named by the line directives, when there are some.
+    line_file: Option<&'a str>,
+    // Whether the statements being lowered are in a match arm, which is a C switch case.
+    in_switch: bool,
+    // C names of the blocks being lowered, the innermost last.
+    enclosing: Vec<Box<str>>,
+    // C names of the blocks declared in other blocks, by symbol.
+    lifted: HashMap<usize, Box<str>>,
+    structs: Vec<CNode>,
+    // Prototypes of the functions and extern declarations of the globals, for the header.
+    declarations: Vec<CNode>,
+    // C headers declaring the extern blocks.
+    headers: Vec<Box<str>>,
+    // Headers named by the extern blocks of every module.
+    project_headers: Vec<Box<str>>,
+    functions: Vec<CNode>,
+    globals: Vec<CNode>,
+    pub errors: Vec<LoweringError>,
+}
+
+// Every module is lowered to a header declaring its root declarations and a source file
+// defining them.
+pub struct CModule {
+    pub header: CFile,
+    pub source: CFile,
+}
+
+// Headers named by the extern blocks of the modules, which may all end up in the same C file.
+fn project_headers(project: &Project) -> Vec<Box<str>> {
+    let mut headers: Vec<Box<str>> = vec![];
+    for module in project.modules.iter() {
+        for node in module.file_ast.nodes.iter() {
+            if let AstNodeKind::Expression(Expression::Extern {
+                header: Some(header),
+                ..
+            }) = &node.kind
+            {
+                if !headers.contains(header) {
+                    headers.push(header.clone());
+                }
+            }
+        }
+    }
+    headers
+}
+
+pub fn header_name(project: &Project, module: usize) -> Box<str> {
+    Box::from(format!("{}.h", project.modules[module].file_stem()))
+}
+
+// Header of a module included from a file of another one. Generated files include each other
+// by their path from the including file, so no include directory is needed which would let the
+// header of a module like math shadow the one of the C library.
+fn relative_header(project: &Project, from: usize, module: usize) -> Box<str> {
+    let depth = project.modules[from].path.len() - 1;
+    Box::from(format!(
+        "{}{}",
+        "../".repeat(depth),
+        header_name(project, module)
+    ))
+}
+
+// With line directives, the C compiler reports its diagnostics at the lines of the llc source
+// rather than the generated ones.
+pub fn lower_module(
+    project: &Project,
+    module: usize,
+    line_directives: bool,
+) -> Result<CModule, Vec<LoweringError>> {
+    let file_ast = &project.modules[module].file_ast;
+    let mut lowering = Lowering {
+        project,
+        module,
+        file_ast,
+        line_file: match line_directives {
+            true => Some(&file_ast.file_name),
+            false => None,
+        },
+        in_switch: false,
+        enclosing: vec![],
+        lifted: HashMap::new(),
+        structs: vec![],
+        declarations: vec![],
+        headers: vec![],
+        project_headers: project_headers(project),
+        functions: vec![],
+        globals: vec![],
+        errors: vec![],
+    };
+
+    let type_definitions = lowering.type_definitions();
+    let mut lowered_types = vec![];
+    for (name, _) in type_definitions.iter() {
+        lowering.lower_type_definition(name, &type_definitions, &mut lowered_types);
+    }
+
+    for index in file_ast.root_nodes.iter() {
+        match &file_ast.nodes[*index].kind {
+            AstNodeKind::Statement(Statement::Declaration {
+                exp_index: Some(exp_index),
+                ..
+            }) if type_definitions.iter().any(|(_, d)| d == exp_index) => {}
+            AstNodeKind::Statement(Statement::Declaration {
+                ident_index,
+                exp_index,
+                ..
+            }) => {
+                if let Some(node) = lowering.lower_declaration(*ident_index, *exp_index) {
+                    lowering.declarations.push(extern_declaration(&node));
+                    lowering.globals.extend(lowering.line_directive(*index));
+                    lowering.globals.push(node);
+                }
+            }
+            AstNodeKind::Statement(Statement::Use { .. }) => {}
+            _ => lowering.errors.push(LoweringError::new(
+                file_ast.nodes[*index].position,
+                "Only declarations are allowed at file root.",
+            )),
+        }
+    }
+
+    if !lowering.errors.is_empty() {
+        return Err(lowering.errors);
+    }
+
+    // Types are declared before they are defined, so they can refer to each other through
+    // pointers whatever the order of their definitions. Slices only point to their elements,
+    // but structs may hold slices and the slice accessors need complete elements.
+    let header = header_name(project, module);
+    let mut header_nodes: Vec<CNode> = type_definitions
+        .iter()
+        .map(|(name, _)| {
+            CNode::StructDeclaration(struct_decl::Data {
+                identifier: c_name::qualified(name),
+            })
+        })
+        .collect();
+
+    let slices = lowering.slice_types();
+    let mut includes = vec![
+        Box::from("stdbool.h"),
+        Box::from("stddef.h"),
+        Box::from("stdint.h"),
+    ];
+    if !slices.is_empty() || lowering.uses_index() {
+        header_nodes.push(CNode::BoundsCheck);
+    }
+    for h in lowering.headers {
+        if !includes.contains(&h) {
+            includes.push(h);
+        }
+    }
+    header_nodes.extend(slices.iter().map(|element| {
+        CNode::SliceDefinition(slice::Data {
+            element: element.clone(),
+        })
+    }));
+    header_nodes.append(&mut lowering.structs);
+    header_nodes.extend(
+        slices
+            .into_iter()
+            .map(|element| CNode::SliceAccessors(slice::Accessors { element })),
+    );
+    header_nodes.append(&mut lowering.declarations);
+
+    let mut source_nodes = lowering.globals;
+    source_nodes.append(&mut lowering.functions);
+
+    Ok(CModule {
+        header: CFile {
+            generated_from: Some(file_ast.file_name.clone()),
+            includes,
+            local_includes: project.modules[module]
+                .dependencies
+                .iter()
+                .map(|d| relative_header(project, module, *d))
+                .collect(),
+            include_guard: Some(Box::from(header.to_uppercase().replace(['/', '.'], "_"))),
+            nodes: header_nodes,
+        },
+        source: CFile {
+            generated_from: Some(file_ast.file_name.clone()),
+            includes: vec![],
+            local_includes: vec![relative_header(project, module, module)],
+            include_guard: None,
+            nodes: source_nodes,
+        },
+    })
+}
+
+// Element types of the slices in the type, inner slices first.
+fn collect_slices(llc_type: &Type, elements: &mut Vec<Type>) {
+    match llc_type {
+        Type::Slice { element } => {
+            collect_slices(element, elements);
+            if !elements.contains(element) {
+                elements.push(*element.clone());
+            }
+        }
+        Type::Array { element: inner, .. } | Type::Pointer { target: inner, .. } => {
+            collect_slices(inner, elements)
+        }
+        Type::Block { input, output, .. } => input
+            .iter()
+            .flatten()
+            .chain(output.as_deref())
+            .for_each(|t| collect_slices(t, elements)),
+        _ => {}
+    }
+}
+
+fn extern_declaration(global: &CNode) -> CNode {
+    let (type_identifier, identifier) = match global {
+        CNode::VarDeclaration(data) => (&data.type_identifier, &data.identifier),
+        CNode::VarDeclAssignment(data) => (&data.type_identifier, &data.identifier),
+        _ => panic!("Expected a global variable"),
+    };
+
+    CNode::ExternVarDeclaration(var_decl::Data {
+        type_identifier: type_identifier.clone(),
+        identifier: identifier.clone(),
+    })
+}
+
+impl<'a> Lowering<'a> {
+    fn identifier_name(&self, ident_node: usize) -> &'a str {
+        match &self.file_ast.nodes[ident_node].kind {
+            AstNodeKind::Expression(Expression::Identifier { index }) => {
+                &self.file_ast.identifiers[*index].name
+            }
+            _ => panic!("Expected an identifier"),
+        }
+    }
+
+    // Name in C of the symbol an identifier node refers to. main is the entry point when it is a
+    // block, a variable of that name would clash with it.
+    fn c_identifier(&self, ident_node: usize) -> Box<str> {
+        let symbol = self.symbol(ident_node);
+        if let Some(name) = self.lifted.get(&symbol) {
+            return name.clone();
+        }
+
+        let name = self.identifier_name(ident_node);
+        let declaration = self.file_ast.identifiers[symbol].declaration_idx;
+        match self.file_ast.nodes[declaration].kind {
+            AstNodeKind::Statement(Statement::Use { .. }) => {
+                return self.root_c_name(self.used_module(ident_node), name)
+            }
+            AstNodeKind::Statement(Statement::Declaration { .. })
+                if self.file_ast.root_nodes.contains(&declaration)
+                    && self.module + 1 < self.project.modules.len() =>
+            {
+                return self.root_c_name(self.module, name)
+            }
+            _ => {}
+        }
+
+        match name {
+            "main" if !matches!(self.identifier_type(ident_node), Type::Block { .. }) => {
+                Box::from("llc_main")
+            }
+            _ => c_name::mangle(name),
+        }
+    }
+
+    fn symbol(&self, ident_node: usize) -> usize {
+        match self.file_ast.nodes[ident_node].kind {
+            AstNodeKind::Expression(Expression::Identifier { index }) => index,
+            _ => panic!("Expected an identifier"),
+        }
+    }
+
+    // Module imported by the use statement declaring an identifier, the module itself or the
+    // one of an imported declaration.
+    fn used_module(&self, ident_node: usize) -> usize {
+        let declaration = self.file_ast.identifiers[self.symbol(ident_node)].declaration_idx;
+        self.project.modules[self.module]
+            .used_module(declaration)
+            .expect("Expected a use statement")
+    }
+
+    // Root declarations of all modules share the C namespace, those of the modules used by the
+    // entry one are prefixed by the path of their module, shapes__area for the block area of
+    // shapes. Extern blocks keep the name C knows them by.
+    fn root_c_name(&self, module: usize, name: &str) -> Box<str> {
+        let file_ast = &self.project.modules[module].file_ast;
+        let is_extern = file_ast
+            .root_nodes
+            .iter()
+            .any(|n| match file_ast.nodes[*n].kind {
+                AstNodeKind::Statement(Statement::Declaration {
+                    ident_index,
+                    exp_index: Some(exp_index),
+                    ..
+                }) => {
+                    matches!(
+                        file_ast.nodes[exp_index].kind,
+                        AstNodeKind::Expression(Expression::Extern { .. })
+                    ) && matches!(
+                        file_ast.nodes[ident_index].kind,
+                        AstNodeKind::Expression(Expression::Identifier { index })
+                            if *file_ast.identifiers[index].name == *name
+                    )
+                }
+                _ => false,
+            });
+
+        match is_extern {
+            true => c_name::mangle(name),
+            false => c_name::qualified(&exports::qualify(&self.project.qualifier(module), name)),
+        }
+    }
+
+    // Name in C of the struct or enum named by a type node, Name or module::Name.
+    fn type_c_name(&self, type_node: usize) -> Box<str> {
+        match self.file_ast.nodes[type_node].kind {
+            AstNodeKind::Expression(Expression::Path { module, name }) => {
+                let qualifier = self.project.qualifier(self.used_module(module));
+                c_name::qualified(&exports::qualify(&qualifier, self.identifier_name(name)))
+            }
+            _ => match self.identifier_type(type_node) {
+                Type::Struct { name } | Type::Enum { name } => c_name::qualified(name),
+                _ => panic!("Expected a type"),
+            },
+        }
+    }
+
+    // Identifier node naming a declaration, m::f is named by f.
+    fn path_name(&self, node: usize) -> usize {
+        match self.file_ast.nodes[node].kind {
+            AstNodeKind::Expression(Expression::Path { name, .. }) => name,
+            _ => node,
+        }
+    }
+
+    fn identifier_type(&self, ident_node: usize) -> &'a Type {
+        let identifier = match &self.file_ast.nodes[ident_node].kind {
+            AstNodeKind::Expression(Expression::Identifier { index }) => {
+                &self.file_ast.identifiers[*index]
+            }
+            _ => panic!("Expected an identifier"),
+        };
+
+        match &identifier.type_state {
+            TypeState::Ok(t) => t,
+            _ => panic!("Expected a type checked identifier"),
+        }
+    }
+
+    // Structs and enums by their qualified name, both are lowered before the globals and
+    // functions using them.
+    fn type_definitions(&self) -> Vec<(Box<str>, usize)> {
+        let file_ast = self.file_ast;
+        let mut definitions = vec![];
+
+        for index in file_ast.root_nodes.iter() {
+            if let AstNodeKind::Statement(Statement::Declaration {
+                ident_index,
+                exp_index: Some(exp_index),
+                ..
+            }) = &file_ast.nodes[*index].kind
+            {
+                if let AstNodeKind::Expression(
+                    Expression::StructDefinition { .. } | Expression::EnumDefinition { .. },
+                ) = file_ast.nodes[*exp_index].kind
+                {
+                    definitions.push((
+                        exports::qualify(
+                            &self.project.qualifier(self.module),
+                            self.identifier_name(*ident_index),
+                        ),
+                        *exp_index,
+                    ));
+                }
+            }
+        }
+
+        definitions
+    }
+
+    // C needs the types used by value in the fields of a struct or the payloads of an enum
+    // to be defined before it.
+    fn lower_type_definition(
+        &mut self,
+        name: &str,
+        definitions: &[(Box<str>, usize)],
+        lowered: &mut Vec<Box<str>>,
+    ) {
+        if lowered.iter().any(|l| **l == *name) {
+            return;
+        }
+        lowered.push(Box::from(name));
+
+        let file_ast = self.file_ast;
+        // Types imported from other modules are defined by their headers.
+        let definition = match definitions.iter().find(|(n, _)| **n == *name) {
+            Some((_, d)) => *d,
+            None => return,
+        };
+        let fields = match &file_ast.nodes[definition].kind {
+            AstNodeKind::Expression(Expression::StructDefinition { fields }) => fields,
+            AstNodeKind::Expression(Expression::EnumDefinition { variants }) => {
+                return self.lower_enum(name, variants, definitions, lowered)
+            }
+            _ => panic!("Expected a type definition"),
+        };
+
+        let mut c_fields = vec![];
+        for f in fields {
+            let (ident_index, llc_type) = match &file_ast.nodes[*f].kind {
+                AstNodeKind::Statement(Statement::Field {
+                    ident_index,
+                    llc_type,
+                }) => (*ident_index, llc_type),
+                _ => panic!("Expected a struct field"),
+            };
+
+            if let Type::Struct { name } | Type::Enum { name } = llc_type {
+                self.lower_type_definition(name, definitions, lowered);
+            }
+
+            let type_identifier = match c_type::from_type(llc_type) {
+                Some(t) => t,
+                None => {
+                    self.errors.push(LoweringError::new(
+                        file_ast.nodes[*f].position,
+                        &format!("Fields of type {} are not supported yet.", llc_type),
+                    ));
+                    continue;
+                }
+            };
+
+            c_fields.push(StructField {
+                type_identifier,
+                identifier: c_name::mangle(self.identifier_name(ident_index)),
+            });
+        }
+
+        self.structs.push(CNode::StructDefinition(struct_def::Data {
+            identifier: c_name::qualified(name),
+            fields: c_fields,
+        }));
+    }
+
+    fn lower_enum(
+        &mut self,
+        name: &str,
+        variants: &[usize],
+        definitions: &[(Box<str>, usize)],
+        lowered: &mut Vec<Box<str>>,
+    ) {
+        let file_ast = self.file_ast;
+        let mut c_variants = vec![];
+
+        for v in variants {
+            let (ident_index, payload) = match &file_ast.nodes[*v].kind {
+                AstNodeKind::Statement(Statement::Variant {
+                    ident_index,
+                    payload,
+                }) => (*ident_index, payload),
+                _ => panic!("Expected an enum variant"),
+            };
+
+            let mut type_identifiers = vec![];
+            for llc_type in payload {
+                if let Type::Struct { name } | Type::Enum { name } = llc_type {
+                    self.lower_type_definition(name, definitions, lowered);
+                }
+
+                match c_type::from_type(llc_type) {
+                    Some(t) => type_identifiers.push(t),
+                    None => self.errors.push(LoweringError::new(
+                        file_ast.nodes[*v].position,
+                        &format!("Variant values of type {} are not supported yet.", llc_type),
+                    )),
+                }
+            }
+
+            c_variants.push(Variant {
+                identifier: c_name::mangle(self.identifier_name(ident_index)),
+                type_identifiers,
+            });
+        }
+
+        self.structs.push(CNode::TaggedUnion(tagged_union::Data {
+            identifier: c_name::qualified(name),
+            variants: c_variants,
+        }));
+    }
+
+    // Returns a C node when the declaration lives in the current scope, blocks are
+    // lifted to their own C function instead.
+    fn lower_declaration(&mut self, ident_index: usize, exp_index: Option<usize>) -> Option<CNode> {
+        let file_ast = self.file_ast;
+        let name = self.identifier_name(ident_index);
+
+        let llc_type = self.identifier_type(ident_index);
+
+        if let Some(AstNodeKind::Expression(Expression::Block {
+            signature,
+            statements,
+        })) = exp_index.map(|e| &file_ast.nodes[e].kind)
+        {
+            let name = match self.enclosing.is_empty() {
+                true => self.c_identifier(ident_index),
+                false => self.lift(ident_index),
+            };
+            self.lower_function(&name, llc_type, exp_index.unwrap(), signature, statements);
+            return None;
+        }
+
+        if let Some(AstNodeKind::Expression(Expression::Extern { signature, header })) =
+            exp_index.map(|e| &file_ast.nodes[e].kind)
+        {
+            self.lower_extern(name, llc_type, exp_index.unwrap(), signature, header);
+            return None;
+        }
+
+        let type_identifier = match c_type::from_type(llc_type) {
+            Some(t) => t,
+            None => {
+                self.errors.push(LoweringError::new(
+                    self.file_ast.nodes[ident_index].position,
+                    &format!(
+                        "Values of type {} cannot be stored in a variable yet.",
+                        llc_type
+                    ),
+                ));
+                return None;
+            }
+        };
+
+        let exp_index = match exp_index {
+            Some(e) => e,
+            None => {
+                return Some(CNode::VarDeclaration(var_decl::Data {
+                    type_identifier,
+                    identifier: self.c_identifier(ident_index),
+                }))
+            }
+        };
+
+        if !self.check_array_value(exp_index, Some(llc_type)) {
+            return None;
+        }
+
+        Some(CNode::VarDeclAssignment(var_declassign::Data {
+            type_identifier,
+            identifier: self.c_identifier(ident_index),
+            value: self.lower_expression(exp_index)?,
+        }))
+    }
+
+    // C functions are all at the root of the file, a block declared in another one is named
+    // after it, f__g for the block g of f, and numbered when the name is already taken.
+    fn lift(&mut self, ident_node: usize) -> Box<str> {
+        let prefix = format!(
+            "{}__{}",
+            self.enclosing.last().unwrap(),
+            self.identifier_name(ident_node)
+        );
+        let roots: Vec<Box<str>> = self
+            .file_ast
+            .root_nodes
+            .iter()
+            .filter_map(|n| match self.file_ast.nodes[*n].kind {
+                AstNodeKind::Statement(Statement::Declaration { ident_index, .. }) => {
+                    Some(self.c_identifier(ident_index))
+                }
+                _ => None,
+            })
+            .collect();
+
+        let mut name: Box<str> = Box::from(prefix.as_str());
+        let mut count = 1;
+        while roots.contains(&name) || self.lifted.values().any(|l| *l == name) {
+            count += 1;
+            name = Box::from(format!("{}_{}", prefix, count));
+        }
+
+        let symbol = self.symbol(ident_node);
+        self.lifted.insert(symbol, name.clone());
+        name
+    }
+
+    fn lower_function(
+        &mut self,
+        name: &str,
+        llc_type: &Type,
+        block_index: usize,
+        signature: &Option<Signature>,
+        statements: &[usize],
+    ) {
+        let is_entry_point = name == "main";
+        let output = match llc_type {
+            Type::Block { output, .. } => output,
+            _ => panic!("Expected a block type"),
+        };
+
+        let type_identifier = match (&output, is_entry_point) {
+            (None, true) => CType::Named(Box::from("int")),
+            (None, false) => CType::Named(Box::from("void")),
+            (Some(t), _) => match c_type::value_type(t) {
+                Some(t) => t,
+                None => {
+                    self.errors.push(LoweringError::new(
+                        self.file_ast.nodes[block_index].position,
+                        &format!("Blocks cannot return values of type {} yet.", t),
+                    ));
+                    return;
+                }
+            },
+        };
+
+        let params = match signature {
+            Some(signature) => self.lower_params(signature),
+            None => vec![],
+        };
+
+        self.enclosing.push(Box::from(name));
+        let mut body = self.lower_statements(statements);
+        self.enclosing.pop();
+        if is_entry_point && output.is_none() {
+            body.push(CNode::FuncReturn(func_return::Data {
+                value: Some(node::Expression::Litteral(Box::from("0"))),
+            }));
+        }
+
+        self.declarations
+            .push(CNode::FuncDeclaration(func_decl::Data {
+                type_identifier: type_identifier.clone(),
+                identifier: Box::from(name),
+                params: params
+                    .iter()
+                    .map(|p| FuncParam {
+                        type_identifier: p.type_identifier.clone(),
+                        identifier: p.identifier.clone(),
+                    })
+                    .collect(),
+                variadic: false,
+            }));
+
+        self.functions.extend(self.line_directive(block_index));
+        self.functions.push(CNode::FuncDefinition(func_def::Data {
+            type_identifier,
+            identifier: Box::from(name),
+            params,
+            body,
+        }));
+    }
+
+    fn lower_params(&mut self, signature: &Signature) -> Vec<FuncParam> {
+        let file_ast = self.file_ast;
+        let mut params = vec![];
+
+        for p in signature.params.iter() {
+            let (ident_index, llc_type) = match &file_ast.nodes[*p].kind {
+                AstNodeKind::Statement(Statement::Parameter {
+                    ident_index,
+                    llc_type,
+                }) => (*ident_index, llc_type),
+                _ => panic!("Expected a parameter"),
+            };
+
+            let type_identifier = match c_type::value_type(llc_type) {
+                Some(t) => t,
+                None => {
+                    self.errors.push(LoweringError::new(
+                        file_ast.nodes[*p].position,
+                        &format!("Parameters of type {} are not supported yet.", llc_type),
+                    ));
+                    continue;
+                }
+            };
+
+            params.push(FuncParam {
+                type_identifier,
+                identifier: self.c_identifier(ident_index),
+            });
+        }
+
+        params
+    }
+
+    // Externs are defined by C code linked with the program. The header declaring them is
+    // included when there is one, a prototype is written from the signature otherwise. A
+    // header of the C library included by the project is included rather than a prototype
+    // of one of its functions, which may not match its declaration.
+    fn lower_extern(
+        &mut self,
+        name: &str,
+        llc_type: &Type,
+        extern_index: usize,
+        signature: &Signature,
+        header: &Option<Box<str>>,
+    ) {
+        let library_header = c_library::header(name)
+            .map(Box::from)
+            .filter(|h| self.project_headers.contains(h));
+        if let Some(header) = header.clone().or(library_header) {
+            if !self.headers.contains(&header) {
+                self.headers.push(header);
+            }
+            return;
+        }
+
+        // C needs a named parameter before the variable ones.
+        if signature.variadic && signature.params.is_empty() {
+            self.errors.push(LoweringError::new(
+                self.file_ast.nodes[extern_index].position,
+                "Variadic extern blocks need a parameter before ... or a header declaring them.",
+            ));
+            return;
+        }
+
+        let output = match llc_type {
+            Type::Block { output, .. } => output,
+            _ => panic!("Expected a block type"),
+        };
+
+        let type_identifier = match c_type::output_type(output.as_deref()) {
+            Some(t) => t,
+            None => {
+                self.errors.push(LoweringError::new(
+                    self.file_ast.nodes[extern_index].position,
+                    &format!(
+                        "Blocks cannot return values of type {} yet.",
+                        output.as_deref().unwrap()
+                    ),
+                ));
+                return;
+            }
+        };
+
+        let params = self.lower_params(signature);
+        self.declarations
+            .push(CNode::FuncDeclaration(func_decl::Data {
+                type_identifier,
+                identifier: Box::from(name),
+                params,
+                variadic: signature.variadic,
+            }));
+    }
+
+    fn lower_statements(&mut self, statements: &[usize]) -> Vec<CNode> {
+        let mut body = vec![];
+
+        for index in statements {
+            self.lower_statement(*index, &mut body);
+        }
+
+        body
+    }
+
+    fn line_directive(&self, index: usize) -> Option<CNode> {
+        Some(CNode::LineDirective(line_directive::Data {
+            line: self.file_ast.nodes[index].position.begin.row,
+            file: Box::from(self.line_file?),
+        }))
+    }
+
+    fn lower_statement(&mut self, index: usize, body: &mut Vec<CNode>) {
+        let file_ast = self.file_ast;
+        body.extend(self.line_directive(index));
+        match &file_ast.nodes[index].kind {
+            AstNodeKind::Statement(Statement::Declaration {
+                ident_index,
+                exp_index: Some(exp_index),
+                ..
+            }) if self.is_branching(*exp_index) => {
+                if !self.check_array_value(*exp_index, Some(self.identifier_type(*ident_index))) {
+                    return;
+                }
+
+                let name = self.c_identifier(*ident_index);
+                let type_identifier = match c_type::from_type(self.identifier_type(*ident_index)) {
+                    Some(t) => t,
+                    None => {
+                        self.errors.push(LoweringError::new(
+                            file_ast.nodes[*ident_index].position,
+                            &format!(
+                                "Values of type {} cannot be stored in a variable yet.",
+                                self.identifier_type(*ident_index)
+                            ),
+                        ));
+                        return;
+                    }
+                };
+
+                body.push(CNode::VarDeclaration(var_decl::Data {
+                    type_identifier,
+                    identifier: name,
+                }));
+                body.extend(self.lower_branching(*exp_index, BranchValue::Assign(*ident_index)));
+            }
+            AstNodeKind::Statement(Statement::Declaration {
+                ident_index,
+                exp_index,
+                ..
+            }) => body.extend(self.lower_declaration(*ident_index, *exp_index)),
+            AstNodeKind::Statement(Statement::Assignment { target, .. })
+                if matches!(self.expression_type(*target), Some(Type::Array { .. })) =>
+            {
+                self.errors.push(LoweringError::new(
+                    file_ast.nodes[index].position,
+                    "Arrays cannot be assigned yet, assign their elements instead.",
+                ));
+            }
+            AstNodeKind::Statement(Statement::Assignment { target, exp_index })
+                if self.is_branching(*exp_index) =>
+            {
+                body.extend(self.lower_branching(*exp_index, BranchValue::Assign(*target)));
+            }
+            AstNodeKind::Statement(Statement::Assignment { target, exp_index }) => {
+                let target = self.lower_expression(*target);
+                let value = self.lower_expression(*exp_index);
+                if let (Some(target), Some(value)) = (target, value) {
+                    body.push(CNode::VarAssignment(var_assign::Data { target, value }));
+                }
+            }
+            AstNodeKind::Statement(Statement::Return { value: Some(v) })
+                if self.is_branching(*v) =>
+            {
+                body.extend(self.lower_branching(*v, BranchValue::Return));
+            }
+            AstNodeKind::Statement(Statement::Return { value }) => {
+                let value = match value {
+                    Some(v) => match self.lower_expression(*v) {
+                        Some(v) => Some(v),
+                        None => return,
+                    },
+                    None => None,
+                };
+
+                body.push(CNode::FuncReturn(func_return::Data { value }));
+            }
+            AstNodeKind::Statement(Statement::While {
+                condition,
+                statements,
+            }) => {
+                let condition = self.lower_expression(*condition);
+                let loop_body = self.lower_loop_body(statements);
+                if let Some(condition) = condition {
+                    body.push(CNode::WhileLoop(while_loop::Data {
+                        condition,
+                        body: loop_body,
+                    }));
+                }
+            }
+            AstNodeKind::Statement(Statement::For {
+                ident_index,
+                start,
+                end,
+                statements,
+            }) => {
+                let llc_type = self.identifier_type(*ident_index);
+                let type_identifier =
+                    c_type::from_type(llc_type).expect("Expected an integer type");
+                let start = self.lower_expression(*start);
+                // The end is evaluated once, before the first iteration, like in the source. It is
+                // stored in a temporary unless it is a literal.
+                let end = self.lower_expression(*end).map(|end| match end {
+                    node::Expression::Litteral(_) => end,
+                    value => {
+                        let identifier: Box<str> = Box::from(format!("_end{}", index));
+                        body.push(CNode::VarDeclAssignment(var_declassign::Data {
+                            type_identifier: type_identifier.clone(),
+                            identifier: identifier.clone(),
+                            value,
+                        }));
+                        node::Expression::Variable(identifier)
+                    }
+                });
+                let loop_body = self.lower_loop_body(statements);
+
+                if let (Some(start), Some(end)) = (start, end) {
+                    body.push(CNode::ForLoop(for_loop::Data {
+                        type_identifier,
+                        identifier: self.c_identifier(*ident_index),
+                        start,
+                        end,
+                        body: loop_body,
+                    }));
+                }
+            }
+            AstNodeKind::Statement(Statement::Break) if self.in_switch => {
+                self.errors.push(LoweringError::new(
+                    file_ast.nodes[index].position,
+                    "break inside of a match arm is not supported yet.",
+                ));
+            }
+            AstNodeKind::Statement(Statement::Break) => body.push(CNode::Break),
+            AstNodeKind::Statement(Statement::Continue) => body.push(CNode::Continue),
+            AstNodeKind::Statement(Statement::Expression) => {}
+            AstNodeKind::Statement(
+                Statement::Parameter { .. }
+                | Statement::Field { .. }
+                | Statement::FieldValue { .. }
+                | Statement::Variant { .. }
+                | Statement::MatchArm { .. }
+                | Statement::Use { .. },
+            ) => self.errors.push(LoweringError::new(
+                file_ast.nodes[index].position,
+                &format!(
+                    "{} statements cannot be lowered in a block.",
+                    file_ast.nodes[index].name()
+                ),
+            )),
+            AstNodeKind::Expression(Expression::If { .. } | Expression::Match { .. }) => {
+                body.extend(self.lower_branching(index, BranchValue::Discard));
+            }
+            AstNodeKind::Expression(_) => {
+                if let Some(exp) = self.lower_expression(index) {
+                    body.push(CNode::Expression(exp));
+                }
+            }
+        }
+    }
+
+    // Branch values are single expressions when the if can be lowered to a ternary.
+    fn branch_value(&self, statements: &[usize]) -> Option<usize> {
+        match statements {
+            [value] => match self.file_ast.nodes[*value].kind {
+                AstNodeKind::Expression(_) => Some(*value),
+                AstNodeKind::Statement(_) => None,
+            },
+            _ => None,
+        }
+    }
+
+    // If expressions whose branches hold more than a value cannot be C expressions, the
+    // statement using them is lowered to an if statement storing the branch values instead.
+    // Match expressions are always lowered to a switch.
+    fn is_branching(&self, index: usize) -> bool {
+        match &self.file_ast.nodes[index].kind {
+            AstNodeKind::Expression(Expression::Match { .. }) => true,
+            AstNodeKind::Expression(Expression::If {
+                then_statements,
+                else_statements: Some(else_statements),
+                ..
+            }) => [then_statements, else_statements].iter().any(|branch| {
+                self.branch_value(branch)
+                    .is_none_or(|v| self.is_branching(v))
+            }),
+            _ => false,
+        }
+    }
+
+    fn lower_branching(&mut self, index: usize, value: BranchValue) -> Vec<CNode> {
+        match &self.file_ast.nodes[index].kind {
+            AstNodeKind::Expression(Expression::Match { .. }) => self.lower_match(index, value),
+            _ => self.lower_if(index, value).into_iter().collect(),
+        }
+    }
+
+    fn lower_if(&mut self, index: usize, value: BranchValue) -> Option<CNode> {
+        let file_ast = self.file_ast;
+        let (condition, then_statements, else_statements) = match &file_ast.nodes[index].kind {
+            AstNodeKind::Expression(Expression::If {
+                condition,
+                then_statements,
+                else_statements,
+            }) => (*condition, then_statements, else_statements),
+            _ => panic!("Expected an if expression"),
+        };
+
+        let condition = self.lower_expression(condition);
+        let body = self.lower_branch(then_statements, value);
+        let else_body = else_statements
+            .as_ref()
+            .map(|statements| self.lower_branch(statements, value));
+
+        Some(CNode::IfElse(if_else::Data {
+            condition: condition?,
+            body,
+            else_body,
+        }))
+    }
+
+    fn lower_branch(&mut self, statements: &[usize], value: BranchValue) -> Vec<CNode> {
+        let (last, statements) = match (value, statements.split_last()) {
+            (BranchValue::Discard, _) | (_, None) => return self.lower_statements(statements),
+            (_, Some((last, statements))) => (*last, statements),
+        };
+
+        let mut body = self.lower_statements(statements);
+
+        if self.is_branching(last) {
+            body.extend(self.lower_branching(last, value));
+            return body;
+        }
+
+        let exp = match self.lower_expression(last) {
+            Some(exp) => exp,
+            None => return body,
+        };
+
+        match value {
+            BranchValue::Assign(target) => {
+                if let Some(target) = self.lower_expression(target) {
+                    body.push(CNode::VarAssignment(var_assign::Data {
+                        target,
+                        value: exp,
+                    }));
+                }
+            }
+            BranchValue::Return => {
+                body.push(CNode::FuncReturn(func_return::Data { value: Some(exp) }))
+            }
+            BranchValue::Discard => panic!("Expected a branch value"),
+        }
+        body
+    }
+
+    // A break in a loop body leaves the loop, even when the loop is inside of a match arm.
+    fn lower_loop_body(&mut self, statements: &[usize]) -> Vec<CNode> {
+        let in_switch = std::mem::replace(&mut self.in_switch, false);
+        let body = self.lower_statements(statements);
+        self.in_switch = in_switch;
+        body
+    }
+
+    // The matched value is read once for the tag and once per binding, values that are not
+    // variables are stored in a temporary first.
+    fn lower_match(&mut self, index: usize, value: BranchValue) -> Vec<CNode> {
+        let file_ast = self.file_ast;
+        let (matched, arms) = match &file_ast.nodes[index].kind {
+            AstNodeKind::Expression(Expression::Match { value, arms }) => (*value, arms),
+            _ => panic!("Expected a match expression"),
+        };
+
+        let mut body = vec![];
+        let enum_name = match self.expression_type(matched) {
+            Some(Type::Enum { name }) => name,
+            _ => {
+                self.errors.push(LoweringError::new(
+                    file_ast.nodes[matched].position,
+                    "Only variables, fields, calls and variants can be matched yet.",
+                ));
+                return body;
+            }
+        };
+
+        // Identifiers of the source cannot start with an underscore, the temporary cannot
+        // shadow one of them.
+        let subject = match &file_ast.nodes[matched].kind {
+            AstNodeKind::Expression(Expression::Identifier { .. }) => {
+                Some(self.c_identifier(matched))
+            }
+            _ => self.lower_expression(matched).map(|value| {
+                let identifier: Box<str> = Box::from(format!("_match{}", index));
+                body.push(CNode::VarDeclAssignment(var_declassign::Data {
+                    type_identifier: CType::Named(c_name::qualified(&enum_name)),
+                    identifier: identifier.clone(),
+                    value,
+                }));
+                identifier
+            }),
+        };
+
+        let in_switch = std::mem::replace(&mut self.in_switch, true);
+        let mut cases = vec![];
+
+        for arm in arms {
+            let (pattern, statements) = match &file_ast.nodes[*arm].kind {
+                AstNodeKind::Statement(Statement::MatchArm {
+                    pattern,
+                    statements,
+                }) => (pattern, statements),
+                _ => panic!("Expected a match arm"),
+            };
+
+            let (label, mut case_body) = match pattern {
+                Pattern::Wildcard => (None, vec![]),
+                Pattern::Variant {
+                    ident_index,
+                    bindings,
+                } => {
+                    let variant = c_name::mangle(self.identifier_name(*ident_index));
+                    let case_body = match &subject {
+                        Some(subject) => self.lower_bindings(subject, &variant, bindings),
+                        None => vec![],
+                    };
+
+                    (
+                        Some(tagged_union::tag_identifier(
+                            &c_name::qualified(&enum_name),
+                            &variant,
+                        )),
+                        case_body,
+                    )
+                }
+            };
+
+            case_body.extend(self.lower_branch(statements, value));
+            cases.push(Case {
+                label,
+                body: case_body,
+            });
+        }
+
+        self.in_switch = in_switch;
+
+        if let Some(subject) = subject {
+            body.push(CNode::Switch(switch::Data {
+                value: node::Expression::FieldAccess(field_access::Data {
+                    object: Box::new(node::Expression::Variable(subject)),
+                    field: Box::from("tag"),
+                    through_pointer: false,
+                }),
+                cases,
+            }));
+        }
+        body
+    }
+
+    // Each binding is a copy of its payload value: T name = subject.as.Variant._0;
+    fn lower_bindings(
+        &mut self,
+        subject: &str,
+        variant: &str,
+        bindings: &[Option<usize>],
+    ) -> Vec<CNode> {
+        let mut body = vec![];
+
+        for (i, binding) in bindings.iter().enumerate() {
+            let binding = match binding {
+                Some(b) => *b,
+                None => continue,
+            };
+
+            let llc_type = self.identifier_type(binding);
+            let type_identifier = match c_type::value_type(llc_type) {
+                Some(t) => t,
+                None => {
+                    self.errors.push(LoweringError::new(
+                        self.file_ast.nodes[binding].position,
+                        &format!(
+                            "Values of type {} cannot be stored in a variable yet.",
+                            llc_type
+                        ),
+                    ));
+                    continue;
+                }
+            };
+
+            let payload = [
+                Box::from("as"),
+                Box::from(variant),
+                Box::from(format!("_{}", i)),
+            ]
+            .into_iter()
+            .fold(
+                node::Expression::Variable(Box::from(subject)),
+                |object, field| {
+                    node::Expression::FieldAccess(field_access::Data {
+                        object: Box::new(object),
+                        field,
+                        through_pointer: false,
+                    })
+                },
+            );
+
+            body.push(CNode::VarDeclAssignment(var_declassign::Data {
+                type_identifier,
+                identifier: self.c_identifier(binding),
+                value: payload,
+            }));
+        }
+
+        body
+    }
+
+    fn slice_identifier(&mut self, element: &Type, index: usize) -> Option<Box<str>> {
+        let identifier = c_type::slice_identifier(element);
+        if identifier.is_none() {
+            self.errors.push(LoweringError::new(
+                self.file_ast.nodes[index].position,
+                &format!("Slices of {} are not supported yet.", element),
+            ));
+        }
+        identifier
+    }
+
+    // C arrays can only be initialized by an initializer list, they cannot be copied.
+    fn check_array_value(&mut self, value: usize, llc_type: Option<&Type>) -> bool {
+        let is_array = match llc_type {
+            Some(t) => matches!(t, Type::Array { .. }),
+            None => matches!(self.expression_type(value), Some(Type::Array { .. })),
+        };
+
+        if !is_array
+            || matches!(
+                self.file_ast.nodes[value].kind,
+                AstNodeKind::Expression(Expression::ArrayLiteral { .. })
+            )
+        {
+            return true;
+        }
+
+        self.errors.push(LoweringError::new(
+            self.file_ast.nodes[value].position,
+            "Arrays can only be initialized by an array literal yet.",
+        ));
+        false
+    }
+
+    // Slice types used by the module, the slices of its elements come before a slice.
+    fn slice_types(&self) -> Vec<CType> {
+        let file_ast = self.file_ast;
+        let mut types = vec![];
+
+        for identifier in file_ast.identifiers.iter() {
+            if let TypeState::Ok(t) = &identifier.type_state {
+                collect_slices(t, &mut types);
+            }
+        }
+
+        for node in file_ast.nodes.iter() {
+            match &node.kind {
+                AstNodeKind::Statement(Statement::Field { llc_type, .. }) => {
+                    collect_slices(llc_type, &mut types)
+                }
+                AstNodeKind::Statement(Statement::Variant { payload, .. }) => {
+                    payload.iter().for_each(|t| collect_slices(t, &mut types))
+                }
+                AstNodeKind::Expression(Expression::Slicing {
+                    sequence: Some(Type::Array { element, .. } | Type::Slice { element }),
+                    ..
+                }) => collect_slices(
+                    &Type::Slice {
+                        element: element.clone(),
+                    },
+                    &mut types,
+                ),
+                _ => {}
+            }
+        }
+
+        let mut slices: Vec<CType> = vec![];
+        for element in types.iter().filter_map(c_type::from_type) {
+            if !slices
+                .iter()
+                .any(|s| slice::identifier(s) == slice::identifier(&element))
+            {
+                slices.push(element);
+            }
+        }
+        slices
+    }
+
+    fn uses_index(&self) -> bool {
+        self.file_ast.nodes.iter().any(|n| {
+            matches!(
+                n.kind,
+                AstNodeKind::Expression(Expression::Index { .. } | Expression::Slicing { .. })
+            )
+        })
+    }
+
+    // The type of the expressions that can be matched, the lowering has no type information
+    // beyond the one of the declared identifiers.
+    fn expression_type(&self, index: usize) -> Option<Type> {
+        let file_ast = self.file_ast;
+        match &file_ast.nodes[index].kind {
+            AstNodeKind::Expression(Expression::Identifier { .. }) => {
+                Some(self.identifier_type(index).clone())
+            }
+            AstNodeKind::Expression(Expression::EnumVariant { enum_index, .. }) => {
+                Some(self.identifier_type(*enum_index).clone())
+            }
+            AstNodeKind::Expression(Expression::FunctionCall { callee, .. }) => {
+                match self.identifier_type(self.path_name(*callee)) {
+                    Type::Block {
+                        output: Some(output),
+                        ..
+                    } => Some(*output.clone()),
+                    _ => None,
+                }
+            }
+            AstNodeKind::Expression(Expression::Unary {
+                operator: Operator::Times,
+                operand,
+            }) => match self.expression_type(*operand)? {
+                Type::Pointer { target, .. } => Some(*target),
+                _ => None,
+            },
+            AstNodeKind::Expression(Expression::Index {
+                sequence: Some(Type::Array { element, .. } | Type::Slice { element }),
+                ..
+            }) => Some(*element.clone()),
+            AstNodeKind::Expression(Expression::FieldAccess { object, field }) => {
+                let name = match self.expression_type(*object)? {
+                    Type::Struct { name } => name,
+                    _ => return None,
+                };
+
+                let definition = self
+                    .type_definitions()
+                    .into_iter()
+                    .find(|(n, _)| **n == *name)?
+                    .1;
+                let fields = match &file_ast.nodes[definition].kind {
+                    AstNodeKind::Expression(Expression::StructDefinition { fields }) => fields,
+                    _ => return None,
+                };
+
+                let field_name = self.identifier_name(*field);
+                fields.iter().find_map(|f| match &file_ast.nodes[*f].kind {
+                    AstNodeKind::Statement(Statement::Field {
+                        ident_index,
+                        llc_type,
+                    }) if self.identifier_name(*ident_index) == field_name => {
+                        Some(self.user_type(llc_type))
+                    }
+                    _ => None,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    // Field types are the ones written in the source, where enums are named like structs.
+    fn user_type(&self, llc_type: &Type) -> Type {
+        match llc_type {
+            Type::Struct { name } => {
+                match self.type_definitions().iter().find(|(n, _)| **n == **name) {
+                    Some((_, d))
+                        if matches!(
+                            self.file_ast.nodes[*d].kind,
+                            AstNodeKind::Expression(Expression::EnumDefinition { .. })
+                        ) =>
+                    {
+                        Type::Enum { name: name.clone() }
+                    }
+                    _ => llc_type.clone(),
+                }
+            }
+            _ => llc_type.clone(),
+        }
+    }
+
+    fn lower_expression(&mut self, index: usize) -> Option<node::Expression> {
+        let file_ast = self.file_ast;
+        let node = &file_ast.nodes[index];
+        match &node.kind {
+            AstNodeKind::Expression(Expression::Identifier { .. }) => {
+                Some(node::Expression::Variable(self.c_identifier(index)))
+            }
+            AstNodeKind::Expression(Expression::Literal(lit)) => {
+                Some(node::Expression::Litteral(c_type::literal(&lit.value)))
+            }
+            AstNodeKind::Expression(Expression::FunctionCall { callee, args }) => {
+                let mut params = vec![];
+                for a in args {
+                    params.push(self.lower_expression(*a)?);
+                }
+
+                Some(node::Expression::FunctionCall(func_call::Data {
+                    identifier: self.c_identifier(self.path_name(*callee)),
+                    params,
+                }))
+            }
+            AstNodeKind::Expression(Expression::Binary { operator, lhs, rhs }) => {
+                Some(node::Expression::BinaryOperation(binary_op::Data {
+                    operator: operator::binary(*operator),
+                    lhs: Box::new(self.lower_expression(*lhs)?),
+                    rhs: Box::new(self.lower_expression(*rhs)?),
+                }))
+            }
+            AstNodeKind::Expression(Expression::Unary { operator, operand }) => {
+                Some(node::Expression::UnaryOperation(unary_op::Data {
+                    operator: operator::unary(*operator),
+                    operand: Box::new(self.lower_expression(*operand)?),
+                }))
+            }
+            AstNodeKind::Expression(Expression::If {
+                condition,
+                then_statements,
+                else_statements: Some(else_statements),
+            }) if !self.is_branching(index) => {
+                let then_value = self.branch_value(then_statements).unwrap();
+                let else_value = self.branch_value(else_statements).unwrap();
+
+                Some(node::Expression::Ternary(ternary::Data {
+                    condition: Box::new(self.lower_expression(*condition)?),
+                    then_value: Box::new(self.lower_expression(then_value)?),
+                    else_value: Box::new(self.lower_expression(else_value)?),
+                }))
+            }
+            AstNodeKind::Expression(Expression::If { .. }) => {
+                self.errors.push(LoweringError::new(
+                    node.position,
+                    "If expressions with statements in their branches can only be declared, assigned or returned.",
+                ));
+                None
+            }
+            AstNodeKind::Expression(Expression::Block { .. } | Expression::Extern { .. }) => {
+                self.errors.push(LoweringError::new(
+                    node.position,
+                    "Blocks must be declared before they can be used.",
+                ));
+                None
+            }
+            AstNodeKind::Expression(Expression::StructLiteral { type_index, fields }) => {
+                let mut values = vec![];
+                for f in fields {
+                    let (ident_index, exp_index) = match &file_ast.nodes[*f].kind {
+                        AstNodeKind::Statement(Statement::FieldValue {
+                            ident_index,
+                            exp_index,
+                        }) => (*ident_index, *exp_index),
+                        _ => panic!("Expected a field value"),
+                    };
+
+                    if !self.check_array_value(exp_index, None) {
+                        return None;
+                    }
+
+                    values.push(FieldValue {
+                        identifier: c_name::mangle(self.identifier_name(ident_index)),
+                        value: self.lower_expression(exp_index)?,
+                    });
+                }
+
+                Some(node::Expression::StructLiteral(struct_literal::Data {
+                    type_identifier: self.type_c_name(*type_index),
+                    fields: values,
+                }))
+            }
+            // The type checker makes the dereference of references explicit, (*r).x is r->x.
+            AstNodeKind::Expression(Expression::FieldAccess { object, field }) => {
+                let (object, through_pointer) = match file_ast.nodes[*object].kind {
+                    AstNodeKind::Expression(Expression::Unary {
+                        operator: Operator::Times,
+                        operand,
+                    }) => (operand, true),
+                    _ => (*object, false),
+                };
+
+                Some(node::Expression::FieldAccess(field_access::Data {
+                    object: Box::new(self.lower_expression(object)?),
+                    field: c_name::mangle(self.identifier_name(*field)),
+                    through_pointer,
+                }))
+            }
+            AstNodeKind::Expression(Expression::EnumVariant {
+                enum_index,
+                variant,
+                args,
+            }) => {
+                let mut values = vec![];
+                for a in args {
+                    if !self.check_array_value(*a, None) {
+                        return None;
+                    }
+                    values.push(self.lower_expression(*a)?);
+                }
+
+                Some(node::Expression::VariantLiteral(variant_literal::Data {
+                    type_identifier: self.type_c_name(*enum_index),
+                    variant: c_name::mangle(self.identifier_name(*variant)),
+                    values,
+                }))
+            }
+            AstNodeKind::Expression(Expression::Match { .. }) => {
+                self.errors.push(LoweringError::new(
+                    node.position,
+                    "Match expressions can only be declared, assigned or returned.",
+                ));
+                None
+            }
+            // The name of a path refers to the use statement of its module, like the module.
+            AstNodeKind::Expression(Expression::Path { name, .. }) => {
+                Some(node::Expression::Variable(self.c_identifier(*name)))
+            }
+            AstNodeKind::Expression(Expression::ArrayLiteral { elements }) => {
+                let mut values = vec![];
+                for e in elements {
+                    values.push(self.lower_expression(*e)?);
+                }
+
+                Some(node::Expression::ArrayLiteral(array_literal::Data {
+                    values,
+                }))
+            }
+            // Array subscripts check the index against the size of the array, the elements of
+            // a slice are read through a pointer which checks it against the length.
+            AstNodeKind::Expression(Expression::Index {
+                object,
+                index: i,
+                sequence,
+            }) => {
+                let object = self.lower_expression(*object)?;
+                let i = self.lower_expression(*i)?;
+
+                match sequence {
+                    Some(Type::Array { size, .. }) => Some(node::Expression::Index(index::Data {
+                        object: Box::new(object),
+                        index: Box::new(node::Expression::FunctionCall(func_call::Data {
+                            identifier: Box::from("llc_check_index"),
+                            params: vec![
+                                i,
+                                node::Expression::Litteral(Box::from(size.to_string())),
+                            ],
+                        })),
+                    })),
+                    Some(Type::Slice { element }) => {
+                        let slice = self.slice_identifier(element, index)?;
+                        Some(node::Expression::UnaryOperation(unary_op::Data {
+                            operator: unary_op::Operator::Dereference,
+                            operand: Box::new(node::Expression::FunctionCall(func_call::Data {
+                                identifier: slice::at_identifier(&slice),
+                                params: vec![object, i],
+                            })),
+                        }))
+                    }
+                    _ => panic!("Expected a type checked index"),
+                }
+            }
+            // Arrays are sliced through a slice of all their elements.
+            AstNodeKind::Expression(Expression::Slicing {
+                object,
+                start,
+                end,
+                sequence,
+            }) => {
+                let (element, size) = match sequence {
+                    Some(Type::Array { element, size }) => (element, Some(size)),
+                    Some(Type::Slice { element }) => (element, None),
+                    _ => panic!("Expected a type checked slicing"),
+                };
+
+                let slice = self.slice_identifier(element, index)?;
+                let object = self.lower_expression(*object)?;
+                let mut params = vec![match size {
+                    Some(size) => node::Expression::StructLiteral(struct_literal::Data {
+                        type_identifier: slice.clone(),
+                        fields: vec![
+                            FieldValue {
+                                identifier: Box::from("ptr"),
+                                value: object,
+                            },
+                            FieldValue {
+                                identifier: Box::from("len"),
+                                value: node::Expression::Litteral(Box::from(size.to_string())),
+                            },
+                        ],
+                    }),
+                    None => object,
+                }];
+
+                params.push(match start {
+                    Some(s) => self.lower_expression(*s)?,
+                    None => node::Expression::Litteral(Box::from("0")),
+                });
+
+                let identifier = match end {
+                    Some(e) => {
+                        params.push(self.lower_expression(*e)?);
+                        slice::range_identifier(&slice)
+                    }
+                    None => slice::from_identifier(&slice),
+                };
+
+                Some(node::Expression::FunctionCall(func_call::Data {
+                    identifier,
+                    params,
+                }))
+            }
+            AstNodeKind::Expression(
+                Expression::StructDefinition { .. } | Expression::EnumDefinition { .. },
+            ) => {
+                self.errors.push(LoweringError::new(
+                    node.position,
+                    "Types can only be defined by root declarations.",
+                ));
+                None
+            }
+            AstNodeKind::Statement(_) => panic!("Expected an expression"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use llc_core::common::source_map::SourceMap;
+
+    use super::*;
+
+    // C files of the entry module of a project of a single file, which has to be well typed.
+    fn lower(source: &str) -> Result<CModule, Vec<String>> {
+        lower_files(&[("main.llc", source)])
+    }
+
+    // Like lower, for a project whose entry file is main.llc.
+    fn lower_files(files: &[(&str, &str)]) -> Result<CModule, Vec<String>> {
+        let mut sources = SourceMap::new();
+        for (name, content) in files {
+            sources.add(&format!("test/{}", name), content);
+        }
+        let project = Project::load_sources(Path::new("test"), Path::new("test/main.llc"), sources);
+        let errors: Vec<String> = project
+            .compile_errors()
+            .iter()
+            .map(|e| e.to_string())
+            .collect();
+        assert!(errors.is_empty(), "{:?}", errors);
+
+        match lower_module(&project, project.modules.len() - 1, false) {
+            Ok(c_module) => Ok(c_module),
+            Err(errors) => Err(errors.iter().map(|e| e.reason.to_string()).collect()),
+        }
+    }
+
+    #[test]
+    fn reports_statements_which_cannot_be_lowered() {
+        let mut sources = SourceMap::new();
+        sources.add("test/main.llc", "main := {\n    use util;\n};\n");
+        sources.add("test/util.llc", "two := 2;\n");
+        let project = Project::load_sources(Path::new("test"), Path::new("test/main.llc"), sources);
+
+        // The checker rejects the use, the lowering reports it rather than panicking.
+        assert!(project.has_errors());
+        let errors = match lower_module(&project, project.modules.len() - 1, false) {
+            Ok(_) => panic!("Expected a lowering error"),
+            Err(errors) => errors,
+        };
+        assert_eq!(
+            &*errors[0].reason,
+            "Use statements cannot be lowered in a block."
+        );
+    }
+
+    #[test]
+    fn evaluates_the_end_of_a_for_loop_once() {
+        let source = lower(
+            "limit := () -> i32 {\n    => 3;\n};\n\nmain := {\n    s := 0;\n    for i in 0..(-> limit()) {\n        s = s + i;\n    };\n};\n",
+        )
+        .unwrap()
+        .source
+        .content();
+
+        assert_eq!(source.matches("limit()").count(), 1, "{}", source);
+        assert!(source.contains("int32_t _end"), "{}", source);
+    }
+
+    #[test]
+    fn keeps_a_literal_end_in_the_condition() {
+        let source =
+            lower("main := {\n    s := 0;\n    for i in 0..10 {\n        s = s + i;\n    };\n};\n")
+                .unwrap()
+                .source
+                .content();
+
+        assert!(source.contains("i < 10;"), "{}", source);
+    }
+
+    #[test]
+    fn checks_bounds_without_the_c_library_headers() {
+        let header = lower("main := {\n    a := [1, 2, 3];\n    x := a[1];\n};\n")
+            .unwrap()
+            .header
+            .content();
+
+        assert!(header.contains("llc_check_index"), "{}", header);
+        assert!(!header.contains("<stdio.h>"), "{}", header);
+        assert!(!header.contains("<stdlib.h>"), "{}", header);
+    }
+
+    #[test]
+    fn names_declarations_of_other_modules_by_their_c_name() {
+        let source = lower_files(&[
+            ("main.llc", "use point;\n\nmain := {\n    p := point::Point.{ x: 1, y: 2 };\n    point::origin = p;\n};\n"),
+            ("point.llc", "Point := struct {\n    x: i32,\n    y: i32,\n};\n\norigin := Point.{ x: 0, y: 0 };\n"),
+        ])
+        .unwrap()
+        .source
+        .content();
+
+        assert!(source.contains("(point__Point){"), "{}", source);
+        assert!(source.contains("point__origin = p;"), "{}", source);
+    }
+
+    #[test]
+    fn prefixes_root_declarations_with_the_path_of_their_module() {
+        let source = lower_files(&[
+            ("main.llc", "use shapes;\nuse squares::area;\n\nmain := {\n    a := -> shapes::area(2);\n    b := -> area(3);\n};\n"),
+            ("shapes.llc", "area := (side: i32) -> i32 {\n    => side;\n};\n"),
+            ("squares.llc", "area := (side: i32) -> i32 {\n    => side * side;\n};\n"),
+        ])
+        .unwrap()
+        .source
+        .content();
+
+        assert!(source.contains("shapes__area(2)"), "{}", source);
+        assert!(source.contains("squares__area(3)"), "{}", source);
+    }
+
+    #[test]
+    fn names_nested_blocks_after_the_blocks_declaring_them() {
+        let source = lower(
+            "helper := 10;\n\nf := () -> i32 {\n    helper := () -> i32 {\n        => 1;\n    };\n    => -> helper();\n};\n\ng := () -> i32 {\n    helper := () -> i32 {\n        => 2;\n    };\n    if true {\n        helper := () -> i32 {\n            => 3;\n        };\n        => -> helper();\n    };\n    => -> helper();\n};\n\nmain := {};\n",
+        )
+        .unwrap()
+        .source
+        .content();
+
+        for function in ["f__helper(void)", "g__helper(void)", "g__helper_2(void)"] {
+            assert_eq!(source.matches(function).count(), 1, "{}", source);
+        }
+        assert!(source.contains("return g__helper_2();"), "{}", source);
+        assert!(source.contains("return g__helper();"), "{}", source);
+        assert!(source.contains("int32_t helper = 10;"), "{}", source);
+    }
+
+    #[test]
+    fn prefixes_names_reserved_by_c() {
+        let module = lower(
+            "default := 3;\ndouble := struct {\n    long: i32,\n};\n\nmain := {\n    int := default;\n    d := double.{ long: int };\n    main := d.long;\n};\n",
+        )
+        .unwrap();
+        let source = module.source.content();
+
+        assert!(source.contains("int32_t llc_default = 3;"), "{}", source);
+        assert!(
+            source.contains("int32_t llc_int = llc_default;"),
+            "{}",
+            source
+        );
+        assert!(
+            source.contains("(llc_double){ .llc_long = llc_int }"),
+            "{}",
+            source
+        );
+        assert!(
+            source.contains("int32_t llc_main = d.llc_long;"),
+            "{}",
+            source
+        );
+        assert!(source.contains("int main(void)"), "{}", source);
+    }
+}
@@ -0,0 +1,31 @@
+use ccg::ast::{binary_op, unary_op};
+use llc_core::common::operator::Operator;
+
+pub fn binary(operator: Operator) -> binary_op::Operator {
+    match operator {
+        Operator::Plus => binary_op::Operator::Add,
+        Operator::Minus => binary_op::Operator::Subtract,
+        Operator::Times => binary_op::Operator::Multiply,
+        Operator::Divide => binary_op::Operator::Divide,
+        Operator::Modulus => binary_op::Operator::Modulo,
+        Operator::Equality => binary_op::Operator::Equal,
+        Operator::GreaterThan => binary_op::Operator::Greater,
+        Operator::LessThan => binary_op::Operator::Less,
+        Operator::NotEqual => binary_op::Operator::NotEqual,
+        Operator::GreaterOrEqual => binary_op::Operator::GreaterEqual,
+        Operator::LessOrEqual => binary_op::Operator::LessEqual,
+        Operator::And => binary_op::Operator::And,
+        Operator::Or => binary_op::Operator::Or,
+        _ => panic!("Expected a binary operator"),
+    }
+}
+
+pub fn unary(operator: Operator) -> unary_op::Operator {
+    match operator {
+        Operator::Minus => unary_op::Operator::Negate,
+        Operator::Not => unary_op::Operator::Not,
+        Operator::Ampersand => unary_op::Operator::AddressOf,
+        Operator::Times => unary_op::Operator::Dereference,
+        _ => panic!("Expected a unary operator"),
+    }
+}
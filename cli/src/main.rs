@@ -1,80 +1,297 @@
-use std::env;
-
-use ccg::{
-    ast::{
-        func_call,
-        func_def::{self, FuncParam},
-        node::{
-            AstNode::{FuncDefinition, VarAssignment, VarDeclAssignment, VarDeclaration},
-            Expression::{FunctionCall, Litteral, Variable},
-        },
-        var_assign, var_decl, var_declassign,
-    },
-    file::c_file::CFile,
+mod args;
+mod ast_dump;
+mod build;
+mod json;
+mod lowering;
+mod report;
+mod sarif;
+
+use std::{
+    env,
+    fs::File,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process::{self, Command},
 };
 
-use llc_core::parser::parser::FileAst;
+use args::{AstFormat, Command as LlcCommand};
+use build::{BuildError, BuildOptions};
+use llc_core::{
+    common::{
+        compile_error::CompileError,
+        source_map::{FileId, SourceMap},
+    },
+    lexer::{self, token::TokenKind},
+    parser::parser::FileAst,
+    project::Project,
+};
+use report::Reporter;
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().skip(1).collect();
 
-    let path = match args.get(1) {
-        Some(p) => p,
-        None => "src/test.llc"
+    let parsed = args::take_globals(&mut args).and_then(|globals| Ok((globals, args::parse(&args)?)));
+    let ((color, format, error_output), command) = match parsed {
+        Ok(c) => c,
+        Err(reason) => {
+            eprintln!("{}\n\n{}", reason, args::USAGE);
+            process::exit(2);
+        }
     };
 
-    let res = FileAst::new(path);
+    let error_file = match error_output.map(|path| File::create(&path).map_err(|e| (path, e))) {
+        Some(Ok(file)) => Some(file),
+        Some(Err((path, e))) => {
+            eprintln!("Cannot write the diagnostics to {}: {}.", path.display(), e);
+            process::exit(2);
+        }
+        None => None,
+    };
+    let mut reporter = Reporter::new(color, format, error_file);
 
-    for node in res.nodes {
-        print!("{} ", node);
+    match command {
+        LlcCommand::Check { path } => {
+            load(&path, &mut reporter);
+        }
+        LlcCommand::Build { path, options } => build(&path, &options, &mut reporter),
+        LlcCommand::Run { path, options, program_args } => {
+            build(&path, &options, &mut reporter);
+            reporter.finish();
+            run(&options.output, &program_args);
+        }
+        LlcCommand::EmitC { path, output_dir } => match output_dir {
+            Some(dir) => {
+                let mut project = load(&path, &mut reporter);
+                if let Err(e) = build::emit_c(&project, &dir, false) {
+                    report_build_error(&e, &mut project.sources, &mut reporter);
+                    fail(&mut reporter);
+                }
+            }
+            None => print_c(&path, &mut reporter),
+        },
+        LlcCommand::Tokens { path, json } => print_tokens(&path, json, &mut reporter),
+        LlcCommand::Ast { path, format } => print_ast(&path, format, &mut reporter),
+        LlcCommand::Help => println!("{}", args::USAGE),
+        LlcCommand::Version => println!("llc {}", env!("CARGO_PKG_VERSION")),
     }
 
-    if !res.errors.is_empty() {
-        eprintln!("Build failed: Errors happened while compiling the file {}.", res.file_name)
+    reporter.finish();
+}
+
+// Exits once the diagnostics held back by the reporter are written.
+fn fail(reporter: &mut Reporter) -> ! {
+    reporter.finish();
+    process::exit(1);
+}
+
+// Reads the source of the file, or of the standard input for -, exits when it cannot be read.
+fn read(path: &str, reporter: &mut Reporter) -> (SourceMap, FileId) {
+    let mut sources = SourceMap::new();
+    let name = args::source_name(path);
+
+    let read = match path {
+        "-" => {
+            let mut source = String::new();
+            match io::stdin().read_to_string(&mut source) {
+                Ok(_) => Ok(sources.add(name, &source)),
+                // The source is empty, the error still needs a file to be about.
+                Err(e) => Err(CompileError::Io {
+                    file: sources.add(name, ""),
+                    reason: format!("The standard input cannot be read: {}.", e).into_boxed_str(),
+                }),
+            }
+        }
+        path => sources.load(path),
+    };
+
+    match read {
+        Ok(id) => (sources, id),
+        Err(e) => {
+            report_errors(&[e], &sources, "compiling", reporter);
+            fail(reporter);
+        }
     }
-    for r in res.errors.iter() {
-        eprintln!("{0}", r)
+}
+
+// Loads the project of the entry file, reports its errors and exits when there are some.
+fn load(path: &str, reporter: &mut Reporter) -> Project {
+    let (sources, id) = read(path, reporter);
+    // Modules are looked up from the directory of the entry file.
+    let entry = PathBuf::from(&*sources.get(id).name);
+    let root = entry.parent().unwrap_or(Path::new("."));
+    let project = Project::load_sources(root, &entry, sources);
+
+    if project.has_errors() {
+        report_errors(&project.compile_errors(), &project.sources, "compiling", reporter);
+        fail(reporter);
     }
+
+    project
 }
 
-fn _test_ccg() {
-    let decl = VarDeclaration(var_decl::Data {
-        type_identifier: Box::from("int"),
-        identifier: Box::from("i"),
-    });
-
-    let assign = VarAssignment(var_assign::Data {
-        identifier: Box::from("i"),
-        value: Litteral(Box::from("2")),
-    });
-
-    let declassign = VarDeclAssignment(var_declassign::Data {
-        type_identifier: Box::from("int"),
-        identifier: Box::from("j"),
-        value: FunctionCall(func_call::Data {
-            identifier: Box::from("printf"),
-            params: Vec::from([Variable(Box::from("i"))]),
-        }),
-    });
-
-    let param_lhs = FuncParam {
-        type_identifier: Box::from("char*"),
-        identifier: Box::from("lhs"),
-    };
+// Reports the errors file by file, in the order of their first error.
+fn report_errors(errors: &[CompileError], sources: &SourceMap, action: &str, reporter: &mut Reporter) {
+    let mut files: Vec<FileId> = vec![];
+    for e in errors.iter() {
+        if !files.contains(&e.file()) {
+            files.push(e.file());
+        }
+    }
+
+    for file in files {
+        let diagnostics: Vec<_> = errors.iter().filter(|e| e.file() == file).map(|e| e.diagnostic()).collect();
+        reporter.report_file(sources, file, action, &diagnostics);
+    }
+}
+
+fn print_c(path: &str, reporter: &mut Reporter) {
+    let mut project = load(path, reporter);
 
-    let param_rhs = FuncParam {
-        type_identifier: Box::from("int"),
-        identifier: Box::from("rhs"),
+    for index in 0..project.modules.len() {
+        let module = &project.modules[index];
+        match lowering::lower_module(&project, index, false) {
+            Ok(c_module) => {
+                println!("// {}.h", module.file_stem());
+                c_module.header.print();
+                println!("// {}.c", module.file_stem());
+                c_module.source.print();
+            }
+            Err(errors) => {
+                let error = BuildError::Lowering {
+                    file: module.file_ast.file_id,
+                    errors,
+                };
+                report_build_error(&error, &mut project.sources, reporter);
+                fail(reporter);
+            }
+        }
+    }
+}
+
+fn build(path: &str, options: &BuildOptions, reporter: &mut Reporter) {
+    let mut project = load(path, reporter);
+
+    match build::build(&project, options) {
+        Ok(warnings) => {
+            for w in warnings.iter() {
+                reporter.report(&w.diagnostic(&mut project.sources), &project.sources);
+            }
+        }
+        Err(e) => {
+            report_build_error(&e, &mut project.sources, reporter);
+            fail(reporter);
+        }
+    }
+}
+
+// Errors with positions in the sources are rendered with them, the others are written as they
+// are displayed.
+fn report_build_error(error: &BuildError, sources: &mut SourceMap, reporter: &mut Reporter) {
+    match error {
+        BuildError::EntryPoint(error) => report_errors(std::slice::from_ref(error), sources, "building", reporter),
+        BuildError::Lowering { file, errors } => {
+            let errors: Vec<_> = errors.iter().map(|e| e.compile_error(*file)).collect();
+            report_errors(&errors, sources, "generating C for", reporter);
+        }
+        BuildError::Compiler { diagnostics, other } => {
+            for d in diagnostics.iter() {
+                let diagnostic = d.diagnostic(sources);
+                reporter.report(&diagnostic, sources);
+            }
+            for line in other.iter() {
+                eprintln!("{}", line);
+            }
+            eprintln!("Build failed: The C compiler rejected the generated code.");
+        }
+        e => eprintln!("{}", e),
+    }
+}
+
+// Exits with the exit code of the executable.
+fn run(program: &Path, args: &[String]) {
+    // A relative path without directory would be looked up in the PATH.
+    let program = match program.is_absolute() {
+        true => program.to_path_buf(),
+        false => Path::new(".").join(program),
     };
 
-    let func = FuncDefinition(func_def::Data {
-        identifier: Box::from("my_func"),
-        type_identifier: Box::from("void"),
-        params: Vec::from([param_lhs, param_rhs]),
-        body: Vec::from([decl, assign, declassign]),
-    });
+    match Command::new(&program).args(args).status() {
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(e) => {
+            eprintln!("Run failed: Could not run {}: {}.", program.display(), e);
+            process::exit(1);
+        }
+    }
+}
+
+fn print_tokens(path: &str, json: bool, reporter: &mut Reporter) {
+    let (sources, id) = read(path, reporter);
+    let lexed = lexer::get_tokens(&sources, id);
 
-    let file = CFile { nodes: vec![func] };
+    match json {
+        true => println!("{}", tokens_json(&lexed)),
+        false => {
+            for t in lexed.tokens() {
+                println!("{}: {}.", t.position, lexed.describe(t));
+            }
+        }
+    }
+
+    if !lexed.errors.is_empty() {
+        if !json {
+            let diagnostics: Vec<_> = lexed.errors.iter().map(|e| e.diagnostic(id)).collect();
+            reporter.report_file(&sources, id, "compiling", &diagnostics);
+        }
+        fail(reporter);
+    }
+}
+
+// {"file": "main.llc", "tokens": [{"kind": "Identifier", "value": "x", "span": ...}], "errors": []}
+fn tokens_json(lexed: &lexer::LexedFile) -> String {
+    let tokens: Vec<String> = lexed
+        .tokens()
+        .iter()
+        .map(|t| {
+            let mut value = match lexed.token_value(t) {
+                Some(v) => format!(",\"value\":{}", json::string(&v)),
+                None => String::new(),
+            };
+            if let TokenKind::Literal(lit) = &t.kind {
+                value.push_str(&format!(",\"type\":{}", json::string(&lit.llc_type.to_string())));
+            }
+            format!("{{\"kind\":{}{},\"span\":{}}}", json::string(t.kind.name()), value, json::span(&t.position))
+        })
+        .collect();
+
+    let errors: Vec<String> = lexed
+        .errors
+        .iter()
+        .map(|e| format!("{{\"message\":{},\"span\":{}}}", json::string(&e.reason), json::span(&e.position)))
+        .collect();
 
-    file.print();
+    format!(
+        "{{\"file\":{},\"tokens\":{},\"errors\":{}}}",
+        json::string(&lexed.file_name),
+        json::array(&tokens),
+        json::array(&errors)
+    )
+}
+
+fn print_ast(path: &str, format: AstFormat, reporter: &mut Reporter) {
+    let (sources, id) = read(path, reporter);
+    let file_ast = FileAst::parse(&sources, id);
+
+    match format {
+        AstFormat::Text => println!("{}", ast_dump::text(&file_ast)),
+        AstFormat::Json => println!("{}", ast_dump::json(&file_ast)),
+        AstFormat::Sexpr => println!("{}", ast_dump::sexpr(&file_ast)),
+    }
+
+    if !file_ast.errors.is_empty() {
+        if !matches!(format, AstFormat::Json) {
+            let diagnostics: Vec<_> = file_ast.errors.iter().map(|e| e.diagnostic(id)).collect();
+            reporter.report_file(&sources, id, "compiling", &diagnostics);
+        }
+        fail(reporter);
+    }
 }
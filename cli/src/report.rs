@@ -0,0 +1,170 @@
+use std::{
+    env,
+    fs::File,
+    io::{self, IsTerminal, Write},
+};
+
+use llc_core::common::{
+    diagnostic::{Diagnostic, Severity},
+    source_map::{FileId, SourceMap},
+};
+
+use crate::{json, sarif};
+
+#[derive(Clone, Copy)]
+pub enum ColorChoice {
+    // Colors when the diagnostics are written to a terminal and NO_COLOR is not set.
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ErrorFormat {
+    // Rendered with the lines of source they are about.
+    Human,
+    // One JSON object per line and diagnostic.
+    Json,
+    // A single SARIF log written once every diagnostic is known.
+    Sarif,
+}
+
+// Writes diagnostics to stderr, or to the file given to --error-output, whatever their format: the
+// standard output is left to the C code, tokens, trees and programs the commands print. Errors
+// which are not about a position in a source, like a C compiler which cannot be found, are
+// written as text to stderr whatever the format.
+pub struct Reporter {
+    color: bool,
+    format: ErrorFormat,
+    output: Box<dyn Write>,
+    // SARIF results waiting for the log.
+    results: Vec<String>,
+}
+
+impl ColorChoice {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "auto" => Some(Self::Auto),
+            "always" => Some(Self::Always),
+            "never" => Some(Self::Never),
+            _ => None,
+        }
+    }
+}
+
+impl ErrorFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "human" => Some(Self::Human),
+            "json" => Some(Self::Json),
+            "sarif" => Some(Self::Sarif),
+            _ => None,
+        }
+    }
+}
+
+impl Reporter {
+    pub fn new(color: ColorChoice, format: ErrorFormat, file: Option<File>) -> Self {
+        let color = match color {
+            ColorChoice::Auto => {
+                file.is_none()
+                    && io::stderr().is_terminal()
+                    && env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+            }
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        };
+
+        let output: Box<dyn Write> = match file {
+            Some(file) => Box::new(file),
+            None => Box::new(io::stderr()),
+        };
+
+        Reporter {
+            color,
+            format,
+            output,
+            results: vec![],
+        }
+    }
+
+    // Diagnostics which cannot be written are lost, there is nowhere left to report it.
+    fn write(&mut self, text: &str) {
+        let _ = writeln!(self.output, "{}", text);
+    }
+
+    // Diagnostics are rendered with the sources they were compiled from rather than the files,
+    // which may not exist or have changed since.
+    pub fn report(&mut self, diagnostic: &Diagnostic, sources: &SourceMap) {
+        match self.format {
+            ErrorFormat::Human => {
+                let text = diagnostic.render(sources, self.color);
+                self.write(&format!("{}\n", text));
+            }
+            ErrorFormat::Json => self.write(&json::diagnostic(diagnostic, sources)),
+            ErrorFormat::Sarif => self.results.push(sarif::result(diagnostic, sources)),
+        }
+    }
+
+    // Writes the diagnostics held back until the end, to be called before exiting.
+    pub fn finish(&mut self) {
+        if self.format == ErrorFormat::Sarif {
+            let log = sarif::log(&self.results);
+            self.write(&log);
+            self.results.clear();
+        }
+        let _ = self.output.flush();
+    }
+
+    // Reports the diagnostics of a file followed by their count, nothing is written when there
+    // are none.
+    pub fn report_file(
+        &mut self,
+        sources: &SourceMap,
+        file: FileId,
+        action: &str,
+        diagnostics: &[Diagnostic],
+    ) {
+        for d in diagnostics.iter() {
+            self.report(d, sources);
+        }
+
+        // Tools count the diagnostics themselves.
+        if self.format != ErrorFormat::Human {
+            return;
+        }
+
+        let errors = count(diagnostics, Severity::Error);
+        let warnings = count(diagnostics, Severity::Warning);
+        let summary = match (errors, warnings) {
+            (0, 0) => return,
+            (0, w) => format!(
+                "{} happened while {} the file {}.",
+                plural(w, "warning"),
+                action,
+                sources.name(file)
+            ),
+            (e, _) => format!(
+                "Build failed: {} happened while {} the file {}.",
+                plural(e, "error"),
+                action,
+                sources.name(file)
+            ),
+        };
+        self.write(&summary);
+    }
+}
+
+fn count(diagnostics: &[Diagnostic], severity: Severity) -> usize {
+    diagnostics
+        .iter()
+        .filter(|d| d.severity == severity)
+        .count()
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    match count {
+        1 => format!("1 {}", noun),
+        n => format!("{} {}s", n, noun),
+    }
+}
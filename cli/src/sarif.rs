@@ -0,0 +1,132 @@
+use std::path::Path;
+
+use llc_core::common::{
+    diagnostic::{self, Diagnostic, Severity},
+    position::FileSpan,
+    source_map::SourceMap,
+};
+
+use crate::json;
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+// Log of a single run of llc in the SARIF 2.1.0 format read by code scanning services. Every
+// code is listed as a rule, so the results can refer to them.
+pub fn log(results: &[String]) -> String {
+    let rules: Vec<String> = diagnostic::CODES
+        .iter()
+        .map(|code| {
+            format!(
+                "{{\"id\":{},\"shortDescription\":{{\"text\":{}}}}}",
+                json::string(code),
+                json::string(diagnostic::describe(code))
+            )
+        })
+        .collect();
+
+    format!(
+        "{{\"$schema\":{},\"version\":\"2.1.0\",\"runs\":[{{\"tool\":{{\"driver\":{{\"name\":\"llc\",\"version\":{},\"rules\":[{}]}}}},\"results\":{}}}]}}",
+        json::string(SCHEMA),
+        json::string(env!("CARGO_PKG_VERSION")),
+        rules.join(","),
+        json::array(results)
+    )
+}
+
+// Results are written as soon as they are known, as the sources may not outlive them.
+pub fn result(diagnostic: &Diagnostic, sources: &SourceMap) -> String {
+    let file_name = sources.name(diagnostic.position.file);
+
+    let level = match diagnostic.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note => "note",
+    };
+
+    let related: Vec<String> = diagnostic
+        .labels
+        .iter()
+        .enumerate()
+        .map(|(i, l)| {
+            format!(
+                "{{\"id\":{},\"message\":{{\"text\":{}}},\"physicalLocation\":{}}}",
+                i,
+                json::string(&l.message),
+                location(file_name, &l.position)
+            )
+        })
+        .collect();
+
+    let fixes: Vec<String> = diagnostic
+        .fixes
+        .iter()
+        .map(|f| {
+            format!(
+                "{{\"description\":{{\"text\":{}}},\"artifactChanges\":[{{\"artifactLocation\":{{\"uri\":{}}},\"replacements\":[{{\"deletedRegion\":{},\"insertedContent\":{{\"text\":{}}}}}]}}]}}",
+                json::string(&f.message),
+                json::string(&uri(file_name)),
+                fix_region(&f.position),
+                json::string(&f.replacement)
+            )
+        })
+        .collect();
+
+    format!(
+        "{{\"ruleId\":{},\"level\":\"{}\",\"message\":{{\"text\":{}}},\"locations\":[{{\"physicalLocation\":{}}}],\"relatedLocations\":[{}],\"fixes\":[{}]}}",
+        json::string(diagnostic.code),
+        level,
+        json::string(&diagnostic.message),
+        location(file_name, &diagnostic.position.position),
+        related.join(","),
+        fixes.join(",")
+    )
+}
+
+// Errors about a whole file, at line 0, have no region.
+fn location(file_name: &str, position: &FileSpan) -> String {
+    let artifact = format!(
+        "\"artifactLocation\":{{\"uri\":{}}}",
+        json::string(&uri(file_name))
+    );
+    match position.begin.row {
+        0 => format!("{{{}}}", artifact),
+        _ => format!("{{{},\"region\":{}}}", artifact, region(position)),
+    }
+}
+
+// SARIF columns end after the last character, a column of 0 is a whole line and is left out.
+fn region(position: &FileSpan) -> String {
+    match position.begin.col {
+        0 => format!(
+            "{{\"startLine\":{},\"endLine\":{}}}",
+            position.begin.row, position.end.row
+        ),
+        col => format!(
+            "{{\"startLine\":{},\"startColumn\":{},\"endLine\":{},\"endColumn\":{}}}",
+            position.begin.row,
+            col,
+            position.end.row,
+            position.end.col + 1
+        ),
+    }
+}
+
+// The span of a fix already ends after its last character, an insertion is an empty region.
+fn fix_region(position: &FileSpan) -> String {
+    format!(
+        "{{\"startLine\":{},\"startColumn\":{},\"endLine\":{},\"endColumn\":{}}}",
+        position.begin.row, position.begin.col, position.end.row, position.end.col
+    )
+}
+
+// Relative paths are kept relative, to the directory llc was run from.
+fn uri(file_name: &str) -> String {
+    let path = file_name
+        .replace('\\', "/")
+        .replace('%', "%25")
+        .replace(' ', "%20");
+    match Path::new(file_name).is_absolute() {
+        true => format!("file://{}", path),
+        false => path,
+    }
+}
@@ -0,0 +1,363 @@
+use std::{
+    env,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Output, Stdio},
+};
+
+// Runs llc on a source given on the standard input.
+fn llc(args: &[&str], source: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_llc"))
+        .args(args)
+        .env("NO_COLOR", "1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Expected llc to start");
+
+    // llc exits without reading the source on usage errors, the pipe may be closed.
+    let _ = child.stdin.take().unwrap().write_all(source.as_bytes());
+    child.wait_with_output().unwrap()
+}
+
+// Runs the source with llc run, in a build directory of its own so tests can run in parallel.
+fn run(name: &str, source: &str) -> Output {
+    let build_dir: PathBuf = env::temp_dir().join("llc-tests").join(name);
+    llc(
+        &["run", "-", "--build-dir", &build_dir.to_string_lossy()],
+        source,
+    )
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).to_string()
+}
+
+#[test]
+fn rejects_a_blank_c_compiler() {
+    for cc in ["", " "] {
+        let output = llc(&["build", "-", "--cc", cc], "main := {};\n");
+
+        assert_eq!(output.status.code(), Some(2), "{}", stderr(&output));
+        assert!(stderr(&output).starts_with("Expected a C compiler after --cc."));
+    }
+}
+
+#[test]
+fn declares_c_library_functions_once() {
+    let source = "malloc := extern (size: u64) -> *u8;\nfree := extern \"stdlib.h\" (p: *u8);\n\nmain := () -> i32 {\n    p := -> malloc(8);\n    -> free(p);\n    a := [1, 2, 3];\n    => a[1];\n};\n";
+    let output = run("c_library", source);
+
+    assert_eq!(output.status.code(), Some(2), "{}", stderr(&output));
+}
+
+#[test]
+fn indexes_arrays_next_to_a_header_less_extern() {
+    let source = "malloc := extern (size: u64) -> *u8;\n\nmain := () -> i32 {\n    p := -> malloc(8);\n    a := [1, 2, 3];\n    => a[2];\n};\n";
+    let output = run("header_less_extern", source);
+
+    assert_eq!(output.status.code(), Some(3), "{}", stderr(&output));
+}
+
+#[test]
+fn checks_if_expression_arguments_like_the_build() {
+    let source = "g := (v: i32) -> i32 {\n    => v;\n};\n\nmain := () -> i32 {\n    d := true;\n    => -> g(if d { y := 1; y; } else { 2; });\n};\n";
+
+    for action in ["check", "build"] {
+        let output = llc(&[action, "-"], source);
+
+        assert_eq!(output.status.code(), Some(1), "{}", stderr(&output));
+        assert!(
+            stderr(&output).contains("error[E0002]"),
+            "{}",
+            stderr(&output)
+        );
+    }
+}
+
+#[test]
+fn checks_match_expression_operands_like_the_build() {
+    let source = "Shape := enum { Circle(i32), Square(i32), };\n\nmain := () -> i32 {\n    s := Shape.Circle(2);\n    => 1 + match s { Circle(r) => { r; }, Square(c) => { c; }, };\n};\n";
+
+    for action in ["check", "build"] {
+        let output = llc(&[action, "-"], source);
+
+        assert_eq!(output.status.code(), Some(1), "{}", stderr(&output));
+        assert!(
+            stderr(&output).contains("error[E0002]"),
+            "{}",
+            stderr(&output)
+        );
+    }
+}
+
+#[test]
+fn writes_the_sarif_log_to_the_standard_error() {
+    let output = llc(
+        &["check", "-", "--error-format", "sarif"],
+        "main := {\n    x := 1\n};\n",
+    );
+    let log = stderr(&output);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(log.starts_with("{\"$schema\""), "{}", log);
+    assert!(output.stdout.is_empty());
+    // The missing ; is inserted in an empty region, after the 1.
+    assert!(
+        log.contains(
+            "\"deletedRegion\":{\"startLine\":2,\"startColumn\":11,\"endLine\":2,\"endColumn\":11}"
+        ),
+        "{}",
+        log
+    );
+}
+
+#[test]
+fn keeps_the_sarif_log_out_of_the_emitted_c() {
+    let output = llc(&["emit-c", "-", "--error-format", "sarif"], "main := {};\n");
+    let c = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+    assert!(c.starts_with("// stdin.h"), "{}", c);
+    assert!(!c.contains("$schema"), "{}", c);
+    assert!(
+        stderr(&output).starts_with("{\"$schema\""),
+        "{}",
+        stderr(&output)
+    );
+}
+
+#[test]
+fn writes_the_diagnostics_to_the_error_output() {
+    let path = env::temp_dir().join("llc-tests").join("errors.json");
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let output = llc(
+        &[
+            "check",
+            "-",
+            "--error-format",
+            "json",
+            "--error-output",
+            &path.to_string_lossy(),
+        ],
+        "main := {\n    x := y;\n};\n",
+    );
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).is_empty(), "{}", stderr(&output));
+    let diagnostics = std::fs::read_to_string(&path).unwrap();
+    assert!(
+        diagnostics.contains("\"code\":\"E0008\""),
+        "{}",
+        diagnostics
+    );
+}
+
+#[test]
+fn exits_with_0_for_a_valid_source() {
+    let output = llc(&["check", "-"], "main := {};\n");
+
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+    assert!(stderr(&output).is_empty(), "{}", stderr(&output));
+}
+
+#[test]
+fn exits_with_1_for_compile_errors() {
+    for source in [
+        "main := {\n    x := 1\n};\n",
+        "main := {\n    x : i32 = true;\n};\n",
+    ] {
+        let output = llc(&["check", "-"], source);
+
+        assert_eq!(output.status.code(), Some(1), "{}", stderr(&output));
+    }
+
+    let output = llc(&["check", "/nonexistent/main.llc"], "");
+    assert_eq!(output.status.code(), Some(1), "{}", stderr(&output));
+    assert!(
+        stderr(&output).contains("error[E0006]"),
+        "{}",
+        stderr(&output)
+    );
+}
+
+#[test]
+fn exits_with_2_for_usage_errors() {
+    for args in [
+        &["frobnicate", "-"][..],
+        &["check", "-", "--nope"],
+        &["check"],
+    ] {
+        let output = llc(args, "main := {};\n");
+
+        assert_eq!(
+            output.status.code(),
+            Some(2),
+            "{:?}: {}",
+            args,
+            stderr(&output)
+        );
+    }
+}
+
+#[test]
+fn exits_with_the_code_of_the_program_it_runs() {
+    let output = run("exit_code", "main := () -> i32 {\n    => 3;\n};\n");
+
+    assert_eq!(output.status.code(), Some(3), "{}", stderr(&output));
+}
+
+#[test]
+fn prints_f64_values_declared_with_decimal_literals() {
+    let source = "printf := extern \"stdio.h\" (format: string, ...) -> i32;\n\ng : f64 = 0.1;\n\nmain := () -> i32 {\n    h : f32 = 0.5;\n    -> printf(\"%.17g %.17g\\n\", g, h);\n    => 0;\n};\n";
+    let output = run("f64_literals", source);
+
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "0.10000000000000001 0.5\n"
+    );
+}
+
+#[test]
+fn builds_blocks_declaring_blocks_of_the_same_name() {
+    let source = "helper := 10;\n\nf := () -> i32 {\n    helper := () -> i32 {\n        => 1;\n    };\n    => -> helper();\n};\n\ng := () -> i32 {\n    helper := () -> i32 {\n        => 2;\n    };\n    => -> helper();\n};\n\nmain := () -> i32 {\n    => -> f() + -> g() + helper;\n};\n";
+    let output = run("nested_blocks", source);
+
+    assert_eq!(output.status.code(), Some(13), "{}", stderr(&output));
+}
+
+#[test]
+fn runs_modules_declaring_the_same_names() {
+    let root = env::temp_dir().join("llc-tests").join("same_names");
+    std::fs::create_dir_all(&root).unwrap();
+    let files = [
+        (
+            "main.llc",
+            "use circles;\nuse squares;\n\nmain := () -> i32 {\n    => -> circles::area(circles::Shape.{ size: 2 }) + -> squares::area(squares::Shape.{ size: 3 });\n};\n",
+        ),
+        (
+            "circles.llc",
+            "Shape := struct {\n    size: i32,\n};\n\narea := (shape: Shape) -> i32 {\n    => 3 * shape.size * shape.size;\n};\n",
+        ),
+        (
+            "squares.llc",
+            "Shape := struct {\n    size: i32,\n};\n\narea := (shape: Shape) -> i32 {\n    => shape.size * shape.size;\n};\n",
+        ),
+    ];
+    for (name, source) in files {
+        std::fs::write(root.join(name), source).unwrap();
+    }
+
+    let build_dir = root.join("build");
+    let output = llc(
+        &[
+            "run",
+            &root.join("main.llc").to_string_lossy(),
+            "--build-dir",
+            &build_dir.to_string_lossy(),
+        ],
+        "",
+    );
+
+    assert_eq!(output.status.code(), Some(21), "{}", stderr(&output));
+}
+
+#[test]
+fn runs_modules_named_like_c_library_headers() {
+    let root = env::temp_dir().join("llc-tests").join("library_names");
+    std::fs::create_dir_all(&root).unwrap();
+    let files = [
+        (
+            "main.llc",
+            "use stdint;\n\nmain := () -> i32 {\n    => -> stdint::seven();\n};\n",
+        ),
+        ("stdint.llc", "seven := () -> i32 {\n    => 7;\n};\n"),
+    ];
+    for (name, source) in files {
+        std::fs::write(root.join(name), source).unwrap();
+    }
+
+    let build_dir = root.join("build");
+    let output = llc(
+        &[
+            "run",
+            &root.join("main.llc").to_string_lossy(),
+            "--build-dir",
+            &build_dir.to_string_lossy(),
+        ],
+        "",
+    );
+
+    assert_eq!(output.status.code(), Some(7), "{}", stderr(&output));
+}
+
+#[test]
+fn rejects_entry_points_c_cannot_start() {
+    let output = run(
+        "entry_point",
+        "main := (code: i32) -> bool {\n    => code > 0;\n};\n",
+    );
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        stderr(&output).contains("error[E0007]: The main block cannot take parameters."),
+        "{}",
+        stderr(&output)
+    );
+}
+
+#[test]
+fn dumps_the_tokens_as_json() {
+    let output = llc(
+        &["tokens", "-", "--json"],
+        "main := {\n    x := 1 + 2;\n};\n",
+    );
+
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        r#"{"file":"stdin.llc","tokens":[
+{"kind":"Identifier","value":"main","span":{"begin":{"row":1,"col":1},"end":{"row":1,"col":4}}},
+{"kind":"Operator","value":":=","span":{"begin":{"row":1,"col":6},"end":{"row":1,"col":7}}},
+{"kind":"OpenCurly","span":{"begin":{"row":1,"col":9},"end":{"row":1,"col":9}}},
+{"kind":"Identifier","value":"x","span":{"begin":{"row":2,"col":5},"end":{"row":2,"col":5}}},
+{"kind":"Operator","value":":=","span":{"begin":{"row":2,"col":7},"end":{"row":2,"col":8}}},
+{"kind":"Literal","value":"1","type":"i32","span":{"begin":{"row":2,"col":10},"end":{"row":2,"col":10}}},
+{"kind":"Operator","value":"+","span":{"begin":{"row":2,"col":12},"end":{"row":2,"col":12}}},
+{"kind":"Literal","value":"2","type":"i32","span":{"begin":{"row":2,"col":14},"end":{"row":2,"col":14}}},
+{"kind":"EOI","span":{"begin":{"row":2,"col":15},"end":{"row":2,"col":15}}},
+{"kind":"ClosingCurly","span":{"begin":{"row":3,"col":1},"end":{"row":3,"col":1}}},
+{"kind":"EOI","span":{"begin":{"row":3,"col":2},"end":{"row":3,"col":2}}},
+{"kind":"EOF","span":{"begin":{"row":4,"col":1},"end":{"row":4,"col":1}}}
+],"errors":[]}
+"#
+    );
+}
+
+#[test]
+fn dumps_the_ast_as_json() {
+    let output = llc(&["ast", "-", "--json"], "main := {\n    x := 1 + 2;\n};\n");
+
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        r#"{"file":"stdin.llc","nodes":[
+{"index":7,"kind":"Declaration","span":{"begin":{"row":1,"col":1},"end":{"row":3,"col":1}},"parent":null,"children":[{"index":0,"kind":"Identifier","name":"main","span":{"begin":{"row":1,"col":1},"end":{"row":1,"col":4}},"parent":7,"children":[]},{"index":6,"kind":"BlockExpression","span":{"begin":{"row":1,"col":9},"end":{"row":3,"col":1}},"parent":7,"children":[{"index":5,"kind":"Declaration","span":{"begin":{"row":2,"col":5},"end":{"row":2,"col":14}},"parent":6,"children":[{"index":1,"kind":"Identifier","name":"x","span":{"begin":{"row":2,"col":5},"end":{"row":2,"col":5}},"parent":5,"children":[]},{"index":4,"kind":"BinaryOperation","operator":"+","span":{"begin":{"row":2,"col":10},"end":{"row":2,"col":14}},"parent":5,"children":[{"index":2,"kind":"Literal","value":"1","type":"i32","span":{"begin":{"row":2,"col":10},"end":{"row":2,"col":10}},"parent":4,"children":[]},{"index":3,"kind":"Literal","value":"2","type":"i32","span":{"begin":{"row":2,"col":14},"end":{"row":2,"col":14}},"parent":4,"children":[]}]}]}]}]}
+],"errors":[]}
+"#
+    );
+}
+
+#[test]
+fn dumps_the_ast_as_an_s_expression() {
+    let output = llc(&["ast", "-", "--sexpr"], "main := {\n    x := 1 + 2;\n};\n");
+
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        r#"(Declaration :span "1:1-3:1" (Identifier :name "main" :span "1:1-1:4") (BlockExpression :span "1:9-3:1" (Declaration :span "2:5-2:14" (Identifier :name "x" :span "2:5-2:5") (BinaryOperation :operator "+" :span "2:10-2:14" (Literal :value "1" :type "i32" :span "2:10-2:10") (Literal :value "2" :type "i32" :span "2:14-2:14")))))
+"#
+    );
+}
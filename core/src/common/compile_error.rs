@@ -0,0 +1,114 @@
+use std::fmt::Display;
+
+use crate::{project::module_error::ModuleError, type_system::type_error::TypeError};
+
+use super::{
+    diagnostic::{self, Diagnostic, Severity},
+    position::FileSpan,
+    source_map::{FileId, SourceSpan},
+    syntax_error::SyntaxError,
+};
+
+// Every error which can stop the compilation of a file, along with the file it is about.
+#[derive(Debug, Clone)]
+pub enum CompileError {
+    // The file cannot be read or is not UTF-8.
+    Io {
+        file: FileId,
+        reason: Box<str>,
+    },
+    // Errors of the lexer and of the parser.
+    Syntax {
+        file: FileId,
+        error: SyntaxError,
+    },
+    Type {
+        file: FileId,
+        error: TypeError,
+    },
+    Module(ModuleError),
+    // Programs which are well typed but cannot be written in C.
+    Codegen {
+        file: FileId,
+        position: FileSpan,
+        reason: Box<str>,
+    },
+    // The entry module cannot be built to a program, the position is the one of its main
+    // declaration when there is one.
+    EntryPoint {
+        file: FileId,
+        position: Option<FileSpan>,
+        reason: Box<str>,
+    },
+}
+
+impl CompileError {
+    pub fn file(&self) -> FileId {
+        match self {
+            Self::Io { file, .. }
+            | Self::Syntax { file, .. }
+            | Self::Type { file, .. }
+            | Self::Codegen { file, .. }
+            | Self::EntryPoint { file, .. } => *file,
+            Self::Module(error) => error.file,
+        }
+    }
+
+    pub fn diagnostic(&self) -> Diagnostic {
+        match self {
+            Self::Io { file, reason } => Diagnostic::new(
+                Severity::Error,
+                diagnostic::IO_ERROR,
+                reason,
+                SourceSpan::file(*file),
+            ),
+            Self::Syntax { file, error } => error.diagnostic(*file),
+            Self::Type { file, error } => error.diagnostic(*file),
+            Self::Module(error) => error.diagnostic(),
+            Self::Codegen {
+                file,
+                position,
+                reason,
+            } => Diagnostic::new(
+                Severity::Error,
+                diagnostic::CODEGEN_ERROR,
+                reason,
+                SourceSpan::new(*file, *position),
+            ),
+            Self::EntryPoint {
+                file,
+                position,
+                reason,
+            } => Diagnostic::new(
+                Severity::Error,
+                diagnostic::ENTRY_POINT_ERROR,
+                reason,
+                match position {
+                    Some(p) => SourceSpan::new(*file, *p),
+                    None => SourceSpan::file(*file),
+                },
+            ),
+        }
+    }
+}
+
+// The file is left out, its name is known from the SourceMap.
+impl Display for CompileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io { reason, .. } => write!(f, "IoError: {0}", reason),
+            Self::Syntax { error, .. } => write!(f, "{0}", error),
+            Self::Type { error, .. } => write!(f, "{0}", error),
+            Self::Module(error) => write!(f, "{0}", error),
+            Self::Codegen {
+                position, reason, ..
+            } => write!(f, "{0}. CodegenError: {1}", position, reason),
+            Self::EntryPoint {
+                position: Some(position),
+                reason,
+                ..
+            } => write!(f, "{0}. EntryPointError: {1}", position, reason),
+            Self::EntryPoint { reason, .. } => write!(f, "EntryPointError: {0}", reason),
+        }
+    }
+}
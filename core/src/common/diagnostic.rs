@@ -0,0 +1,417 @@
+use std::fmt::Display;
+
+use super::{
+    position::{FilePosition, FileSpan},
+    source_map::{SourceMap, SourceSpan},
+};
+
+// Codes are stable, tools may match on them. Each kind of error has its own.
+pub const SYNTAX_ERROR: &str = "E0001";
+pub const TYPE_ERROR: &str = "E0002";
+pub const MODULE_ERROR: &str = "E0003";
+pub const CODEGEN_ERROR: &str = "E0004";
+pub const C_COMPILER_ERROR: &str = "E0005";
+pub const C_COMPILER_WARNING: &str = "W0005";
+pub const IO_ERROR: &str = "E0006";
+pub const ENTRY_POINT_ERROR: &str = "E0007";
+pub const UNDECLARED_NAME: &str = "E0008";
+pub const MISMATCHED_TYPES: &str = "E0009";
+pub const NON_EXHAUSTIVE_MATCH: &str = "E0010";
+pub const ARITY_MISMATCH: &str = "E0011";
+pub const REDECLARATION: &str = "E0012";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+// Secondary position of a diagnostic, like the first declaration of a name declared twice. Labels
+// and fixes are in the file of their diagnostic.
+#[derive(Debug, Clone)]
+pub struct Label {
+    pub position: FileSpan,
+    pub message: Box<str>,
+}
+
+// Change of the source which would fix a diagnostic. Unlike the other spans, the span of a fix ends
+// before its end column: the replacement takes the place of the columns from its beginning up to
+// its end, and a span ending where it begins is empty, the replacement is inserted there.
+#[derive(Debug, Clone)]
+pub struct Fix {
+    pub message: Box<str>,
+    pub position: FileSpan,
+    pub replacement: Box<str>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: &'static str,
+    pub message: Box<str>,
+    // A column of 0 marks the whole line, for tools only reporting lines like C compilers.
+    pub position: SourceSpan,
+    pub labels: Vec<Label>,
+    pub fixes: Vec<Fix>,
+}
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const CYAN: &str = "\x1b[1;36m";
+const BLUE: &str = "\x1b[1;34m";
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Error => "error",
+                Self::Warning => "warning",
+                Self::Note => "note",
+            }
+        )
+    }
+}
+
+impl Severity {
+    fn color(&self) -> &'static str {
+        match self {
+            Self::Error => RED,
+            Self::Warning => YELLOW,
+            Self::Note => CYAN,
+        }
+    }
+}
+
+impl Fix {
+    // Replaces the text of the span, which ends on its last column like the span of a token.
+    pub fn replace(position: FileSpan, replacement: &str, message: &str) -> Self {
+        let end = FilePosition::new(position.end.row, position.end.col + 1);
+        Fix::new(FileSpan::new(position.begin, end), replacement, message)
+    }
+
+    // Inserts the text right after the span, like a ; forgotten after a token.
+    pub fn insert_after(position: FileSpan, text: &str, message: &str) -> Self {
+        let after = FilePosition::new(position.end.row, position.end.col + 1);
+        Fix::new(FileSpan::new(after, after), text, message)
+    }
+
+    fn new(position: FileSpan, replacement: &str, message: &str) -> Self {
+        Fix {
+            message: Box::from(message),
+            position,
+            replacement: Box::from(replacement),
+        }
+    }
+
+    pub fn is_insertion(&self) -> bool {
+        self.position.begin.row == self.position.end.row
+            && self.position.begin.col == self.position.end.col
+    }
+}
+
+// Short description of the errors of a code, for the tools listing the rules they report.
+pub fn describe(code: &str) -> &'static str {
+    match code {
+        SYNTAX_ERROR => "The source cannot be parsed.",
+        TYPE_ERROR => "The program is not well typed.",
+        MODULE_ERROR => "A module cannot be used.",
+        CODEGEN_ERROR => "C code cannot be generated for the program.",
+        C_COMPILER_ERROR => "The C compiler rejected the generated code.",
+        C_COMPILER_WARNING => "The C compiler warned about the generated code.",
+        IO_ERROR => "A source file cannot be read.",
+        ENTRY_POINT_ERROR => "The entry module has no main block C can start the program with.",
+        UNDECLARED_NAME => "An identifier or a type is used but not declared.",
+        MISMATCHED_TYPES => "A value does not have the type it is expected to have.",
+        NON_EXHAUSTIVE_MATCH => "A match does not cover every variant of the enum.",
+        ARITY_MISMATCH => "A block or a variant is given another number of values than it takes.",
+        REDECLARATION => "A name is declared twice in the same scope, struct or enum.",
+        _ => "Unknown diagnostic.",
+    }
+}
+
+pub const CODES: [&str; 13] = [
+    SYNTAX_ERROR,
+    TYPE_ERROR,
+    MODULE_ERROR,
+    CODEGEN_ERROR,
+    C_COMPILER_ERROR,
+    C_COMPILER_WARNING,
+    IO_ERROR,
+    ENTRY_POINT_ERROR,
+    UNDECLARED_NAME,
+    MISMATCHED_TYPES,
+    NON_EXHAUSTIVE_MATCH,
+    ARITY_MISMATCH,
+    REDECLARATION,
+];
+
+impl Diagnostic {
+    pub fn new(
+        severity: Severity,
+        code: &'static str,
+        message: &str,
+        position: SourceSpan,
+    ) -> Self {
+        Diagnostic {
+            severity,
+            code,
+            message: Box::from(message),
+            position,
+            labels: vec![],
+            fixes: vec![],
+        }
+    }
+
+    // error[E0008]: Identifier x is not declared.
+    //  --> main.llc:3:5
+    //   |
+    // 3 |     -> f(x);
+    //   |          ^
+    // The source lines are left out when the file cannot be read.
+    pub fn render(&self, sources: &SourceMap, color: bool) -> String {
+        let paint = |style: &str, text: &str| match color {
+            true => format!("{}{}{}", style, text, RESET),
+            false => text.to_string(),
+        };
+
+        let file = self.position.file;
+        let file_name = sources.name(file);
+        let rows = [self.position.position.begin.row]
+            .into_iter()
+            .chain(self.labels.iter().map(|l| l.position.begin.row));
+        let width = rows.map(|r| r.to_string().len()).max().unwrap_or(1);
+        let gutter = paint(BLUE, &format!("{} |", " ".repeat(width)));
+
+        let mut out = format!(
+            "{}{}",
+            paint(
+                self.severity.color(),
+                &format!("{}[{}]", self.severity, self.code)
+            ),
+            paint(BOLD, &format!(": {}", self.message))
+        );
+        // Errors about a whole file, like one that cannot be read, are at line 0.
+        let file_location = match self.position.position.begin.row {
+            0 => file_name.to_string(),
+            _ => format!("{}:{}", file_name, location(&self.position.position)),
+        };
+        out.push_str(&format!(
+            "\n{}{} {}",
+            " ".repeat(width),
+            paint(BLUE, "-->"),
+            file_location
+        ));
+
+        let mut snippets = vec![(self.position.position, None, self.severity.color(), '^')];
+        snippets.extend(
+            self.labels
+                .iter()
+                .map(|l| (l.position, Some(&l.message), BLUE, '-')),
+        );
+
+        for (position, message, style, mark) in snippets {
+            let line = match sources.line(&SourceSpan::new(file, position)) {
+                Some(line) => line,
+                None => {
+                    if let Some(message) = message {
+                        out.push_str(&format!(
+                            "\n{} {}: {}",
+                            paint(BLUE, &format!("{} =", " ".repeat(width))),
+                            paint(BOLD, "note"),
+                            message
+                        ));
+                        out.push_str(&format!(" at {}", location(&position)));
+                    }
+                    continue;
+                }
+            };
+
+            let (start, end) = underline(line, &position);
+            // Tabs are kept under the line, so the marks stay aligned whatever their width.
+            let indent: String = line
+                .chars()
+                .take(start)
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            let marks = mark.to_string().repeat(end - start + 1);
+            let mut under = format!("{}{}", indent, paint(style, &marks));
+            if let Some(message) = message {
+                under.push_str(&format!(" {}", paint(style, message)));
+            }
+
+            out.push_str(&format!(
+                "\n{}\n{} {}\n{} {}",
+                gutter,
+                paint(BLUE, &format!("{:>width$} |", position.begin.row)),
+                line,
+                gutter,
+                under
+            ));
+        }
+
+        for fix in self.fixes.iter() {
+            out.push_str(&format!(
+                "\n{} {}: {}",
+                paint(BLUE, &format!("{} =", " ".repeat(width))),
+                paint(BOLD, "help"),
+                fix.message
+            ));
+        }
+
+        out
+    }
+}
+
+fn location(position: &FileSpan) -> String {
+    match position.begin.col {
+        0 => position.begin.row.to_string(),
+        col => format!("{}:{}", position.begin.row, col),
+    }
+}
+
+// Columns of the line to underline, counted from 0. A span over several lines is underlined up
+// to the end of its first line.
+fn underline(line: &str, position: &FileSpan) -> (usize, usize) {
+    let length = line.chars().count().max(1);
+
+    if position.begin.col == 0 {
+        let start = line.chars().take_while(|c| c.is_whitespace()).count();
+        return (start.min(length - 1), length - 1);
+    }
+
+    let start = (position.begin.col - 1).min(length - 1);
+    let end = match position.end.row == position.begin.row {
+        true => position.end.col.saturating_sub(1).clamp(start, length - 1),
+        false => length - 1,
+    };
+
+    (start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(row: usize, begin: usize, end: usize) -> FileSpan {
+        FileSpan::new(FilePosition::new(row, begin), FilePosition::new(row, end))
+    }
+
+    #[test]
+    fn inserts_in_an_empty_span_after_the_token() {
+        let fix = Fix::insert_after(span(2, 5, 10), ";", "Add ;.");
+
+        assert!(fix.is_insertion());
+        assert_eq!((fix.position.begin.col, fix.position.end.col), (11, 11));
+    }
+
+    #[test]
+    fn replaces_up_to_the_end_of_the_token() {
+        let fix = Fix::replace(span(2, 5, 10), "counter", "Rename.");
+
+        assert!(!fix.is_insertion());
+        assert_eq!((fix.position.begin.col, fix.position.end.col), (5, 11));
+        assert!(!Fix::replace(span(1, 3, 3), "b", "Rename.").is_insertion());
+    }
+
+    fn render(diagnostic: &Diagnostic, sources: &SourceMap) -> Vec<String> {
+        diagnostic
+            .render(sources, false)
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn underlines_the_span_in_the_source_line() {
+        let mut sources = SourceMap::new();
+        let file = sources.add("main.llc", "main := {\n    -> f(x);\n};\n");
+        let diagnostic = Diagnostic::new(
+            Severity::Error,
+            UNDECLARED_NAME,
+            "Identifier x is not declared.",
+            SourceSpan::new(file, span(2, 10, 10)),
+        );
+
+        assert_eq!(
+            render(&diagnostic, &sources),
+            [
+                "error[E0008]: Identifier x is not declared.",
+                " --> main.llc:2:10",
+                "  |",
+                "2 |     -> f(x);",
+                "  |          ^",
+            ]
+        );
+    }
+
+    #[test]
+    fn renders_labels_and_fixes_after_the_span() {
+        let mut sources = SourceMap::new();
+        let file = sources.add("main.llc", "x := 1;\nx := 2;\n");
+        let mut diagnostic = Diagnostic::new(
+            Severity::Error,
+            REDECLARATION,
+            "Identifier x is already declared.",
+            SourceSpan::new(file, span(2, 1, 1)),
+        );
+        diagnostic.labels.push(Label {
+            position: span(1, 1, 1),
+            message: Box::from("declared here"),
+        });
+        diagnostic
+            .fixes
+            .push(Fix::replace(span(2, 1, 1), "y", "Rename the declaration."));
+
+        assert_eq!(
+            render(&diagnostic, &sources),
+            [
+                "error[E0012]: Identifier x is already declared.",
+                " --> main.llc:2:1",
+                "  |",
+                "2 | x := 2;",
+                "  | ^",
+                "  |",
+                "1 | x := 1;",
+                "  | - declared here",
+                "  = help: Rename the declaration.",
+            ]
+        );
+    }
+
+    #[test]
+    fn renders_file_errors_without_a_line() {
+        let mut sources = SourceMap::new();
+        let file = sources.add("missing.llc", "");
+        let diagnostic = Diagnostic::new(
+            Severity::Error,
+            IO_ERROR,
+            "Cannot read the file.",
+            SourceSpan::file(file),
+        );
+
+        assert_eq!(
+            render(&diagnostic, &sources),
+            ["error[E0006]: Cannot read the file.", " --> missing.llc"]
+        );
+    }
+
+    #[test]
+    fn colors_the_severity_only_when_asked() {
+        let mut sources = SourceMap::new();
+        let file = sources.add("main.llc", "x := 1;\n");
+        let diagnostic = Diagnostic::new(
+            Severity::Warning,
+            C_COMPILER_WARNING,
+            "Unused variable.",
+            SourceSpan::new(file, span(1, 1, 1)),
+        );
+
+        assert!(diagnostic.render(&sources, true).starts_with(YELLOW));
+        assert!(!diagnostic.render(&sources, false).contains('\x1b'));
+    }
+}
@@ -11,6 +11,7 @@ pub struct Identifier {
 #[derive(PartialEq, Eq, Clone)]
 pub enum TypeState {
     Unchecked,
+    InProgress,
     Ok(Type),
     Invalid,
 }
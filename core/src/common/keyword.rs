@@ -5,6 +5,17 @@ use phf::phf_map;
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Keyword {
     Use,
+    If,
+    Else,
+    While,
+    For,
+    In,
+    Break,
+    Continue,
+    Struct,
+    Enum,
+    Match,
+    Extern,
 }
 
 impl Display for Keyword {
@@ -14,18 +25,37 @@ impl Display for Keyword {
             "{}",
             match self {
                 Self::Use => "use",
+                Self::If => "if",
+                Self::Else => "else",
+                Self::While => "while",
+                Self::For => "for",
+                Self::In => "in",
+                Self::Break => "break",
+                Self::Continue => "continue",
+                Self::Struct => "struct",
+                Self::Enum => "enum",
+                Self::Match => "match",
+                Self::Extern => "extern",
             }
         )
     }
 }
 
 pub fn parse_keyword(s: &str) -> Option<Keyword> {
-    match KEYWORD_MAP.get(s) {
-        None => None,
-        Some(k) => Some(*k),
-    }
+    KEYWORD_MAP.get(s).copied()
 }
 
 static KEYWORD_MAP: phf::Map<&str, Keyword> = phf_map! {
-    "use" => Keyword::Use
+    "use" => Keyword::Use,
+    "if" => Keyword::If,
+    "else" => Keyword::Else,
+    "while" => Keyword::While,
+    "for" => Keyword::For,
+    "in" => Keyword::In,
+    "break" => Keyword::Break,
+    "continue" => Keyword::Continue,
+    "struct" => Keyword::Struct,
+    "enum" => Keyword::Enum,
+    "match" => Keyword::Match,
+    "extern" => Keyword::Extern
 };
@@ -10,38 +10,6 @@ pub struct LiteralValue {
 
 impl Display for LiteralValue {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let t = match &self.llc_type {
-            Type::Char => "char".to_string(),
-            Type::String => "string".to_string(),
-            Type::Float { signed, size } => format!(
-                "{}f{}",
-                match signed {
-                    true => "s",
-                    false => "u",
-                },
-                size
-            ),
-            Type::Integer { signed, size } => format!(
-                "{}i{}",
-                match signed {
-                    true => "s",
-                    false => "u",
-                },
-                size
-            ),
-            Type::Block { input, output } => format!(
-                "{} -> {}",
-                match input {
-                    Some(params) => "",
-                    None => "()",
-                },
-                match output {
-                    Some(ret) => todo!(),
-                    None => "{}",
-                }
-            ),
-        };
-
-        write!(f, "{}", format!("Literal {} ({})", t, self.value))
+        write!(f, "Literal {} ({})", self.llc_type, self.value)
     }
 }
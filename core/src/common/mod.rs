@@ -1,5 +1,8 @@
+pub mod compile_error;
+pub mod diagnostic;
 pub mod identifier;
 pub mod position;
+pub mod source_map;
 pub mod keyword;
 pub mod literal;
 pub mod operator;
@@ -19,6 +19,16 @@ pub enum Operator {
     Into,
     Return,
     NameSpaceNav,
+    Dot,
+    Range,
+    Ampersand,
+    Pipe,
+    Not,
+    And,
+    Or,
+    NotEqual,
+    GreaterOrEqual,
+    LessOrEqual,
 }
 
 impl Display for Operator {
@@ -42,6 +52,16 @@ impl Display for Operator {
                 Self::Into => "Into",
                 Self::Return => "Return",
                 Self::NameSpaceNav => "NameSpaceNav",
+                Self::Dot => "Dot",
+                Self::Range => "Range",
+                Self::Ampersand => "Ampersand",
+                Self::Pipe => "Pipe",
+                Self::Not => "Not",
+                Self::And => "And",
+                Self::Or => "Or",
+                Self::NotEqual => "NotEqual",
+                Self::GreaterOrEqual => "GreaterOrEqual",
+                Self::LessOrEqual => "LessOrEqual",
             }
         )
     }
@@ -49,31 +69,63 @@ impl Display for Operator {
 
 impl Operator {
     pub fn is_composite(&self) -> bool {
-        match self {
+        matches!(
+            self,
             Self::Declassignment
-            | Self::NameSpaceNav
-            | Self::Return
-            | Self::Into
+                | Self::NameSpaceNav
+                | Self::Range
+                | Self::And
+                | Self::Or
+                | Self::NotEqual
+                | Self::GreaterOrEqual
+                | Self::LessOrEqual
+                | Self::Return
+                | Self::Into
+                | Self::LessThan
+                | Self::Equality
+                | Self::GreaterThan
+        )
+    }
+
+    // Text of the operator in the source.
+    pub fn symbol(&self) -> String {
+        COMPOSITE_OPERATOR_MAP
+            .entries()
+            .find(|(_, op)| *op == self)
+            .map(|(s, _)| s.to_string())
+            .or_else(|| {
+                CHAR_OPERATOR_MAP
+                    .entries()
+                    .find(|(_, op)| *op == self)
+                    .map(|(c, _)| c.to_string())
+            })
+            .unwrap_or_default()
+    }
+
+    // Binding power of the operator when used between two expressions, higher binds tighter.
+    pub fn binary_precedence(&self) -> Option<u8> {
+        match self {
+            Self::Or => Some(1),
+            Self::And => Some(2),
+            Self::Equality
+            | Self::NotEqual
+            | Self::GreaterThan
             | Self::LessThan
-            | Self::Equality
-            | Self::GreaterThan => true,
-            _ => false,
+            | Self::GreaterOrEqual
+            | Self::LessOrEqual => Some(3),
+            Self::Plus | Self::Minus => Some(4),
+            Self::Times | Self::Divide | Self::Modulus => Some(5),
+            _ => None,
         }
     }
 }
 
 pub fn parse_operator(c: char) -> Option<Operator> {
-    match CHAR_OPERATOR_MAP.get(&c) {
-        None => None,
-        Some(op) => Some(*op),
-    }
+    CHAR_OPERATOR_MAP.get(&c).copied()
 }
 
 pub fn parse_comp_operator(s: &str) -> Option<Operator> {
-    match COMPOSITE_OPERATOR_MAP.get(s) {
-        None => None,
-        Some(op) => Some(*op),
-    }
+    COMPOSITE_OPERATOR_MAP.get(s).copied()
 }
 
 static CHAR_OPERATOR_MAP: phf::Map<char, Operator> = phf_map!(
@@ -86,6 +138,10 @@ static CHAR_OPERATOR_MAP: phf::Map<char, Operator> = phf_map!(
     ':' => Operator::Declaration,
     '>' => Operator::GreaterThan,
     '<' => Operator::LessThan,
+    '.' => Operator::Dot,
+    '&' => Operator::Ampersand,
+    '|' => Operator::Pipe,
+    '!' => Operator::Not,
     '_' => Operator::Discard
 );
 
@@ -94,5 +150,11 @@ static COMPOSITE_OPERATOR_MAP: phf::Map<&str, Operator> = phf_map!(
     "=>" => Operator::Return,
     "==" => Operator::Equality,
     ":=" => Operator::Declassignment,
-    "::" => Operator::NameSpaceNav
+    "::" => Operator::NameSpaceNav,
+    ".." => Operator::Range,
+    "&&" => Operator::And,
+    "||" => Operator::Or,
+    "!=" => Operator::NotEqual,
+    ">=" => Operator::GreaterOrEqual,
+    "<=" => Operator::LessOrEqual
 );
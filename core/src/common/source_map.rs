@@ -0,0 +1,127 @@
+use std::fs;
+
+use super::{
+    compile_error::CompileError,
+    position::{FilePosition, FileSpan},
+};
+
+// Index of a source in its SourceMap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FileId(pub usize);
+
+// Position in one of the sources of a SourceMap.
+#[derive(Debug, Clone, Copy)]
+pub struct SourceSpan {
+    pub file: FileId,
+    pub position: FileSpan,
+}
+
+pub struct SourceFile {
+    // Path of the file, or a name given to a source which is not saved like an editor buffer.
+    pub name: Box<str>,
+    pub content: String,
+    // Why the file cannot be read, its content is then empty.
+    pub error: Option<Box<str>>,
+}
+
+// Sources of the files of a project, read from the disk or given as text. A name is only ever
+// bound to one source, so the same text is used to compile a file and to report its errors.
+#[derive(Default)]
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+}
+
+impl SourceSpan {
+    pub fn new(file: FileId, position: FileSpan) -> Self {
+        SourceSpan { file, position }
+    }
+
+    // Errors about a whole file, like one that cannot be read, are at line 0.
+    pub fn file(file: FileId) -> Self {
+        let position = FilePosition::new(0, 0);
+        SourceSpan::new(file, FileSpan::new(position, position))
+    }
+}
+
+impl SourceMap {
+    pub fn new() -> Self {
+        SourceMap::default()
+    }
+
+    // Adds a source which does not have to exist on the disk, it replaces the previous source of
+    // the same name.
+    pub fn add(&mut self, name: &str, content: &str) -> FileId {
+        if let Some(id) = self.find(name) {
+            self.files[id.0].content = content.to_string();
+            self.files[id.0].error = None;
+            return id;
+        }
+
+        self.files.push(SourceFile {
+            name: Box::from(name),
+            content: content.to_string(),
+            error: None,
+        });
+        FileId(self.files.len() - 1)
+    }
+
+    // Reads the file once, its source is kept for the next loads. A file which cannot be read is
+    // kept too, so its errors have a file to refer to.
+    pub fn load(&mut self, name: &str) -> Result<FileId, CompileError> {
+        let id = match self.find(name) {
+            Some(id) => id,
+            None => {
+                let (content, error) = match read(name) {
+                    Ok(content) => (content, None),
+                    Err(reason) => (String::new(), Some(reason.into_boxed_str())),
+                };
+                self.files.push(SourceFile {
+                    name: Box::from(name),
+                    content,
+                    error,
+                });
+                FileId(self.files.len() - 1)
+            }
+        };
+
+        match &self.get(id).error {
+            Some(reason) => Err(CompileError::Io {
+                file: id,
+                reason: reason.clone(),
+            }),
+            None => Ok(id),
+        }
+    }
+
+    pub fn find(&self, name: &str) -> Option<FileId> {
+        self.files.iter().position(|f| *f.name == *name).map(FileId)
+    }
+
+    pub fn get(&self, id: FileId) -> &SourceFile {
+        &self.files[id.0]
+    }
+
+    pub fn name(&self, id: FileId) -> &str {
+        &self.files[id.0].name
+    }
+
+    pub fn files(&self) -> impl Iterator<Item = (FileId, &SourceFile)> {
+        self.files.iter().enumerate().map(|(i, f)| (FileId(i), f))
+    }
+
+    // Line of the source the span begins on, rows are counted from 1.
+    pub fn line(&self, span: &SourceSpan) -> Option<&str> {
+        let row = span.position.begin.row.checked_sub(1)?;
+        self.get(span.file).content.lines().nth(row)
+    }
+}
+
+fn read(file_name: &str) -> Result<String, String> {
+    let bytes = match fs::read(file_name) {
+        Ok(data) => data,
+        Err(error) => return Err(format!("The file cannot be opened: {}.", error)),
+    };
+
+    String::from_utf8(bytes)
+        .map_err(|error| format!("The file is not valid UTF-8: {}.", error.utf8_error()))
+}
@@ -2,31 +2,31 @@ use std::fmt::Display;
 
 use crate::lexer::token::Token;
 
-use super::position::FileSpan;
+use super::{
+    diagnostic::{self, Diagnostic, Fix, Severity},
+    position::FileSpan,
+    source_map::{FileId, SourceSpan},
+};
 
-#[derive(Debug)]
-pub enum CompileError {
-    Syntax(SyntaxError),
-}
-
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SyntaxError {
     pub position: FileSpan,
     pub reason: Box<str>,
+    pub fixes: Vec<Fix>,
 }
 
-impl<'a> Display for SyntaxError {
+impl Display for SyntaxError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let msg = if self.position.begin.row == self.position.end.row {
             format!(
-                "At line {0}, col {1}:{2}. {3}",
+                "At line {0}, col {1}:{2}. SyntaxError: {3}",
                 self.position.begin.row,
                 self.position.begin.col,
                 self.position.end.col,
                 self.reason
             )
         } else {
-            format!("{0}. {1}", self.position, self.reason)
+            format!("{0}. SyntaxError: {1}", self.position, self.reason)
         };
 
         write!(f, "{}", msg)
@@ -38,9 +38,26 @@ impl SyntaxError {
         SyntaxError {
             position: token.position, 
             reason: match reason {
-                Some(str) => Box::from(format!("SyntaxError: {}", str)),
-                None => Box::from(format!("SyntaxError. {}", token.kind)),
+                Some(str) => str,
+                None => Box::from(format!("Unexpected {}.", token.kind)),
             },
+            fixes: vec![],
         }
     }
+
+    pub fn with_fix(mut self, fix: Fix) -> Self {
+        self.fixes.push(fix);
+        self
+    }
+
+    pub fn diagnostic(&self, file: FileId) -> Diagnostic {
+        let mut diagnostic = Diagnostic::new(
+            Severity::Error,
+            diagnostic::SYNTAX_ERROR,
+            &self.reason,
+            SourceSpan::new(file, self.position),
+        );
+        diagnostic.fixes = self.fixes.clone();
+        diagnostic
+    }
 }
\ No newline at end of file
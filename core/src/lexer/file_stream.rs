@@ -22,7 +22,7 @@ impl FileStream {
         }
     }
 
-    pub fn get_next<'a>(&'a mut self) -> Option<&mut FileLine> {
+    pub fn get_next(&mut self) -> Option<&mut FileLine> {
         match self.lines.get_mut(self.current_index) {
             Some(i) => {
                 self.current_index += 1;
@@ -42,7 +42,7 @@ impl FileLine {
         }
     }
 
-    pub fn get_next<'a>(&'a mut self) -> Option<&char> {
+    pub fn get_next(&mut self) -> Option<&char> {
         match self.content.get(self.current_index) {
             Some(i) => {
                 self.current_index += 1;
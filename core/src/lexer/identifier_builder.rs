@@ -1,10 +1,10 @@
 use std::collections::HashMap;
 
-use crate::common::{identifier::{Identifier, TypeState}, keyword};
+use crate::common::{identifier::Identifier, keyword};
 
 use super::{file_stream::FileLine, token::{Token, TokenKind}};
 
-pub fn build_identifier<'a>(
+pub fn build_identifier(
     line: &mut FileLine,
     identifiers: &mut Vec<Identifier>,
     identifiers_index: &mut HashMap<Box<str>, usize>,
@@ -13,13 +13,14 @@ pub fn build_identifier<'a>(
     let from = line.current_index + 1;
     loop {
         if let Some(c) = line.get_next() {
-            if c.is_alphabetic() || *c == '_' {
+            if c.is_alphabetic() || *c == '_' || (c.is_numeric() && !identifier.is_empty()) {
                 identifier.push(*c);
                 continue;
             }
+
+            line.backtrack(1);
         }
 
-        line.backtrack(1);
         break;
     }
 
@@ -28,7 +29,7 @@ pub fn build_identifier<'a>(
     }
 
     if let Some(keyword) = keyword::parse_keyword(&identifier) {
-        return Some(Token::new(TokenKind::Keyword(keyword), line.number, from, line.current_index))
+        return Some(Token::new(TokenKind::Keyword(keyword), line.number + 1, from, line.current_index))
     }
 
     let to = from + identifier.len() -1;
@@ -44,7 +45,7 @@ pub fn build_identifier<'a>(
 
     Some(Token::new(
         TokenKind::Identifier { index },
-        line.number,
+        line.number + 1,
         from,
         to
     ))
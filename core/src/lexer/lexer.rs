@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fs, vec};
+use std::{collections::HashMap, vec};
 
 use super::{
     file_stream::FileLine,
@@ -9,23 +9,60 @@ use super::{
     token_stream::TokenStream,
 };
 use crate::{
-    common::{identifier::Identifier, syntax_error::SyntaxError},
+    common::{
+        identifier::Identifier,
+        source_map::{FileId, SourceMap},
+        syntax_error::SyntaxError,
+    },
     lexer::file_stream::FileStream,
 };
 
 pub struct LexedFile {
     pub file_name: Box<str>,
+    pub file_id: FileId,
     pub stream: TokenStream,
     pub errors: Vec<SyntaxError>,
     pub identifiers: Vec<Identifier>,
 }
 
-pub fn get_tokens(file_name: &str) -> LexedFile {
-    let mut file = get_file_stream(file_name);
+impl LexedFile {
+    pub fn tokens(&self) -> &[Token] {
+        &self.stream.tokens
+    }
+
+    // Text of the token in the source when it has one, identifiers are resolved to their name.
+    pub fn token_value(&self, token: &Token) -> Option<String> {
+        match &token.kind {
+            TokenKind::Identifier { index } => Some(self.identifiers[*index].name.to_string()),
+            TokenKind::Literal(lit) => Some(lit.value.to_string()),
+            TokenKind::Operator(op) => Some(op.symbol()),
+            TokenKind::Keyword(k) => Some(k.to_string()),
+            TokenKind::Undefined(t) => Some(t.to_string()),
+            _ => None,
+        }
+    }
+
+    // Describes the token like TokenKind, with the name of identifiers rather than their index.
+    pub fn describe(&self, token: &Token) -> String {
+        match &token.kind {
+            TokenKind::Identifier { index } => {
+                format!("Identifier {}", self.identifiers[*index].name)
+            }
+            kind => kind.to_string(),
+        }
+    }
+}
+
+// The syntax errors are kept in the lexed file.
+pub fn get_tokens(sources: &SourceMap, file_id: FileId) -> LexedFile {
+    let source = sources.get(file_id);
+    let file_name = &source.name;
+    let mut file = FileStream::new(&source.content);
     let mut identifiers_index: HashMap<Box<str>, usize> = HashMap::new();
 
     let mut lexer = LexedFile {
-        file_name: file_name.to_owned().into_boxed_str(),
+        file_name: file_name.clone(),
+        file_id,
         stream: TokenStream::new(vec![]),
         errors: vec![],
         identifiers: vec![],
@@ -53,11 +90,11 @@ pub fn get_tokens(file_name: &str) -> LexedFile {
             };
         }
 
-        if is_comment_line(&mut current_line) {
+        if is_comment_line(current_line) {
             continue;
         }
 
-        if eat_white_spaces(&mut current_line) {
+        if eat_white_spaces(current_line) {
             continue;
         }
 
@@ -83,34 +120,20 @@ pub fn get_tokens(file_name: &str) -> LexedFile {
             continue;
         }
 
-        if let Some(token) = current_line.get_next() {
+        if let Some(c) = current_line.get_next() {
+            let reason = format!("Undefined token {}.", c);
             let token = Token::single_char(
-                TokenKind::Undefined(token.to_string().into_boxed_str()),
-                current_line.number,
+                TokenKind::Undefined(c.to_string().into_boxed_str()),
+                current_line.number + 1,
                 current_line.current_index,
             );
 
-            lexer.errors.push(SyntaxError::from_token(
-                &token,
-                Some(Box::from(format!("Undefined token. {token}"))),
-            ))
+            lexer.errors.push(SyntaxError::from_token(&token, Some(reason.into_boxed_str())))
         }
     }
     lexer
 }
 
-fn get_file_stream(filename: &str) -> FileStream {
-    let bytes = match fs::read(filename) {
-        Ok(data) => data,
-        Err(error) => panic!("Error happened opening the file: {}", error),
-    };
-
-    match std::str::from_utf8(&bytes) {
-        Ok(data) => FileStream::new(data),
-        Err(error) => panic!("Error happend while reading the file: {}", error),
-    }
-}
-
 fn is_comment_line(line: &mut FileLine) -> bool {
     let f = match line.get_next() {
         None => false,
@@ -143,24 +166,26 @@ fn eat_white_spaces(line: &mut FileLine) -> bool {
         Some(c) => match c {
             c if !c.is_whitespace() => {
                 line.backtrack(1);
-                return false;
+                false
             }
-            _ => loop {
+            _ => {
                 eat_white_spaces(line);
-                return true;
-            },
+                true
+            }
         },
         None => false,
     }
 }
 
-fn build_single_char_token<'a>(line: &mut FileLine) -> Option<Token> {
+fn build_single_char_token(line: &mut FileLine) -> Option<Token> {
     if let Some(c) = line.get_next() {
         let value = match c {
             '{' => TokenKind::OpenCurly,
             '}' => TokenKind::ClosingCurly,
             '(' => TokenKind::OpenParenthesis,
             ')' => TokenKind::ClosingParenthesis,
+            '[' => TokenKind::OpenBracket,
+            ']' => TokenKind::ClosingBracket,
             ',' => TokenKind::Comma,
             ';' => TokenKind::EOI,
             _ => {
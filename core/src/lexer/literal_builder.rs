@@ -12,7 +12,7 @@ use super::{
     token::{Token, TokenKind},
 };
 
-pub fn build_literal<'a>(line: &mut FileLine, errors: &mut Vec<SyntaxError>) -> Option<Token> {
+pub fn build_literal(line: &mut FileLine, errors: &mut Vec<SyntaxError>) -> Option<Token> {
     if let Some(token) = build_literal_str(line) {
         return Some(token);
     }
@@ -21,10 +21,14 @@ pub fn build_literal<'a>(line: &mut FileLine, errors: &mut Vec<SyntaxError>) ->
         return Some(token);
     }
 
+    if let Some(token) = build_literal_word(line) {
+        return Some(token);
+    }
+
     None
 }
 
-fn build_literal_str<'a>(line: &mut FileLine) -> Option<Token> {
+fn build_literal_str(line: &mut FileLine) -> Option<Token> {
     let mut lit = String::new();
     let from = line.current_index + 1;
 
@@ -61,36 +65,40 @@ fn build_literal_str<'a>(line: &mut FileLine) -> Option<Token> {
     }
 }
 
-fn build_literal_num<'a>(line: &mut FileLine, errors: &mut Vec<SyntaxError>) -> Option<Token> {
+fn build_literal_num(line: &mut FileLine, errors: &mut Vec<SyntaxError>) -> Option<Token> {
     let mut lit = String::new();
     let from = line.current_index + 1;
     let mut has_decimal = false;
     loop {
-        if let Some(i) = line.get_next() {
-            if *i == '.' {
+        if let Some(&i) = line.get_next() {
+            // A dot only belongs to the number when a digit follows it, 0..n is a range.
+            if i == '.' && line.content.get(line.current_index).is_some_and(|c| c.is_numeric()) {
                 if has_decimal {
-                    let pos = FilePosition::new(line.number, line.current_index + 1);
+                    let pos = FilePosition::new(line.number + 1, line.current_index + 1);
                     errors.push(SyntaxError {
                         position: FileSpan::new(pos, pos),
                         reason: Box::from(
                             "Floating point literal cannot contain more than one decimal point.",
                         ),
+                        fixes: vec![],
                     });
                     return None;
                 }
 
-                has_decimal = true
+                has_decimal = true;
+                lit.push(i);
+                continue;
             }
-            if *i == '_' {
+            if i == '_' {
                 continue;
             }
             if i.is_numeric() {
-                lit.push(*i);
+                lit.push(i);
                 continue;
             }
-        }
 
-        line.backtrack(1);
+            line.backtrack(1);
+        }
 
         return match lit.is_empty() {
             true => None,
@@ -119,3 +127,29 @@ fn build_literal_num<'a>(line: &mut FileLine, errors: &mut Vec<SyntaxError>) ->
         };
     }
 }
+
+// true, false and null are only literals as whole words, truely is an identifier.
+fn build_literal_word(line: &mut FileLine) -> Option<Token> {
+    let from = line.current_index + 1;
+    let word: String = line.content[line.current_index..]
+        .iter()
+        .take_while(|c| c.is_alphanumeric() || **c == '_')
+        .collect();
+
+    let llc_type = match word.as_str() {
+        "true" | "false" => Type::Bool,
+        "null" => Type::Null,
+        _ => return None,
+    };
+
+    line.current_index += word.len();
+    Some(Token::new(
+        TokenKind::Literal(LiteralValue {
+            llc_type,
+            value: word.into_boxed_str(),
+        }),
+        line.number + 1,
+        from,
+        line.current_index,
+    ))
+}
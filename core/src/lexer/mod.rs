@@ -2,7 +2,10 @@ pub(crate) mod identifier_builder;
 pub mod token;
 
 pub(crate) mod file_stream;
+#[allow(clippy::module_inception)]
 pub(crate) mod lexer;
 pub(crate) mod literal_builder;
 pub(crate) mod operator_builder;
 pub(crate) mod token_stream;
+
+pub use lexer::{get_tokens, LexedFile};
@@ -5,7 +5,7 @@ use super::{
     token::{Token, TokenKind},
 };
 
-pub fn build_operator<'a>(line: &mut FileLine) -> Option<Vec<Token>> {
+pub fn build_operator(line: &mut FileLine) -> Option<Vec<Token>> {
     let mut result = Vec::new();
 
     match get_operator(line) {
@@ -43,10 +43,7 @@ enum OperatorBuilderResult {
 
 fn get_operator(line: &mut FileLine) -> OperatorBuilderResult {
     let mut get_op = || -> Option<(char, Operator)> {
-        let c = match line.get_next() {
-            Some(c) => c,
-            None => return None,
-        };
+        let c = line.get_next()?;
 
         let op = match operator::parse_operator(*c) {
             Some(op) => op,
@@ -6,6 +6,7 @@ use crate::common::{
     operator::Operator,
     position::{FilePosition, FileSpan},
 };
+use crate::type_system::llc_type::Type;
 
 #[derive(Debug)]
 pub struct Token {
@@ -26,6 +27,8 @@ pub enum TokenKind {
     ClosingParenthesis,
     OpenCurly,
     ClosingCurly,
+    OpenBracket,
+    ClosingBracket,
     Comma,
     EOI,
     EOF,
@@ -47,7 +50,30 @@ impl Token {
     }
 }
 
-impl<'a> Display for Token {
+impl TokenKind {
+    // Name of the variant, for the outputs read by tools.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Null => "Null",
+            Self::Undefined(_) => "Undefined",
+            Self::Literal(_) => "Literal",
+            Self::Operator(_) => "Operator",
+            Self::Identifier { .. } => "Identifier",
+            Self::Keyword(_) => "Keyword",
+            Self::OpenParenthesis => "OpenParenthesis",
+            Self::ClosingParenthesis => "ClosingParenthesis",
+            Self::OpenCurly => "OpenCurly",
+            Self::ClosingCurly => "ClosingCurly",
+            Self::OpenBracket => "OpenBracket",
+            Self::ClosingBracket => "ClosingBracket",
+            Self::Comma => "Comma",
+            Self::EOI => "EOI",
+            Self::EOF => "EOF",
+        }
+    }
+}
+
+impl Display for Token {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "{}: {}.", self.position, self.kind)
     }
@@ -60,15 +86,21 @@ impl Display for TokenKind {
             "{}",
             match self {
                 Self::Null => String::from("Null token"),
-                Self::Undefined(t) => format!("Undefined: {t}"),
-                Self::Literal(lit) => format!("Literal value: {lit}"),
-                Self::Operator(op) => format!("Operator: {op}"),
-                Self::Identifier {index}=> format!("Identifier: {index}"),
-                Self::Keyword(k) => format!("Keyword: {k}"),
+                Self::Undefined(t) => format!("Undefined token {t}"),
+                Self::Literal(lit) => match lit.llc_type {
+                    Type::String => format!("Literal \"{}\"", lit.value),
+                    _ => format!("Literal {}", lit.value),
+                },
+                Self::Operator(op) => format!("Operator {}", op.symbol()),
+                // The name is in the identifiers of the file, see FileAst::describe.
+                Self::Identifier { .. } => String::from("Identifier"),
+                Self::Keyword(k) => format!("Keyword {k}"),
                 Self::OpenParenthesis => String::from("Opening parenthesis"),
                 Self::ClosingParenthesis => String::from("Closing parenthesis"),
                 Self::OpenCurly => String::from("Opening curly brace"),
                 Self::ClosingCurly => String::from("Closing curly brace"),
+                Self::OpenBracket => String::from("Opening bracket"),
+                Self::ClosingBracket => String::from("Closing bracket"),
                 Self::Comma => String::from("Comma"),
                 Self::EOI => String::from("End of instruction"),
                 Self::EOF => String::from("End of file"),
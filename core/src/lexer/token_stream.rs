@@ -47,7 +47,7 @@ impl TokenStream {
         match compare(token) {
             Some(val) => {
                 self.current_index += 1;
-                return Some(val);
+                Some(val)
             }
             None => None,
         }
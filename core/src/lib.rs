@@ -1,4 +1,5 @@
 pub mod common;
-pub(crate) mod lexer;
+pub mod lexer;
 pub mod parser;
-pub(crate) mod type_system;
+pub mod project;
+pub mod type_system;
@@ -0,0 +1,122 @@
+use crate::{
+    common::{operator::Operator, position::FileSpan, syntax_error::SyntaxError},
+    lexer::{token::TokenKind, token_stream::TokenStream},
+};
+
+use super::{
+    ast_node::{AstNode, AstNodeKind, NodeParent, ParsingResult},
+    expression::{self, Expression},
+    parser::FileAst,
+};
+
+// [value, ...], a trailing comma is allowed.
+pub(in crate::parser) fn parse_literal(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
+    let begin = match stream.take_if(|t| match t.kind {
+        TokenKind::OpenBracket => Some(t.position),
+        _ => None,
+    }) {
+        Some(pos) => pos,
+        None => return ParsingResult::Other,
+    };
+
+    let mut elements = vec![];
+
+    let end = loop {
+        if let Some(end) = stream.take_if(|t| match t.kind {
+            TokenKind::ClosingBracket => Some(t.position),
+            _ => None,
+        }) {
+            break end;
+        }
+
+        match expression::parse(stream, file_ast) {
+            ParsingResult::Ok => elements.push(file_ast.nodes.len() - 1),
+            ParsingResult::Error => return ParsingResult::Error,
+            ParsingResult::Other => {
+                let token = stream.peek(0);
+                let reason = format!("Expected an array element but found {}.", file_ast.describe(token));
+                file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+                return ParsingResult::Error;
+            }
+        }
+
+        if !stream.skip_if(|t| t.kind == TokenKind::Comma) && stream.peek(0).kind != TokenKind::ClosingBracket {
+            let token = stream.peek(0);
+            let reason = format!("Expected , or ] in the array elements but found {}.", file_ast.describe(token));
+            file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+            return ParsingResult::Error;
+        }
+    };
+
+    file_ast.nodes.push(AstNode {
+        kind: AstNodeKind::Expression(Expression::ArrayLiteral { elements }),
+        position: FileSpan::combine(&begin, &end),
+        parent: NodeParent::Unchecked
+    });
+
+    ParsingResult::Ok
+}
+
+// value[index] or value[start..end], called once the indexed value has been parsed. Both
+// bounds of a slicing are optional, value[..] slices every element.
+pub(in crate::parser) fn parse_index(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
+    let object = file_ast.nodes.len() - 1;
+    stream.skip(1);
+
+    let start = match stream.peek(0).kind {
+        TokenKind::Operator(Operator::Range) => None,
+        _ => match parse_bound(stream, file_ast, "an index") {
+            Some(start) => Some(start),
+            None => return ParsingResult::Error,
+        },
+    };
+
+    let kind = match stream.skip_if(|t| t.kind == TokenKind::Operator(Operator::Range)) {
+        true => {
+            let end = match stream.peek(0).kind {
+                TokenKind::ClosingBracket => None,
+                _ => match parse_bound(stream, file_ast, "the end of the slice") {
+                    Some(end) => Some(end),
+                    None => return ParsingResult::Error,
+                },
+            };
+
+            Expression::Slicing { object, start, end, sequence: None }
+        }
+        false => Expression::Index { object, index: start.unwrap(), sequence: None },
+    };
+
+    let end = match stream.take_if(|t| match t.kind {
+        TokenKind::ClosingBracket => Some(t.position),
+        _ => None,
+    }) {
+        Some(end) => end,
+        None => {
+            let token = stream.peek(0);
+            let reason = format!("Expected ] after the index but found {}.", file_ast.describe(token));
+            file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+            return ParsingResult::Error;
+        }
+    };
+
+    file_ast.nodes.push(AstNode {
+        kind: AstNodeKind::Expression(kind),
+        position: FileSpan::combine(&file_ast.nodes[object].position, &end),
+        parent: NodeParent::Unchecked
+    });
+
+    ParsingResult::Ok
+}
+
+fn parse_bound(stream: &mut TokenStream, file_ast: &mut FileAst, context: &str) -> Option<usize> {
+    match expression::parse(stream, file_ast) {
+        ParsingResult::Ok => Some(file_ast.nodes.len() - 1),
+        ParsingResult::Error => None,
+        ParsingResult::Other => {
+            let token = stream.peek(0);
+            let reason = format!("Expected {} but found {}.", context, file_ast.describe(token));
+            file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+            None
+        }
+    }
+}
@@ -1,7 +1,45 @@
-use crate::lexer::token_stream::TokenStream;
+use crate::{
+    common::{operator::Operator, position::FileSpan, syntax_error::SyntaxError},
+    lexer::{token::TokenKind, token_stream::TokenStream},
+};
 
-use super::{parser::FileAst, ast_node::ParsingResult};
+use super::{
+    ast_node::{AstNode, AstNodeKind, NodeParent, ParsingResult},
+    expression,
+    parser::FileAst,
+    statement::Statement,
+};
 
-//pub(in crate::parser) fn parse(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
-//    
-//}
\ No newline at end of file
+// Turns the expression that was just parsed into the target of an assignment when it is
+// followed by =, whether it can be assigned is left to the type checker.
+pub(in crate::parser) fn parse(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
+    if !stream.skip_if(|t| t.kind == TokenKind::Operator(Operator::Assignment)) {
+        return ParsingResult::Other;
+    }
+
+    let target = file_ast.nodes.len() - 1;
+
+    let exp_index = match expression::parse(stream, file_ast) {
+        ParsingResult::Ok => file_ast.nodes.len() - 1,
+        ParsingResult::Error => return ParsingResult::Error,
+        ParsingResult::Other => {
+            file_ast.errors.push(SyntaxError::from_token(
+                stream.peek(0),
+                Some(Box::from("Expected a value after operator =")),
+            ));
+
+            return ParsingResult::Error;
+        }
+    };
+
+    file_ast.nodes.push(AstNode {
+        kind: AstNodeKind::Statement(Statement::Assignment { target, exp_index }),
+        position: FileSpan::combine(
+            &file_ast.nodes[target].position,
+            &file_ast.nodes[exp_index].position,
+        ),
+        parent: NodeParent::Unchecked
+    });
+
+    ParsingResult::Ok
+}
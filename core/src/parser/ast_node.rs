@@ -1,10 +1,12 @@
 use super::{
+    block::Signature,
     expression::Expression,
+    match_expression::Pattern,
     parser::FileAst,
     statement::{self, Statement},
 };
 use crate::{common::position::FileSpan, lexer::token_stream::TokenStream};
-use std::{fmt::Display, default};
+use std::fmt::Display;
 
 pub enum ParsingResult {
     Ok,
@@ -35,22 +37,134 @@ pub(in crate::parser) fn parse(stream: &mut TokenStream, file_ast: &mut FileAst)
     statement::parse(stream, file_ast)
 }
 
-impl Display for AstNode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let s: String = match &self.kind {
+impl AstNode {
+    // Name of the kind of node, for the outputs read by tools.
+    pub fn name(&self) -> &'static str {
+        match &self.kind {
+            AstNodeKind::Statement(stmt) => match stmt {
+                Statement::Declaration { .. } => "Declaration",
+                Statement::Assignment { .. } => "Assignment",
+                Statement::Expression => "ExpressionStatement",
+                Statement::Return { .. } => "Return",
+                Statement::Parameter { .. } => "Parameter",
+                Statement::While { .. } => "While",
+                Statement::For { .. } => "For",
+                Statement::Field { .. } => "Field",
+                Statement::FieldValue { .. } => "FieldValue",
+                Statement::Variant { .. } => "Variant",
+                Statement::MatchArm { .. } => "MatchArm",
+                Statement::Break => "Break",
+                Statement::Continue => "Continue",
+                Statement::Use { .. } => "Use",
+            },
+            AstNodeKind::Expression(exp) => match exp {
+                Expression::Block { .. } => "BlockExpression",
+                Expression::FunctionCall { .. } => "FunctionCall",
+                Expression::Identifier { .. } => "Identifier",
+                Expression::Literal(_) => "Literal",
+                Expression::Binary { .. } => "BinaryOperation",
+                Expression::Unary { .. } => "UnaryOperation",
+                Expression::If { .. } => "IfExpression",
+                Expression::StructDefinition { .. } => "StructDefinition",
+                Expression::StructLiteral { .. } => "StructLiteral",
+                Expression::FieldAccess { .. } => "FieldAccess",
+                Expression::EnumDefinition { .. } => "EnumDefinition",
+                Expression::EnumVariant { .. } => "EnumVariant",
+                Expression::Match { .. } => "MatchExpression",
+                Expression::Path { .. } => "Path",
+                Expression::ArrayLiteral { .. } => "ArrayLiteral",
+                Expression::Extern { .. } => "Extern",
+                Expression::Index { .. } => "Index",
+                Expression::Slicing { .. } => "Slicing",
+            },
+        }
+    }
+
+    // Nodes held by this one, in the order of the source.
+    pub fn children(&self) -> Vec<usize> {
+        match &self.kind {
             AstNodeKind::Statement(stmt) => match stmt {
-                Statement::Declaration { .. } => "Declaration".to_string(),
-                Statement::Expression { .. } => "ExpressionStatement".to_string(),
-                Statement::Return => "Return".to_string(),
+                Statement::Declaration { ident_index, exp_index, .. } => {
+                    [*ident_index].into_iter().chain(*exp_index).collect()
+                }
+                Statement::Assignment { target, exp_index } => vec![*target, *exp_index],
+                Statement::Return { value } => value.iter().copied().collect(),
+                Statement::Parameter { ident_index, .. }
+                | Statement::Field { ident_index, .. }
+                | Statement::Variant { ident_index, .. } => vec![*ident_index],
+                Statement::FieldValue { ident_index, exp_index } => vec![*ident_index, *exp_index],
+                Statement::MatchArm { pattern, statements } => {
+                    let mut children = match pattern {
+                        Pattern::Wildcard => vec![],
+                        Pattern::Variant { ident_index, bindings } => {
+                            [*ident_index].into_iter().chain(bindings.iter().flatten().copied()).collect()
+                        }
+                    };
+                    children.extend(statements);
+                    children
+                }
+                Statement::While { condition, statements } => {
+                    [*condition].into_iter().chain(statements.iter().copied()).collect()
+                }
+                Statement::For { ident_index, start, end, statements } => {
+                    [*ident_index, *start, *end].into_iter().chain(statements.iter().copied()).collect()
+                }
+                Statement::Use { path } => path.clone(),
+                Statement::Expression | Statement::Break | Statement::Continue => vec![],
             },
             AstNodeKind::Expression(exp) => match exp {
-                Expression::Block => "BlockExpression".to_string(),
-                Expression::FunctionCall => "FunctionCall".to_string(),
-                Expression::Identifier { index } => format!("Identifier({})", index),
-                Expression::Literal(lit) => format!("Literal({})", lit.value),
+                Expression::Identifier { .. } | Expression::Literal(_) => vec![],
+                Expression::Block { signature, statements } => {
+                    signature_children(signature.as_ref()).chain(statements.iter().copied()).collect()
+                }
+                Expression::Extern { signature, .. } => signature_children(Some(signature)).collect(),
+                Expression::FunctionCall { callee, args } => {
+                    [*callee].into_iter().chain(args.iter().copied()).collect()
+                }
+                Expression::Binary { lhs, rhs, .. } => vec![*lhs, *rhs],
+                Expression::Unary { operand, .. } => vec![*operand],
+                Expression::If { condition, then_statements, else_statements } => [*condition]
+                    .into_iter()
+                    .chain(then_statements.iter().copied())
+                    .chain(else_statements.iter().flatten().copied())
+                    .collect(),
+                Expression::StructDefinition { fields } => fields.clone(),
+                Expression::StructLiteral { type_index, fields } => {
+                    [*type_index].into_iter().chain(fields.iter().copied()).collect()
+                }
+                Expression::FieldAccess { object, field } => vec![*object, *field],
+                Expression::EnumDefinition { variants } => variants.clone(),
+                Expression::EnumVariant { enum_index, variant, args } => {
+                    [*enum_index, *variant].into_iter().chain(args.iter().copied()).collect()
+                }
+                Expression::Match { value, arms } => [*value].into_iter().chain(arms.iter().copied()).collect(),
+                Expression::Path { module, name } => vec![*module, *name],
+                Expression::ArrayLiteral { elements } => elements.clone(),
+                Expression::Index { object, index, .. } => vec![*object, *index],
+                Expression::Slicing { object, start, end, .. } => {
+                    [*object].into_iter().chain(*start).chain(*end).collect()
+                }
             },
-        };
+        }
+    }
+}
 
-        write!(f, "{}", s)
+fn signature_children(signature: Option<&Signature>) -> impl Iterator<Item = usize> + '_ {
+    signature.into_iter().flat_map(|s| s.params.iter().copied())
+}
+
+impl Display for AstNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            AstNodeKind::Expression(Expression::Identifier { index }) => write!(f, "Identifier({})", index),
+            AstNodeKind::Expression(Expression::Literal(lit)) => write!(f, "Literal({})", lit.value),
+            AstNodeKind::Expression(Expression::Binary { operator, .. }) => {
+                write!(f, "BinaryOperation({})", operator)
+            }
+            AstNodeKind::Expression(Expression::Unary { operator, .. }) => {
+                write!(f, "UnaryOperation({})", operator)
+            }
+            _ => write!(f, "{}", self.name()),
+        }
     }
 }
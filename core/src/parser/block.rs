@@ -1,26 +1,81 @@
-use std::default;
-
 use crate::{
-    common::{syntax_error::SyntaxError, position::FileSpan},
+    common::{operator::Operator, position::FileSpan, syntax_error::SyntaxError},
     lexer::{token::TokenKind, token_stream::TokenStream},
+    type_system::llc_type::Type,
 };
 
 use super::{
     ast_node::{AstNode, ParsingResult, AstNodeKind, NodeParent},
     expression::Expression,
+    identifier,
     parser::FileAst,
-    statement
+    statement::{self, Statement},
+    type_expression,
 };
 
+pub struct Signature {
+    pub params: Vec<usize>,
+    pub output: Option<Type>,
+    // Only extern blocks can take values after their parameters: (format: string, ...)
+    pub variadic: bool,
+}
+
 pub(in crate::parser) fn parse(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
+    let signature_begin = stream.peek(0).position;
+    let signature = match is_signature(stream) {
+        true => match parse_signature(stream, file_ast) {
+            Some(s) => Some(s),
+            None => return ParsingResult::Error,
+        },
+        false => None,
+    };
+
+    if signature.as_ref().is_some_and(|s| s.variadic) {
+        let reason = String::from("Only extern blocks can take a variable number of values.");
+        file_ast.errors.push(SyntaxError::from_token(stream.peek(-1), Some(reason.into_boxed_str())));
+        return ParsingResult::Error;
+    }
+
     let begin = match stream.take_if(|t| match t.kind {
         TokenKind::OpenCurly => Some(t.position),
         _ => None,
     }) {
         Some(b) => b,
-        None => return ParsingResult::Other,
+        None => {
+            if signature.is_none() {
+                return ParsingResult::Other;
+            }
+
+            let token = stream.peek(0);
+            let reason = format!("Expected a block body after the block signature but found {}.", file_ast.describe(token));
+            file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+            return ParsingResult::Error;
+        }
     };
 
+    let begin = match signature {
+        Some(_) => signature_begin,
+        None => begin,
+    };
+
+    let (statements, end) = match parse_body(stream, file_ast) {
+        Some(body) => body,
+        None => return ParsingResult::Error,
+    };
+
+    let node = AstNode {
+        kind: AstNodeKind::Expression(Expression::Block { signature, statements }),
+        position: FileSpan::combine(&begin, &end),
+        parent: NodeParent::Unchecked
+    };
+
+    file_ast.nodes.push(node);
+    ParsingResult::Ok
+}
+
+// Parses the statements following an opening curly brace up to the matching closing one,
+// returns them along with the position of the closing brace.
+pub(in crate::parser) fn parse_body(stream: &mut TokenStream, file_ast: &mut FileAst) -> Option<(Vec<usize>, FileSpan)> {
     let mut statements = vec![];
 
     loop {
@@ -30,26 +85,107 @@ pub(in crate::parser) fn parse(stream: &mut TokenStream, file_ast: &mut FileAst)
                 eof,
                 Some(Box::from("Missing scope end }")),
             ));
-            return ParsingResult::Error;
+            return None;
         }
 
         if let Some(end) = stream.take_if(|t| match t.kind {
             TokenKind::ClosingCurly => Some(t.position),
             _ => None,
         }) {
+            return Some((statements, end));
+        }
 
-            let node = AstNode {
-                kind: AstNodeKind::Expression(Expression::Block),
-                position: FileSpan::combine(&begin, &end),
-                parent: NodeParent::Unchecked
+        match statement::parse(stream, file_ast) {
+            ParsingResult::Ok => statements.push(file_ast.nodes.len() - 1),
+            ParsingResult::Error => statement::recover(stream),
+            ParsingResult::Other => {
+                let token = stream.take();
+                file_ast.errors.push(SyntaxError::from_token(
+                    token,
+                    Some(Box::from(format!("Unexpected token in block: {}", file_ast.describe(token)))),
+                ));
+            }
+        }
+    }
+}
+
+// A parenthesis opens a signature rather than a sub expression when it is directly closed
+// or when its first item is a typed parameter.
+fn is_signature(stream: &TokenStream) -> bool {
+    matches!(
+        (&stream.peek(0).kind, &stream.peek(1).kind, &stream.peek(2).kind),
+        (TokenKind::OpenParenthesis, TokenKind::ClosingParenthesis, _)
+            | (
+                TokenKind::OpenParenthesis,
+                TokenKind::Identifier { .. },
+                TokenKind::Operator(Operator::Declaration),
+            )
+    )
+}
+
+pub(in crate::parser) fn parse_signature(stream: &mut TokenStream, file_ast: &mut FileAst) -> Option<Signature> {
+    stream.skip(1);
+    let mut params = vec![];
+    let mut variadic = false;
+
+    if !stream.skip_if(|t| t.kind == TokenKind::ClosingParenthesis) {
+        loop {
+            if type_expression::skip_ellipsis(stream) {
+                variadic = true;
+                if stream.skip_if(|t| t.kind == TokenKind::ClosingParenthesis) {
+                    break;
+                }
+
+                let token = stream.peek(0);
+                let reason = format!("Expected ) after ... but found {}.", file_ast.describe(token));
+                file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+                return None;
+            }
+
+            let ident_index = match identifier::parse(stream, file_ast) {
+                ParsingResult::Ok => file_ast.nodes.len() - 1,
+                _ => {
+                    let token = stream.peek(0);
+                    let reason = format!("Expected a parameter name but found {}.", file_ast.describe(token));
+                    file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+                    return None;
+                }
             };
 
-            file_ast.nodes.push(node);
-            return ParsingResult::Ok;
-        }
+            if !stream.skip_if(|t| t.kind == TokenKind::Operator(Operator::Declaration)) {
+                let token = stream.peek(0);
+                let reason = format!("Expected : after the parameter name but found {}.", file_ast.describe(token));
+                file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+                return None;
+            }
+
+            let llc_type = type_expression::parse(stream, file_ast)?;
+            file_ast.nodes.push(AstNode {
+                kind: AstNodeKind::Statement(Statement::Parameter { ident_index, llc_type }),
+                position: FileSpan::combine(&file_ast.nodes[ident_index].position, &stream.peek(-1).position),
+                parent: NodeParent::Unchecked
+            });
+            params.push(file_ast.nodes.len() - 1);
+
+            if stream.skip_if(|t| t.kind == TokenKind::Comma) {
+                continue;
+            }
 
-        if let ParsingResult::Ok = statement::parse(stream, file_ast) {
-            statements.push(file_ast.nodes.len() - 1)
+            if stream.skip_if(|t| t.kind == TokenKind::ClosingParenthesis) {
+                break;
+            }
+
+            let token = stream.peek(0);
+            let reason = format!("Expected , or ) in the parameter list but found {}.", file_ast.describe(token));
+            file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+            return None;
         }
     }
+
+    let output = match stream.skip_if(|t| t.kind == TokenKind::Operator(Operator::Into)) {
+        true => Some(type_expression::parse(stream, file_ast)?),
+        false => None,
+    };
+
+    Some(Signature { params, output, variadic })
 }
@@ -1,5 +1,5 @@
 use crate::{
-    common::{operator::Operator, position::FileSpan, syntax_error::SyntaxError, identifier::TypeState},
+    common::{operator::Operator, position::FileSpan, syntax_error::SyntaxError},
     lexer::{
         token::TokenKind,
         token_stream::TokenStream,
@@ -8,67 +8,67 @@ use crate::{
 
 use super::{
     ast_node::{AstNode, AstNodeKind, ParsingResult, NodeParent},
-    expression::{self, Expression},
+    expression,
     parser::FileAst,
-    statement::Statement, identifier
+    statement::Statement, identifier, type_expression
 };
 
-pub struct Declaration {
-    id_idx: usize,
-    exp_idx: usize,
-    name: Box<str>,
-    llc_type: TypeState,
-}
-
 pub(in crate::parser) fn parse(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
+    let is_annotated = match (&stream.peek(0).kind, &stream.peek(1).kind) {
+        (TokenKind::Identifier { .. }, TokenKind::Operator(Operator::Declassignment)) => false,
+        (TokenKind::Identifier { .. }, TokenKind::Operator(Operator::Declaration)) => true,
+        _ => return ParsingResult::Other,
+    };
+
     let ident_index = match identifier::parse(stream, file_ast) {
         ParsingResult::Ok => file_ast.nodes.len() - 1,
         _ => return ParsingResult::Other,
     };
 
-    if !stream.skip_if(|t| t.kind == TokenKind::Operator(Operator::Declassignment)) {
-        return ParsingResult::Other;
+    stream.skip(1);
+
+    // name : type; declares without a value, name : type = value; checks the value against it.
+    let annotation = match is_annotated {
+        true => match type_expression::parse(stream, file_ast) {
+            Some(t) => Some(t),
+            None => return ParsingResult::Error,
+        },
+        false => None,
+    };
+
+    if annotation.is_some() && !stream.skip_if(|t| t.kind == TokenKind::Operator(Operator::Assignment)) {
+        file_ast.nodes.push(AstNode {
+            kind: AstNodeKind::Statement(Statement::Declaration { ident_index, annotation, exp_index: None }),
+            position: FileSpan::combine(&file_ast.nodes[ident_index].position, &stream.peek(-1).position),
+            parent: NodeParent::Unchecked
+        });
+
+        return ParsingResult::Ok;
     }
 
-    let exp_node = match expression::parse(stream, file_ast) {
-        ParsingResult::Ok => &file_ast.nodes[file_ast.nodes.len() - 1],
+    let exp_index = match expression::parse(stream, file_ast) {
+        ParsingResult::Ok => file_ast.nodes.len() - 1,
         ParsingResult::Error => return ParsingResult::Error,
         ParsingResult::Other => {
-            let begin = stream.take().position;
-            stream.skip_until(
-                |t| t.kind == TokenKind::EOI || t.kind == TokenKind::EOF,
-                false,
-            );
-
-            let end = stream.take().position;
-            file_ast.errors.push(SyntaxError {
-                position: FileSpan::combine(&begin, &end),
-                reason: Box::from("Expected a value after operator :="),
-            });
+            let operator = match annotation {
+                Some(_) => "=",
+                None => ":=",
+            };
+            let reason = format!("Expected a value after operator {}", operator);
+            file_ast.errors.push(SyntaxError::from_token(stream.peek(0), Some(reason.into_boxed_str())));
 
             return ParsingResult::Error;
         }
     };
 
-    if let AstNodeKind::Expression(Expression::Literal(lit)) = &exp_node.kind {
-        file_ast.identifiers[ident_index].type_state = TypeState::Ok(lit.value.llc_type.clone());
-    }
-
     file_ast.nodes.push(AstNode {
-        kind: AstNodeKind::Statement(Statement::Declaration { ident_index }),
+        kind: AstNodeKind::Statement(Statement::Declaration { ident_index, annotation, exp_index: Some(exp_index) }),
         position: FileSpan::combine(
             &file_ast.nodes[ident_index].position,
-            &exp_node.position,
+            &file_ast.nodes[exp_index].position,
         ),
         parent: NodeParent::Unchecked
     });
 
-    match file_ast.nodes[ident_index].kind {
-        AstNodeKind::Expression(Expression::Identifier { index }) => {
-            file_ast.identifiers[index].declaration_idx = file_ast.nodes.len()
-        },
-        _ => panic!("Expected and identifier")
-    };
-
-    return ParsingResult::Ok;
+    ParsingResult::Ok
 }
@@ -0,0 +1,106 @@
+use crate::{
+    common::{keyword::Keyword, position::FileSpan, syntax_error::SyntaxError},
+    lexer::{token::TokenKind, token_stream::TokenStream},
+};
+
+use super::{
+    ast_node::{AstNode, AstNodeKind, NodeParent, ParsingResult},
+    expression::Expression,
+    identifier,
+    parser::FileAst,
+    statement::Statement,
+    type_expression,
+};
+
+// enum { Name, Name(type, ...), ... }
+pub(in crate::parser) fn parse_definition(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
+    let begin = match stream.take_if(|t| match t.kind {
+        TokenKind::Keyword(Keyword::Enum) => Some(t.position),
+        _ => None,
+    }) {
+        Some(pos) => pos,
+        None => return ParsingResult::Other,
+    };
+
+    if !stream.skip_if(|t| t.kind == TokenKind::OpenCurly) {
+        let token = stream.peek(0);
+        let reason = format!("Expected {{ to open the enum variants but found {}.", file_ast.describe(token));
+        file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+        return ParsingResult::Error;
+    }
+
+    let mut variants = vec![];
+
+    let end = loop {
+        if let Some(end) = stream.take_if(|t| match t.kind {
+            TokenKind::ClosingCurly => Some(t.position),
+            _ => None,
+        }) {
+            break end;
+        }
+
+        match parse_variant(stream, file_ast) {
+            Some(v) => variants.push(v),
+            None => return ParsingResult::Error,
+        }
+
+        if stream.skip_if(|t| t.kind == TokenKind::Comma) {
+            continue;
+        }
+
+        if stream.peek(0).kind != TokenKind::ClosingCurly {
+            let token = stream.peek(0);
+            let reason = format!("Expected , or }} in the enum variants but found {}.", file_ast.describe(token));
+            file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+            return ParsingResult::Error;
+        }
+    };
+
+    file_ast.nodes.push(AstNode {
+        kind: AstNodeKind::Expression(Expression::EnumDefinition { variants }),
+        position: FileSpan::combine(&begin, &end),
+        parent: NodeParent::Unchecked
+    });
+
+    ParsingResult::Ok
+}
+
+fn parse_variant(stream: &mut TokenStream, file_ast: &mut FileAst) -> Option<usize> {
+    let ident_index = match identifier::parse(stream, file_ast) {
+        ParsingResult::Ok => file_ast.nodes.len() - 1,
+        _ => {
+            let token = stream.peek(0);
+            let reason = format!("Expected a variant name but found {}.", file_ast.describe(token));
+            file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+            return None;
+        }
+    };
+
+    let mut payload = vec![];
+    if stream.skip_if(|t| t.kind == TokenKind::OpenParenthesis) {
+        loop {
+            payload.push(type_expression::parse(stream, file_ast)?);
+
+            if stream.skip_if(|t| t.kind == TokenKind::Comma) {
+                continue;
+            }
+
+            if stream.skip_if(|t| t.kind == TokenKind::ClosingParenthesis) {
+                break;
+            }
+
+            let token = stream.peek(0);
+            let reason = format!("Expected , or ) in the variant payload but found {}.", file_ast.describe(token));
+            file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+            return None;
+        }
+    }
+
+    file_ast.nodes.push(AstNode {
+        kind: AstNodeKind::Statement(Statement::Variant { ident_index, payload }),
+        position: FileSpan::combine(&file_ast.nodes[ident_index].position, &stream.peek(-1).position),
+        parent: NodeParent::Unchecked
+    });
+
+    Some(file_ast.nodes.len() - 1)
+}
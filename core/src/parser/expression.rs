@@ -1,76 +1,319 @@
 use crate::{
-    common::{operator::Operator, syntax_error::SyntaxError},
+    common::{diagnostic::Fix, operator::Operator, position::FileSpan, syntax_error::SyntaxError},
     lexer::{token::TokenKind, token_stream::TokenStream},
+    type_system::llc_type::Type,
 };
 
 use super::{
-    ast_node::ParsingResult, function_call, identifier, literal::{Literal, self},
-    parser::FileAst, block,
+    array_expression,
+    ast_node::{AstNode, AstNodeKind, NodeParent, ParsingResult},
+    block::{self, Signature},
+    enum_expression, extern_expression, function_call, identifier, if_expression,
+    literal::{self, Literal},
+    match_expression, path,
+    parser::FileAst,
+    struct_expression,
 };
 
 pub enum Expression {
     Identifier {index: usize},
     Literal(Literal),
-    Block,
-    FunctionCall,
+    Block { signature: Option<Signature>, statements: Vec<usize> },
+    FunctionCall { callee: usize, args: Vec<usize> },
+    Binary { operator: Operator, lhs: usize, rhs: usize },
+    Unary { operator: Operator, operand: usize },
+    If { condition: usize, then_statements: Vec<usize>, else_statements: Option<Vec<usize>> },
+    StructDefinition { fields: Vec<usize> },
+    StructLiteral { type_index: usize, fields: Vec<usize> },
+    FieldAccess { object: usize, field: usize },
+    EnumDefinition { variants: Vec<usize> },
+    EnumVariant { enum_index: usize, variant: usize, args: Vec<usize> },
+    Match { value: usize, arms: Vec<usize> },
+    Path { module: usize, name: usize },
+    ArrayLiteral { elements: Vec<usize> },
+    // C function declared by the header when there is one: extern "stdio.h" (format: string, ...) -> i32
+    Extern { signature: Signature, header: Option<Box<str>> },
+    // The type of the indexed value is set by the type checker, the C code depends on it.
+    Index { object: usize, index: usize, sequence: Option<Type> },
+    Slicing { object: usize, start: Option<usize>, end: Option<usize>, sequence: Option<Type> },
 }
 
 pub(in crate::parser) fn parse(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
-    match stream.peek(0).kind {
-        TokenKind::Identifier { .. }
-        | TokenKind::Literal(_)
-        | TokenKind::OpenCurly
-        | TokenKind::Operator(Operator::Into) => {}
-        _ => return ParsingResult::Other,
-    }
-
-    if let ParsingResult::Ok = parse_left_side(stream, file_ast) {
-        match stream.peek(0).kind {
-            TokenKind::EOI => return ParsingResult::Ok,
-            _ => {}
+    parse_binary(stream, file_ast, 0)
+}
+
+// Precedence climbing: operands are parsed with a minimum precedence one above the
+// current operator's, so operators of the same precedence associate to the left.
+fn parse_binary(stream: &mut TokenStream, file_ast: &mut FileAst, min_precedence: u8) -> ParsingResult {
+    match parse_unary(stream, file_ast) {
+        ParsingResult::Ok => {}
+        res => return res,
+    }
+
+    let mut lhs = file_ast.nodes.len() - 1;
+
+    while let TokenKind::Operator(operator) = stream.peek(0).kind {
+        let precedence = match operator.binary_precedence() {
+            Some(p) if p >= min_precedence => p,
+            _ => break,
+        };
+
+        stream.skip(1);
+
+        match parse_binary(stream, file_ast, precedence + 1) {
+            ParsingResult::Ok => {}
+            ParsingResult::Error => return ParsingResult::Error,
+            ParsingResult::Other => {
+                let token = stream.peek(0);
+                let reason = format!(
+                    "Expected an expression after operator {} but found {}.",
+                    operator.symbol(),
+                    file_ast.describe(token)
+                );
+                file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+                return ParsingResult::Error;
+            }
         }
+
+        let rhs = file_ast.nodes.len() - 1;
+        file_ast.nodes.push(AstNode {
+            kind: AstNodeKind::Expression(Expression::Binary { operator, lhs, rhs }),
+            position: FileSpan::combine(&file_ast.nodes[lhs].position, &file_ast.nodes[rhs].position),
+            parent: NodeParent::Unchecked
+        });
+
+        lhs = file_ast.nodes.len() - 1;
+    }
+
+    ParsingResult::Ok
+}
+
+// Prefix operators: -x, !x, the address &x and the dereference *x.
+fn parse_unary(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
+    let (operator, op_pos) = match stream.take_if(|t| match t.kind {
+        TokenKind::Operator(op @ (Operator::Minus | Operator::Not | Operator::Ampersand | Operator::Times)) => {
+            Some((op, t.position))
+        }
+        _ => None,
+    }) {
+        Some(op) => op,
+        None => return parse_postfix(stream, file_ast),
+    };
+
+    match parse_unary(stream, file_ast) {
+        ParsingResult::Ok => {}
+        ParsingResult::Error => return ParsingResult::Error,
+        ParsingResult::Other => {
+            let token = stream.peek(0);
+            let reason = format!(
+                "Expected an expression after operator {} but found {}.",
+                operator.symbol(),
+                file_ast.describe(token)
+            );
+            file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+            return ParsingResult::Error;
+        }
+    }
+
+    let operand = file_ast.nodes.len() - 1;
+    file_ast.nodes.push(AstNode {
+        kind: AstNodeKind::Expression(Expression::Unary { operator, operand }),
+        position: FileSpan::combine(&op_pos, &file_ast.nodes[operand].position),
+        parent: NodeParent::Unchecked
+    });
+
+    ParsingResult::Ok
+}
+
+// Field accesses, struct literals and enum variants all follow a dot: value.field,
+// Name.{ field: value } and Name.Variant(value). Indices follow the value in brackets.
+fn parse_postfix(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
+    match parse_primary(stream, file_ast) {
+        ParsingResult::Ok => {}
+        res => return res,
+    }
+
+    if let ParsingResult::Error = path::parse(stream, file_ast) {
+        return ParsingResult::Error;
     }
 
     loop {
-        if let ParsingResult::Ok = parse_right_side(stream, file_ast) {
-            match stream.peek(0).kind {
-                TokenKind::EOI => return ParsingResult::Ok,
-                _ => continue,
+        if stream.peek(0).kind == TokenKind::OpenBracket {
+            match array_expression::parse_index(stream, file_ast) {
+                ParsingResult::Ok => continue,
+                res => return res,
             }
         }
 
-        let first = stream.peek(0);
-        let second = stream.peek(1);
-        let reason = format!(
-            "Unexpected token encountered while trying to parse expression. Expected one of(;, function call, function body, operator) after {0} but found {1}", 
-            first.kind, second.kind);
+        if !stream.skip_if(|t| t.kind == TokenKind::Operator(Operator::Dot)) {
+            break;
+        }
 
-        file_ast.errors.push(SyntaxError::from_token(
-            stream.peek(1),
-            Some(reason.into_boxed_str()),
-        ));
-        return ParsingResult::Error;
+        let lhs = file_ast.nodes.len() - 1;
+
+        if stream.peek(0).kind == TokenKind::OpenCurly {
+            let head = &file_ast.nodes[lhs].kind;
+            if !matches!(head, AstNodeKind::Expression(Expression::Identifier { .. } | Expression::Path { .. })) {
+                let token = stream.peek(0);
+                let reason = String::from("Struct literals must start with the name of the struct.");
+                file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+                return ParsingResult::Error;
+            }
+
+            match struct_expression::parse_literal(stream, file_ast, lhs) {
+                ParsingResult::Ok => continue,
+                res => return res,
+            }
+        }
+
+        let field = match identifier::parse(stream, file_ast) {
+            ParsingResult::Ok => file_ast.nodes.len() - 1,
+            _ => {
+                let token = stream.peek(0);
+                let reason = format!("Expected a field name after . but found {}.", file_ast.describe(token));
+                file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+                return ParsingResult::Error;
+            }
+        };
+
+        if stream.peek(0).kind == TokenKind::OpenParenthesis {
+            match parse_variant_args(stream, file_ast, lhs, field) {
+                ParsingResult::Ok => continue,
+                res => return res,
+            }
+        }
+
+        file_ast.nodes.push(AstNode {
+            kind: AstNodeKind::Expression(Expression::FieldAccess { object: lhs, field }),
+            position: FileSpan::combine(&file_ast.nodes[lhs].position, &file_ast.nodes[field].position),
+            parent: NodeParent::Unchecked
+        });
     }
+
+    ParsingResult::Ok
 }
 
-fn parse_right_side(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
-    if let res @ ParsingResult::Ok = function_call::parse(stream, file_ast) {
-        return res;
+// Variants without payload are parsed as field accesses, the type checker tells them apart.
+fn parse_variant_args(stream: &mut TokenStream, file_ast: &mut FileAst, enum_index: usize, variant: usize) -> ParsingResult {
+    if !matches!(file_ast.nodes[enum_index].kind, AstNodeKind::Expression(Expression::Identifier { .. })) {
+        let token = stream.peek(0);
+        let reason = String::from("Enum variants must be built from the name of the enum.");
+        file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+        return ParsingResult::Error;
     }
 
-    ParsingResult::Other
+    stream.skip(1);
+    let mut args = vec![];
+
+    let end = loop {
+        if let Some(end) = stream.take_if(|t| match t.kind {
+            TokenKind::ClosingParenthesis => Some(t.position),
+            _ => None,
+        }) {
+            break end;
+        }
+
+        match parse(stream, file_ast) {
+            ParsingResult::Ok => args.push(file_ast.nodes.len() - 1),
+            ParsingResult::Error => return ParsingResult::Error,
+            ParsingResult::Other => {
+                let token = stream.peek(0);
+                let reason = format!("Expected a variant value but found {}.", file_ast.describe(token));
+                file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+                return ParsingResult::Error;
+            }
+        }
+
+        if !stream.skip_if(|t| t.kind == TokenKind::Comma) && stream.peek(0).kind != TokenKind::ClosingParenthesis {
+            let token = stream.peek(0);
+            let reason = format!("Expected , or ) in the variant values but found {}.", file_ast.describe(token));
+            file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+            return ParsingResult::Error;
+        }
+    };
+
+    file_ast.nodes.push(AstNode {
+        kind: AstNodeKind::Expression(Expression::EnumVariant { enum_index, variant, args }),
+        position: FileSpan::combine(&file_ast.nodes[enum_index].position, &end),
+        parent: NodeParent::Unchecked
+    });
+
+    ParsingResult::Ok
 }
 
-fn parse_left_side(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
+fn parse_primary(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
     if let ParsingResult::Ok = parse_single_token_exp(stream, file_ast) {
         return ParsingResult::Ok;
     }
 
-    if let ParsingResult::Ok = block::parse(stream, file_ast) {
-        return ParsingResult::Ok;
+    match block::parse(stream, file_ast) {
+        ParsingResult::Other => {}
+        res => return res,
     }
 
-    ParsingResult::Other
+    match extern_expression::parse(stream, file_ast) {
+        ParsingResult::Other => {}
+        res => return res,
+    }
+
+    match if_expression::parse(stream, file_ast) {
+        ParsingResult::Other => {}
+        res => return res,
+    }
+
+    match struct_expression::parse_definition(stream, file_ast) {
+        ParsingResult::Other => {}
+        res => return res,
+    }
+
+    match enum_expression::parse_definition(stream, file_ast) {
+        ParsingResult::Other => {}
+        res => return res,
+    }
+
+    match match_expression::parse(stream, file_ast) {
+        ParsingResult::Other => {}
+        res => return res,
+    }
+
+    match function_call::parse(stream, file_ast) {
+        ParsingResult::Other => {}
+        res => return res,
+    }
+
+    match array_expression::parse_literal(stream, file_ast) {
+        ParsingResult::Other => {}
+        res => return res,
+    }
+
+    parse_parenthesized(stream, file_ast)
+}
+
+fn parse_parenthesized(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
+    if !stream.skip_if(|t| t.kind == TokenKind::OpenParenthesis) {
+        return ParsingResult::Other;
+    }
+
+    match parse(stream, file_ast) {
+        ParsingResult::Ok => {}
+        ParsingResult::Error => return ParsingResult::Error,
+        ParsingResult::Other => {
+            let token = stream.peek(0);
+            let reason = format!("Expected an expression after ( but found {}.", file_ast.describe(token));
+            file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+            return ParsingResult::Error;
+        }
+    }
+
+    if !stream.skip_if(|t| t.kind == TokenKind::ClosingParenthesis) {
+        let token = stream.peek(0);
+        let reason = format!("Missing closing parenthesis, found {}.", file_ast.describe(token));
+        let fix = Fix::insert_after(stream.peek(-1).position, ")", "Close the parenthesis.");
+        file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())).with_fix(fix));
+        return ParsingResult::Error;
+    }
+
+    ParsingResult::Ok
 }
 
 fn parse_single_token_exp(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
@@ -0,0 +1,49 @@
+use crate::{
+    common::{keyword::Keyword, literal::LiteralValue, position::FileSpan, syntax_error::SyntaxError},
+    lexer::{token::TokenKind, token_stream::TokenStream},
+    type_system::llc_type::Type,
+};
+
+use super::{
+    ast_node::{AstNode, AstNodeKind, NodeParent, ParsingResult},
+    block,
+    expression::Expression,
+    parser::FileAst,
+};
+
+// extern "header.h" (name: type, ...) -> type, a block signature without a body. The header
+// is optional, the C prototype is written from the signature when there is none.
+pub(in crate::parser) fn parse(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
+    let begin = match stream.take_if(|t| match t.kind {
+        TokenKind::Keyword(Keyword::Extern) => Some(t.position),
+        _ => None,
+    }) {
+        Some(pos) => pos,
+        None => return ParsingResult::Other,
+    };
+
+    let header = stream.take_if(|t| match &t.kind {
+        TokenKind::Literal(LiteralValue { llc_type: Type::String, value }) => Some(value.clone()),
+        _ => None,
+    });
+
+    if stream.peek(0).kind != TokenKind::OpenParenthesis {
+        let token = stream.peek(0);
+        let reason = format!("Expected the signature of the extern block but found {}.", file_ast.describe(token));
+        file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+        return ParsingResult::Error;
+    }
+
+    let signature = match block::parse_signature(stream, file_ast) {
+        Some(s) => s,
+        None => return ParsingResult::Error,
+    };
+
+    file_ast.nodes.push(AstNode {
+        kind: AstNodeKind::Expression(Expression::Extern { signature, header }),
+        position: FileSpan::combine(&begin, &stream.peek(-1).position),
+        parent: NodeParent::Unchecked
+    });
+
+    ParsingResult::Ok
+}
@@ -5,9 +5,10 @@ use crate::{
 
 use super::{
     ast_node::{AstNode, AstNodeKind, ParsingResult, NodeParent},
-    expression::Expression,
+    expression::{self, Expression},
     identifier,
     parser::FileAst,
+    path,
 };
 
 pub(in crate::parser) fn parse(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
@@ -20,11 +21,14 @@ pub(in crate::parser) fn parse(stream: &mut TokenStream, file_ast: &mut FileAst)
     };
 
     let identifier_id = match identifier::parse(stream, file_ast) {
-        ParsingResult::Ok => file_ast.nodes.len() - 1,
+        ParsingResult::Ok => match path::parse(stream, file_ast) {
+            ParsingResult::Error => return ParsingResult::Error,
+            _ => file_ast.nodes.len() - 1,
+        },
         ParsingResult::Error => return ParsingResult::Error,
         ParsingResult::Other => {
             let token = stream.peek(0);
-            let reason = Box::from(format!("Expected an expression after operator -> in function call expression but found {}.", token.kind));
+            let reason = Box::from(format!("Expected an expression after operator -> in function call expression but found {}.", file_ast.describe(token)));
             file_ast
                 .errors
                 .push(SyntaxError::from_token(token, Some(reason)));
@@ -32,9 +36,51 @@ pub(in crate::parser) fn parse(stream: &mut TokenStream, file_ast: &mut FileAst)
         }
     };
 
+    let mut end = file_ast.nodes[identifier_id].position;
+    let mut args = vec![];
+
+    if stream.skip_if(|t| t.kind == TokenKind::OpenParenthesis) {
+        if let Some(pos) = stream.take_if(|t| match t.kind {
+            TokenKind::ClosingParenthesis => Some(t.position),
+            _ => None,
+        }) {
+            end = pos;
+        } else {
+            loop {
+                match expression::parse(stream, file_ast) {
+                    ParsingResult::Ok => args.push(file_ast.nodes.len() - 1),
+                    ParsingResult::Error => return ParsingResult::Error,
+                    ParsingResult::Other => {
+                        let token = stream.peek(0);
+                        let reason = format!("Expected an argument but found {}.", file_ast.describe(token));
+                        file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+                        return ParsingResult::Error;
+                    }
+                }
+
+                if stream.skip_if(|t| t.kind == TokenKind::Comma) {
+                    continue;
+                }
+
+                if let Some(pos) = stream.take_if(|t| match t.kind {
+                    TokenKind::ClosingParenthesis => Some(t.position),
+                    _ => None,
+                }) {
+                    end = pos;
+                    break;
+                }
+
+                let token = stream.peek(0);
+                let reason = format!("Expected , or ) in the argument list but found {}.", file_ast.describe(token));
+                file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+                return ParsingResult::Error;
+            }
+        }
+    }
+
     file_ast.nodes.push(AstNode {
-        kind: AstNodeKind::Expression(Expression::FunctionCall),
-        position: FileSpan::combine(&op_pos, &file_ast.nodes[identifier_id].position),
+        kind: AstNodeKind::Expression(Expression::FunctionCall { callee: identifier_id, args }),
+        position: FileSpan::combine(&op_pos, &end),
         parent: NodeParent::Unchecked
     });
 
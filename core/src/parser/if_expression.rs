@@ -0,0 +1,79 @@
+use crate::{
+    common::{keyword::Keyword, position::FileSpan, syntax_error::SyntaxError},
+    lexer::{token::TokenKind, token_stream::TokenStream},
+};
+
+use super::{
+    ast_node::{AstNode, AstNodeKind, NodeParent, ParsingResult},
+    block,
+    expression::{self, Expression},
+    parser::FileAst,
+};
+
+pub(in crate::parser) fn parse(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
+    let begin = match stream.take_if(|t| match t.kind {
+        TokenKind::Keyword(Keyword::If) => Some(t.position),
+        _ => None,
+    }) {
+        Some(pos) => pos,
+        None => return ParsingResult::Other,
+    };
+
+    let condition = match expression::parse(stream, file_ast) {
+        ParsingResult::Ok => file_ast.nodes.len() - 1,
+        ParsingResult::Error => return ParsingResult::Error,
+        ParsingResult::Other => {
+            let token = stream.peek(0);
+            let reason = format!("Expected a condition after if but found {}.", file_ast.describe(token));
+            file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+            return ParsingResult::Error;
+        }
+    };
+
+    let (then_statements, mut end) = match parse_branch(stream, file_ast) {
+        Some(branch) => branch,
+        None => return ParsingResult::Error,
+    };
+
+    let mut else_statements = None;
+    if stream.skip_if(|t| t.kind == TokenKind::Keyword(Keyword::Else)) {
+        // else if chains are stored as an else branch holding a single if expression.
+        if stream.peek(0).kind == TokenKind::Keyword(Keyword::If) {
+            match parse(stream, file_ast) {
+                ParsingResult::Ok => {}
+                res => return res,
+            }
+
+            let nested = file_ast.nodes.len() - 1;
+            end = file_ast.nodes[nested].position;
+            else_statements = Some(vec![nested]);
+        } else {
+            let (statements, else_end) = match parse_branch(stream, file_ast) {
+                Some(branch) => branch,
+                None => return ParsingResult::Error,
+            };
+
+            end = else_end;
+            else_statements = Some(statements);
+        }
+    }
+
+    file_ast.nodes.push(AstNode {
+        kind: AstNodeKind::Expression(Expression::If { condition, then_statements, else_statements }),
+        position: FileSpan::combine(&begin, &end),
+        parent: NodeParent::Unchecked
+    });
+
+    ParsingResult::Ok
+}
+
+fn parse_branch(stream: &mut TokenStream, file_ast: &mut FileAst) -> Option<(Vec<usize>, FileSpan)> {
+    if !stream.skip_if(|t| t.kind == TokenKind::OpenCurly) {
+        let token = stream.peek(0);
+        let reason = format!("Expected {{ to open the branch but found {}.", file_ast.describe(token));
+        file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+        return None;
+    }
+
+    block::parse_body(stream, file_ast)
+}
@@ -39,8 +39,8 @@ pub struct Literal {
                     kind: AstNodeKind::Expression(Expression::Literal(l)),
                     parent: NodeParent::Unchecked
                 });
-                return ParsingResult::Ok;
+                ParsingResult::Ok
             }
-            None => return ParsingResult::Other,
+            None => ParsingResult::Other,
         }
     }
\ No newline at end of file
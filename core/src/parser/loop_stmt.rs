@@ -0,0 +1,129 @@
+use crate::{
+    common::{keyword::Keyword, operator::Operator, position::FileSpan, syntax_error::SyntaxError},
+    lexer::{token::TokenKind, token_stream::TokenStream},
+};
+
+use super::{
+    ast_node::{AstNode, AstNodeKind, NodeParent, ParsingResult},
+    block, expression, identifier,
+    parser::FileAst,
+    statement::Statement,
+};
+
+pub(in crate::parser) fn parse(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
+    let token = stream.peek(0);
+    let position = token.position;
+    let statement = match token.kind {
+        TokenKind::Keyword(Keyword::While) => return parse_while(stream, file_ast),
+        TokenKind::Keyword(Keyword::For) => return parse_for(stream, file_ast),
+        TokenKind::Keyword(Keyword::Break) => Statement::Break,
+        TokenKind::Keyword(Keyword::Continue) => Statement::Continue,
+        _ => return ParsingResult::Other,
+    };
+
+    stream.skip(1);
+    file_ast.nodes.push(AstNode {
+        kind: AstNodeKind::Statement(statement),
+        position,
+        parent: NodeParent::Unchecked
+    });
+
+    ParsingResult::Ok
+}
+
+fn parse_while(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
+    let begin = stream.take().position;
+
+    let condition = match parse_operand(stream, file_ast, "while") {
+        Some(c) => c,
+        None => return ParsingResult::Error,
+    };
+
+    let (statements, end) = match parse_body(stream, file_ast) {
+        Some(body) => body,
+        None => return ParsingResult::Error,
+    };
+
+    file_ast.nodes.push(AstNode {
+        kind: AstNodeKind::Statement(Statement::While { condition, statements }),
+        position: FileSpan::combine(&begin, &end),
+        parent: NodeParent::Unchecked
+    });
+
+    ParsingResult::Ok
+}
+
+fn parse_for(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
+    let begin = stream.take().position;
+
+    let ident_index = match identifier::parse(stream, file_ast) {
+        ParsingResult::Ok => file_ast.nodes.len() - 1,
+        _ => {
+            let token = stream.peek(0);
+            let reason = format!("Expected a loop variable after for but found {}.", file_ast.describe(token));
+            file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+            return ParsingResult::Error;
+        }
+    };
+
+    if !stream.skip_if(|t| t.kind == TokenKind::Keyword(Keyword::In)) {
+        let token = stream.peek(0);
+        let reason = format!("Expected in after the loop variable but found {}.", file_ast.describe(token));
+        file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+        return ParsingResult::Error;
+    }
+
+    let start = match parse_operand(stream, file_ast, "in") {
+        Some(s) => s,
+        None => return ParsingResult::Error,
+    };
+
+    if !stream.skip_if(|t| t.kind == TokenKind::Operator(Operator::Range)) {
+        let token = stream.peek(0);
+        let reason = format!("Expected .. in the loop range but found {}.", file_ast.describe(token));
+        file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+        return ParsingResult::Error;
+    }
+
+    let end = match parse_operand(stream, file_ast, "..") {
+        Some(e) => e,
+        None => return ParsingResult::Error,
+    };
+
+    let (statements, body_end) = match parse_body(stream, file_ast) {
+        Some(body) => body,
+        None => return ParsingResult::Error,
+    };
+
+    file_ast.nodes.push(AstNode {
+        kind: AstNodeKind::Statement(Statement::For { ident_index, start, end, statements }),
+        position: FileSpan::combine(&begin, &body_end),
+        parent: NodeParent::Unchecked
+    });
+
+    ParsingResult::Ok
+}
+
+fn parse_operand(stream: &mut TokenStream, file_ast: &mut FileAst, after: &str) -> Option<usize> {
+    match expression::parse(stream, file_ast) {
+        ParsingResult::Ok => Some(file_ast.nodes.len() - 1),
+        ParsingResult::Error => None,
+        ParsingResult::Other => {
+            let token = stream.peek(0);
+            let reason = format!("Expected an expression after {} but found {}.", after, file_ast.describe(token));
+            file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+            None
+        }
+    }
+}
+
+fn parse_body(stream: &mut TokenStream, file_ast: &mut FileAst) -> Option<(Vec<usize>, FileSpan)> {
+    if !stream.skip_if(|t| t.kind == TokenKind::OpenCurly) {
+        let token = stream.peek(0);
+        let reason = format!("Expected {{ to open the loop body but found {}.", file_ast.describe(token));
+        file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+        return None;
+    }
+
+    block::parse_body(stream, file_ast)
+}
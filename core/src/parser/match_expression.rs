@@ -0,0 +1,151 @@
+use crate::{
+    common::{keyword::Keyword, operator::Operator, position::FileSpan, syntax_error::SyntaxError},
+    lexer::{token::TokenKind, token_stream::TokenStream},
+};
+
+use super::{
+    ast_node::{AstNode, AstNodeKind, NodeParent, ParsingResult},
+    block,
+    expression::{self, Expression},
+    identifier,
+    parser::FileAst,
+    statement::Statement,
+};
+
+pub enum Pattern {
+    Wildcard,
+    // Bindings are positional, a discarded payload value has no binding. A variant written
+    // without parentheses ignores its whole payload.
+    Variant { ident_index: usize, bindings: Vec<Option<usize>> },
+}
+
+// match value { Variant(a, _) => { ... } _ => { ... } }
+pub(in crate::parser) fn parse(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
+    let begin = match stream.take_if(|t| match t.kind {
+        TokenKind::Keyword(Keyword::Match) => Some(t.position),
+        _ => None,
+    }) {
+        Some(pos) => pos,
+        None => return ParsingResult::Other,
+    };
+
+    let value = match expression::parse(stream, file_ast) {
+        ParsingResult::Ok => file_ast.nodes.len() - 1,
+        ParsingResult::Error => return ParsingResult::Error,
+        ParsingResult::Other => {
+            let token = stream.peek(0);
+            let reason = format!("Expected a value after match but found {}.", file_ast.describe(token));
+            file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+            return ParsingResult::Error;
+        }
+    };
+
+    if !stream.skip_if(|t| t.kind == TokenKind::OpenCurly) {
+        let token = stream.peek(0);
+        let reason = format!("Expected {{ to open the match arms but found {}.", file_ast.describe(token));
+        file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+        return ParsingResult::Error;
+    }
+
+    let mut arms = vec![];
+
+    let end = loop {
+        if let Some(end) = stream.take_if(|t| match t.kind {
+            TokenKind::ClosingCurly => Some(t.position),
+            _ => None,
+        }) {
+            break end;
+        }
+
+        match parse_arm(stream, file_ast) {
+            Some(arm) => arms.push(arm),
+            None => return ParsingResult::Error,
+        }
+
+        stream.skip_if(|t| t.kind == TokenKind::Comma);
+    };
+
+    file_ast.nodes.push(AstNode {
+        kind: AstNodeKind::Expression(Expression::Match { value, arms }),
+        position: FileSpan::combine(&begin, &end),
+        parent: NodeParent::Unchecked
+    });
+
+    ParsingResult::Ok
+}
+
+fn parse_arm(stream: &mut TokenStream, file_ast: &mut FileAst) -> Option<usize> {
+    let begin = stream.peek(0).position;
+    let pattern = parse_pattern(stream, file_ast)?;
+
+    if !stream.skip_if(|t| t.kind == TokenKind::Operator(Operator::Return)) {
+        let token = stream.peek(0);
+        let reason = format!("Expected => after the pattern but found {}.", file_ast.describe(token));
+        file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+        return None;
+    }
+
+    if !stream.skip_if(|t| t.kind == TokenKind::OpenCurly) {
+        let token = stream.peek(0);
+        let reason = format!("Expected {{ to open the match arm but found {}.", file_ast.describe(token));
+        file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+        return None;
+    }
+
+    let (statements, end) = block::parse_body(stream, file_ast)?;
+
+    file_ast.nodes.push(AstNode {
+        kind: AstNodeKind::Statement(Statement::MatchArm { pattern, statements }),
+        position: FileSpan::combine(&begin, &end),
+        parent: NodeParent::Unchecked
+    });
+
+    Some(file_ast.nodes.len() - 1)
+}
+
+fn parse_pattern(stream: &mut TokenStream, file_ast: &mut FileAst) -> Option<Pattern> {
+    if stream.skip_if(|t| t.kind == TokenKind::Operator(Operator::Discard)) {
+        return Some(Pattern::Wildcard);
+    }
+
+    let ident_index = match identifier::parse(stream, file_ast) {
+        ParsingResult::Ok => file_ast.nodes.len() - 1,
+        _ => {
+            let token = stream.peek(0);
+            let reason = format!("Expected a variant name or _ but found {}.", file_ast.describe(token));
+            file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+            return None;
+        }
+    };
+
+    let mut bindings = vec![];
+    if stream.skip_if(|t| t.kind == TokenKind::OpenParenthesis) {
+        loop {
+            if stream.skip_if(|t| t.kind == TokenKind::Operator(Operator::Discard)) {
+                bindings.push(None);
+            } else if let ParsingResult::Ok = identifier::parse(stream, file_ast) {
+                bindings.push(Some(file_ast.nodes.len() - 1));
+            } else {
+                let token = stream.peek(0);
+                let reason = format!("Expected a binding name or _ but found {}.", file_ast.describe(token));
+                file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+                return None;
+            }
+
+            if stream.skip_if(|t| t.kind == TokenKind::Comma) {
+                continue;
+            }
+
+            if stream.skip_if(|t| t.kind == TokenKind::ClosingParenthesis) {
+                break;
+            }
+
+            let token = stream.peek(0);
+            let reason = format!("Expected , or ) in the pattern but found {}.", file_ast.describe(token));
+            file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+            return None;
+        }
+    }
+
+    Some(Pattern::Variant { ident_index, bindings })
+}
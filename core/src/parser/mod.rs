@@ -1,13 +1,25 @@
+pub mod array_expression;
 pub mod assignment;
 pub mod return_stmt;
 pub mod ast_node;
 pub mod declaration;
+pub mod enum_expression;
 pub mod expression;
+pub mod extern_expression;
 pub mod block;
 pub mod function_call;
 pub mod identifier;
+pub mod if_expression;
 pub mod literal;
+pub mod loop_stmt;
+pub mod match_expression;
+#[allow(clippy::module_inception)]
 pub mod parser;
+pub mod path;
 pub mod statement;
+pub mod struct_expression;
+pub mod type_expression;
+pub mod use_stmt;
 
-pub mod test;
+#[cfg(test)]
+mod test;
@@ -3,31 +3,73 @@ use std::fmt::Display;
 use super::ast_node;
 use super::ast_node::ParsingResult;
 use super::ast_node::AstNode;
+use super::ast_node::NodeParent;
+use super::statement;
+use super::type_expression::TypePath;
+use crate::common::compile_error::CompileError;
 use crate::common::identifier::Identifier;
 use crate::common::position::FileSpan;
+use crate::common::source_map::{FileId, SourceMap};
 use crate::common::syntax_error::SyntaxError;
 use crate::lexer::lexer;
-use crate::lexer::token::TokenKind;
-use crate::type_system;
+use crate::lexer::token::{Token, TokenKind};
+use crate::type_system::type_check;
+use crate::type_system::type_error::TypeError;
 
 pub struct FileAst {
     pub file_name: Box<str>,
+    pub file_id: FileId,
     pub nodes: Vec<AstNode>,
     pub errors: Vec<SyntaxError>,
+    pub type_errors: Vec<TypeError>,
     pub identifiers: Vec<Identifier>,
     pub root_nodes: Vec<usize>,
+    pub type_paths: Vec<TypePath>,
 }
 
 impl FileAst {
-    pub fn new<'a>(file_name: &'a str) -> Self {
-        let mut lexer = lexer::get_tokens(file_name);
+    // Builds and type checks a file on its own, files using modules are loaded by a Project.
+    pub fn new(sources: &mut SourceMap, file_name: &str) -> Result<Self, Vec<CompileError>> {
+        let file_id = sources.load(file_name).map_err(|e| vec![e])?;
+        FileAst::check(sources, file_id)
+    }
+
+    // Like new, for a source which does not have to be saved like an editor buffer.
+    pub fn from_source(
+        sources: &mut SourceMap,
+        file_name: &str,
+        source: &str,
+    ) -> Result<Self, Vec<CompileError>> {
+        let file_id = sources.add(file_name, source);
+        FileAst::check(sources, file_id)
+    }
+
+    fn check(sources: &SourceMap, file_id: FileId) -> Result<Self, Vec<CompileError>> {
+        let mut file_ast = FileAst::parse(sources, file_id);
+
+        if file_ast.errors.is_empty() {
+            type_check::check_file(&mut file_ast, "", &[], &[]);
+        }
+
+        match file_ast.compile_errors() {
+            errors if errors.is_empty() => Ok(file_ast),
+            errors => Err(errors),
+        }
+    }
+
+    // The syntax errors are kept in the tree, which holds the statements that could be parsed.
+    pub fn parse(sources: &SourceMap, file_id: FileId) -> Self {
+        let mut lexer = lexer::get_tokens(sources, file_id);
 
         let mut file_ast = FileAst {
-            file_name: Box::from(file_name),
+            file_name: lexer.file_name,
+            file_id,
             nodes: vec![],
             errors: lexer.errors,
+            type_errors: vec![],
             identifiers: lexer.identifiers,
             root_nodes: vec![],
+            type_paths: vec![],
         };
 
         if !file_ast.errors.is_empty() {
@@ -43,13 +85,9 @@ impl FileAst {
                 ParsingResult::Ok => {
                     file_ast.root_nodes.push(file_ast.nodes.len() - 1);
                 }
-                ParsingResult::Error => {
-                    lexer.stream.skip_until(
-                        |t| t.kind == TokenKind::EOI || t.kind == TokenKind::EOF,
-                        false,
-                    );
-                }
+                ParsingResult::Error => statement::recover(&mut lexer.stream),
                 ParsingResult::Other => {
+                    let reason = format!("Expected a statement but found {}.", file_ast.describe(lexer.stream.peek(0)));
                     let begin = lexer.stream.take().position;
                     lexer.stream.skip_until(
                         |t| t.kind == TokenKind::EOI || t.kind == TokenKind::EOF,
@@ -59,13 +97,55 @@ impl FileAst {
                     let end = lexer.stream.take().position;
                     file_ast.errors.push(SyntaxError {
                         position: FileSpan::combine(&begin, &end),
-                        reason: Box::from("Node cannot be parsed with your shit, morron!"),
+                        reason: reason.into_boxed_str(),
+                        fixes: vec![],
                     });
                 }
             };
         }
+
+        file_ast.link_parents();
         file_ast
     }
+
+    pub fn compile_errors(&self) -> Vec<CompileError> {
+        let syntax_errors = self.errors.iter().map(|error| CompileError::Syntax {
+            file: self.file_id,
+            error: error.clone(),
+        });
+        let type_errors = self.type_errors.iter().map(|error| CompileError::Type {
+            file: self.file_id,
+            error: error.clone(),
+        });
+
+        syntax_errors.chain(type_errors).collect()
+    }
+
+    // The token as the syntax errors name it, identifiers by their name in the source.
+    pub(crate) fn describe(&self, token: &Token) -> String {
+        match token.kind {
+            TokenKind::Identifier { index } => format!("Identifier {}", self.identifiers[index].name),
+            _ => token.kind.to_string(),
+        }
+    }
+
+    // Sets the parent of every node from the children of the others, nodes that are not held
+    // by any are the root ones or were left by a statement that could not be parsed.
+    pub(crate) fn link_parents(&mut self) {
+        for node in self.nodes.iter_mut() {
+            node.parent = NodeParent::Unchecked;
+        }
+
+        for index in self.root_nodes.iter() {
+            self.nodes[*index].parent = NodeParent::Root;
+        }
+
+        for index in 0..self.nodes.len() {
+            for child in self.nodes[index].children() {
+                self.nodes[child].parent = NodeParent::Node { index };
+            }
+        }
+    }
 }
 
 impl Display for FileAst {
@@ -0,0 +1,39 @@
+use crate::{
+    common::{operator::Operator, position::FileSpan, syntax_error::SyntaxError},
+    lexer::{token::TokenKind, token_stream::TokenStream},
+};
+
+use super::{
+    ast_node::{AstNode, AstNodeKind, NodeParent, ParsingResult},
+    expression::Expression,
+    identifier,
+    parser::FileAst,
+};
+
+// module::name, called once the name of the module has been parsed as an identifier.
+pub(in crate::parser) fn parse(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
+    let module = file_ast.nodes.len() - 1;
+    if !matches!(file_ast.nodes[module].kind, AstNodeKind::Expression(Expression::Identifier { .. }))
+        || !stream.skip_if(|t| t.kind == TokenKind::Operator(Operator::NameSpaceNav))
+    {
+        return ParsingResult::Other;
+    }
+
+    let name = match identifier::parse(stream, file_ast) {
+        ParsingResult::Ok => file_ast.nodes.len() - 1,
+        _ => {
+            let token = stream.peek(0);
+            let reason = format!("Expected a declaration name after :: but found {}.", file_ast.describe(token));
+            file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+            return ParsingResult::Error;
+        }
+    };
+
+    file_ast.nodes.push(AstNode {
+        kind: AstNodeKind::Expression(Expression::Path { module, name }),
+        position: FileSpan::combine(&file_ast.nodes[module].position, &file_ast.nodes[name].position),
+        parent: NodeParent::Unchecked
+    });
+
+    ParsingResult::Ok
+}
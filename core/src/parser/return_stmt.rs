@@ -19,38 +19,29 @@ pub(in crate::parser) fn parse(stream: &mut TokenStream, file_ast: &mut FileAst)
         None => return ParsingResult::Other,
     };
 
-    let end = match stream.skip_if(|t| t.kind == TokenKind::EOI) {
+    let value = match stream.peek(0).kind == TokenKind::EOI {
         true => None,
         false => match expression::parse(stream, file_ast) {
-            ParsingResult::Ok => Some(stream.peek(-1).position),
+            ParsingResult::Ok => Some(file_ast.nodes.len() - 1),
             ParsingResult::Error => return ParsingResult::Error,
             ParsingResult::Other => {
-                let begin = stream.take().position;
-                stream.skip_until(
-                    |t| t.kind == TokenKind::EOI || t.kind == TokenKind::EOF,
-                    false,
-                );
-                let end = stream.take().position;
-
-                let err = SyntaxError {
-                    position: FileSpan::combine(&begin, &end),
-                    reason: Box::from("Expected ; or a value after return keyword"),
-                };
-
-                file_ast.errors.push(err);
+                file_ast.errors.push(SyntaxError::from_token(
+                    stream.peek(0),
+                    Some(Box::from("Expected ; or a value after return keyword")),
+                ));
                 return ParsingResult::Error;
             }
         },
     };
 
-    let position = match end {
-        Some(pos) => FileSpan::combine(&begin, &pos),
+    let position = match value {
+        Some(index) => FileSpan::combine(&begin, &file_ast.nodes[index].position),
         None => begin,
     };
 
     file_ast.nodes.push(AstNode {
         position,
-        kind: AstNodeKind::Statement(Statement::Return),
+        kind: AstNodeKind::Statement(Statement::Return { value }),
         parent: NodeParent::Unchecked
     });
 
@@ -1,40 +1,98 @@
-use crate::lexer::{token::TokenKind, token_stream::TokenStream};
+use crate::{
+    common::{diagnostic::Fix, syntax_error::SyntaxError},
+    lexer::{token::TokenKind, token_stream::TokenStream},
+    type_system::llc_type::Type,
+};
 
 use super::{
-    ast_node::ParsingResult, declaration, expression, parser::FileAst, return_stmt,
+    assignment, ast_node::ParsingResult, declaration, expression, loop_stmt,
+    match_expression::Pattern, parser::FileAst, return_stmt, use_stmt,
 };
 
 pub enum Statement {
-    Declaration { ident_index: usize },
+    Declaration { ident_index: usize, annotation: Option<Type>, exp_index: Option<usize> },
+    Assignment { target: usize, exp_index: usize },
     Expression,
-    Return 
+    Return { value: Option<usize> },
+    Parameter { ident_index: usize, llc_type: Type },
+    Field { ident_index: usize, llc_type: Type },
+    FieldValue { ident_index: usize, exp_index: usize },
+    Variant { ident_index: usize, payload: Vec<Type> },
+    MatchArm { pattern: Pattern, statements: Vec<usize> },
+    While { condition: usize, statements: Vec<usize> },
+    For { ident_index: usize, start: usize, end: usize, statements: Vec<usize> },
+    Break,
+    Continue,
+    Use { path: Vec<usize> },
 }
 
 pub(in crate::parser) fn parse(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
+    match use_stmt::parse(stream, file_ast) {
+        ParsingResult::Ok => return end_statement(stream, file_ast),
+        ParsingResult::Error => return ParsingResult::Error,
+        ParsingResult::Other => {}
+    }
+
     match declaration::parse(stream, file_ast) {
-        ParsingResult::Ok => {
-            stream.skip_if(|t| t.kind == TokenKind::EOI);
-            return ParsingResult::Ok;
-        }
+        ParsingResult::Ok => return end_statement(stream, file_ast),
         ParsingResult::Error => return ParsingResult::Error,
         ParsingResult::Other => {}
     }
 
     match expression::parse(stream, file_ast) {
         ParsingResult::Ok => {
-            stream.skip_if(|t| t.kind == TokenKind::EOI);
-            return ParsingResult::Ok;
+            return match assignment::parse(stream, file_ast) {
+                ParsingResult::Error => ParsingResult::Error,
+                _ => end_statement(stream, file_ast),
+            }
         }
         ParsingResult::Error => return ParsingResult::Error,
         ParsingResult::Other => {}
     }
 
     match return_stmt::parse(stream, file_ast) {
-        ParsingResult::Ok => {
-            stream.skip_if(|t| t.kind == TokenKind::EOI);
-            return ParsingResult::Ok;
-        }
+        ParsingResult::Ok => return end_statement(stream, file_ast),
         ParsingResult::Error => return ParsingResult::Error,
-        ParsingResult::Other => return ParsingResult::Other,
+        ParsingResult::Other => {}
+    }
+
+    match loop_stmt::parse(stream, file_ast) {
+        ParsingResult::Ok => end_statement(stream, file_ast),
+        ParsingResult::Error => ParsingResult::Error,
+        ParsingResult::Other => ParsingResult::Other,
+    }
+}
+
+fn end_statement(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
+    if stream.skip_if(|t| t.kind == TokenKind::EOI) {
+        return ParsingResult::Ok;
+    }
+
+    let token = stream.peek(0);
+    let reason = format!("Expected ; at the end of the statement but found {}.", file_ast.describe(token));
+    let fix = Fix::insert_after(stream.peek(-1).position, ";", "Add ; at the end of the statement.");
+    file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())).with_fix(fix));
+    ParsingResult::Error
+}
+
+// Skips the remainder of a statement that failed to parse, up to the end of the
+// instruction or of the enclosing block. Nested blocks are skipped as a whole.
+pub(in crate::parser) fn recover(stream: &mut TokenStream) {
+    let mut depth = 0;
+
+    loop {
+        match stream.peek(0).kind {
+            TokenKind::EOF => return,
+            TokenKind::ClosingCurly if depth == 0 => return,
+            TokenKind::EOI if depth == 0 => {
+                stream.skip(1);
+                return;
+            }
+            TokenKind::OpenCurly => depth += 1,
+            TokenKind::ClosingCurly => depth -= 1,
+            _ => {}
+        }
+
+        stream.skip(1);
     }
 }
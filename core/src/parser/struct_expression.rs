@@ -0,0 +1,149 @@
+use crate::{
+    common::{keyword::Keyword, operator::Operator, position::FileSpan, syntax_error::SyntaxError},
+    lexer::{token::TokenKind, token_stream::TokenStream},
+};
+
+use super::{
+    ast_node::{AstNode, AstNodeKind, NodeParent, ParsingResult},
+    expression::{self, Expression},
+    identifier,
+    parser::FileAst,
+    statement::Statement,
+    type_expression,
+};
+
+// struct { name: type, ... }
+pub(in crate::parser) fn parse_definition(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
+    let begin = match stream.take_if(|t| match t.kind {
+        TokenKind::Keyword(Keyword::Struct) => Some(t.position),
+        _ => None,
+    }) {
+        Some(pos) => pos,
+        None => return ParsingResult::Other,
+    };
+
+    let (fields, end) = match parse_fields(stream, file_ast, "struct", parse_field) {
+        Some(fields) => fields,
+        None => return ParsingResult::Error,
+    };
+
+    file_ast.nodes.push(AstNode {
+        kind: AstNodeKind::Expression(Expression::StructDefinition { fields }),
+        position: FileSpan::combine(&begin, &end),
+        parent: NodeParent::Unchecked
+    });
+
+    ParsingResult::Ok
+}
+
+// Name.{ field: value, ... }, the name and the dot are already parsed.
+pub(in crate::parser) fn parse_literal(stream: &mut TokenStream, file_ast: &mut FileAst, type_index: usize) -> ParsingResult {
+    let (fields, end) = match parse_fields(stream, file_ast, "struct literal", parse_field_value) {
+        Some(fields) => fields,
+        None => return ParsingResult::Error,
+    };
+
+    file_ast.nodes.push(AstNode {
+        kind: AstNodeKind::Expression(Expression::StructLiteral { type_index, fields }),
+        position: FileSpan::combine(&file_ast.nodes[type_index].position, &end),
+        parent: NodeParent::Unchecked
+    });
+
+    ParsingResult::Ok
+}
+
+// Parses a curly brace delimited list of comma separated fields, a trailing comma is allowed.
+fn parse_fields(
+    stream: &mut TokenStream,
+    file_ast: &mut FileAst,
+    context: &str,
+    parse_item: fn(&mut TokenStream, &mut FileAst) -> Option<usize>,
+) -> Option<(Vec<usize>, FileSpan)> {
+    if !stream.skip_if(|t| t.kind == TokenKind::OpenCurly) {
+        let token = stream.peek(0);
+        let reason = format!("Expected {{ to open the {} fields but found {}.", context, file_ast.describe(token));
+        file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+        return None;
+    }
+
+    let mut fields = vec![];
+
+    loop {
+        if let Some(end) = stream.take_if(|t| match t.kind {
+            TokenKind::ClosingCurly => Some(t.position),
+            _ => None,
+        }) {
+            return Some((fields, end));
+        }
+
+        fields.push(parse_item(stream, file_ast)?);
+
+        if stream.skip_if(|t| t.kind == TokenKind::Comma) {
+            continue;
+        }
+
+        if stream.peek(0).kind != TokenKind::ClosingCurly {
+            let token = stream.peek(0);
+            let reason = format!("Expected , or }} in the {} fields but found {}.", context, file_ast.describe(token));
+            file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+            return None;
+        }
+    }
+}
+
+fn parse_field_name(stream: &mut TokenStream, file_ast: &mut FileAst) -> Option<usize> {
+    let ident_index = match identifier::parse(stream, file_ast) {
+        ParsingResult::Ok => file_ast.nodes.len() - 1,
+        _ => {
+            let token = stream.peek(0);
+            let reason = format!("Expected a field name but found {}.", file_ast.describe(token));
+            file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+            return None;
+        }
+    };
+
+    if !stream.skip_if(|t| t.kind == TokenKind::Operator(Operator::Declaration)) {
+        let token = stream.peek(0);
+        let reason = format!("Expected : after the field name but found {}.", file_ast.describe(token));
+        file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+        return None;
+    }
+
+    Some(ident_index)
+}
+
+fn parse_field(stream: &mut TokenStream, file_ast: &mut FileAst) -> Option<usize> {
+    let ident_index = parse_field_name(stream, file_ast)?;
+    let llc_type = type_expression::parse(stream, file_ast)?;
+
+    file_ast.nodes.push(AstNode {
+        kind: AstNodeKind::Statement(Statement::Field { ident_index, llc_type }),
+        position: FileSpan::combine(&file_ast.nodes[ident_index].position, &stream.peek(-1).position),
+        parent: NodeParent::Unchecked
+    });
+
+    Some(file_ast.nodes.len() - 1)
+}
+
+fn parse_field_value(stream: &mut TokenStream, file_ast: &mut FileAst) -> Option<usize> {
+    let ident_index = parse_field_name(stream, file_ast)?;
+
+    let exp_index = match expression::parse(stream, file_ast) {
+        ParsingResult::Ok => file_ast.nodes.len() - 1,
+        ParsingResult::Error => return None,
+        ParsingResult::Other => {
+            let token = stream.peek(0);
+            let reason = format!("Expected a field value but found {}.", file_ast.describe(token));
+            file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+            return None;
+        }
+    };
+
+    file_ast.nodes.push(AstNode {
+        kind: AstNodeKind::Statement(Statement::FieldValue { ident_index, exp_index }),
+        position: FileSpan::combine(&file_ast.nodes[ident_index].position, &file_ast.nodes[exp_index].position),
+        parent: NodeParent::Unchecked
+    });
+
+    Some(file_ast.nodes.len() - 1)
+}
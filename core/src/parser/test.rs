@@ -1,53 +1,132 @@
-use crate::common::{identifier::TypeState, literal::LiteralValue, position::FileSpan};
+use super::ast_node::{AstNodeKind, NodeParent};
+use super::expression::Expression;
+use super::parser::FileAst;
+use crate::common::source_map::SourceMap;
 
-pub struct ASTNode {
-    position: FileSpan,
-    kind: AstNodeKind,
+fn parse(source: &str) -> FileAst {
+    let mut sources = SourceMap::new();
+    let file_id = sources.add("test.llc", source);
+    FileAst::parse(&sources, file_id)
 }
 
-pub enum AstNodeKind {
-    Statement(StatementKind),
-    Expression(ExpressionKind),
+// The node as an s-expression, naming identifiers, literals and operators by their source.
+fn tree(ast: &FileAst, index: usize) -> String {
+    let node = &ast.nodes[index];
+    let head = match &node.kind {
+        AstNodeKind::Expression(Expression::Identifier { index }) => {
+            return ast.identifiers[*index].name.to_string()
+        }
+        AstNodeKind::Expression(Expression::Literal(literal)) => return literal.value.value.to_string(),
+        AstNodeKind::Expression(Expression::Binary { operator, .. })
+        | AstNodeKind::Expression(Expression::Unary { operator, .. }) => operator.to_string(),
+        _ => node.name().to_string(),
+    };
+
+    let children: Vec<String> = node.children().into_iter().map(|child| tree(ast, child)).collect();
+    match children.is_empty() {
+        true => format!("({})", head),
+        false => format!("({} {})", head, children.join(" ")),
+    }
+}
+
+fn roots(source: &str) -> Vec<String> {
+    let ast = parse(source);
+    assert!(ast.errors.is_empty(), "{:?}", ast.errors);
+    ast.root_nodes.iter().map(|index| tree(&ast, *index)).collect()
 }
 
-pub enum StatementKind {
-    Declaration { idx: usize },
-    Expression(ExpressionKind),
-    Return(ExpressionKind),
+fn errors(source: &str) -> Vec<String> {
+    parse(source).errors.iter().map(|error| error.reason.to_string()).collect()
 }
 
-pub enum ExpressionKind {
-    Identifier { decl_idx: usize },
-    Literal { idx: usize },
-    FunctionCall { idx: usize },
+#[test]
+fn binds_products_tighter_than_sums() {
+    assert_eq!(
+        roots("x := 1 + 2 * 3;\n"),
+        ["(Declaration x (Plus 1 (Times 2 3)))"]
+    );
 }
 
-pub struct Identifier {
-    decl_idx: usize,
+#[test]
+fn associates_operators_to_the_left() {
+    assert_eq!(
+        roots("x := 8 - 4 - 2;\n"),
+        ["(Declaration x (Minus (Minus 8 4) 2))"]
+    );
 }
 
-pub struct DeclarationStmt {
-    node: ASTNode,
-    id_name: Box<str>,
-    id_type: TypeState,
-    exp_idx: usize,
+#[test]
+fn groups_parenthesized_operations() {
+    assert_eq!(
+        roots("x := (1 + 2) * 3;\n"),
+        ["(Declaration x (Times (Plus 1 2) 3))"]
+    );
 }
 
-pub struct ReturnStmt {
+#[test]
+fn parses_each_root_statement() {
+    let source = "use math;\n\nx := 1;\nf := (a: i32) -> i32 {\n    => a;\n};\n";
 
+    assert_eq!(
+        roots(source),
+        [
+            "(Use math)",
+            "(Declaration x 1)",
+            "(Declaration f (BlockExpression (Parameter a) (Return a)))",
+        ]
+    );
 }
 
-struct AstArray {
-    stmt_array: StmtArray,
-    exp_array: ExpArray,
+#[test]
+fn links_children_to_their_parent() {
+    let ast = parse("x := 1 + 2;\n");
+
+    for (index, node) in ast.nodes.iter().enumerate() {
+        for child in node.children() {
+            assert!(matches!(ast.nodes[child].parent, NodeParent::Node { index: parent } if parent == index));
+        }
+    }
 }
 
-struct StmtArray {
-    decl_array: Vec<Declaration>,
+#[test]
+fn inserts_a_missing_semicolon() {
+    let ast = parse("main := {\n    x := 1\n};\n");
+
+    assert_eq!(ast.errors.len(), 1, "{:?}", ast.errors);
+    let fix = &ast.errors[0].fixes[0];
+    assert_eq!(&*fix.replacement, ";");
+    assert!(fix.is_insertion());
 }
 
-struct ExpArray {}
+#[test]
+fn reports_a_token_which_cannot_begin_a_statement() {
+    assert_eq!(errors(") := 1;\n"), ["Expected a statement but found Closing parenthesis."]);
+}
 
-pub struct DeclarationId {
-    id: usize,
-}
\ No newline at end of file
+#[test]
+fn names_the_found_token_as_it_is_written() {
+    assert_eq!(errors("x := 1 y;\n"), ["Expected ; at the end of the statement but found Identifier y."]);
+    assert_eq!(
+        errors("x := 1 + );\n"),
+        ["Expected an expression after operator + but found Closing parenthesis."]
+    );
+}
+
+#[test]
+fn keeps_parsing_after_a_syntax_error() {
+    let ast = parse("x := ;\ny := 2;\n");
+
+    assert!(!ast.errors.is_empty());
+    let roots: Vec<String> = ast.root_nodes.iter().map(|index| tree(&ast, *index)).collect();
+    assert_eq!(roots, ["(Declaration y 2)"]);
+}
+
+#[test]
+fn records_module_paths_in_types() {
+    let ast = parse("use shapes;\n\np: shapes::Point = shapes::Point.{ x: 1, y: 2 };\n");
+
+    assert!(ast.errors.is_empty(), "{:?}", ast.errors);
+    assert_eq!(ast.type_paths.len(), 1);
+    assert_eq!(&*ast.type_paths[0].module, "shapes");
+    assert_eq!(&*ast.type_paths[0].name, "Point");
+}
@@ -0,0 +1,176 @@
+use crate::{
+    common::{
+        literal::LiteralValue, operator::Operator, position::FileSpan, syntax_error::SyntaxError,
+    },
+    lexer::{token::TokenKind, token_stream::TokenStream},
+    type_system::llc_type::{self, Type},
+};
+
+use super::parser::FileAst;
+
+// A type named through the module declaring it, module::Name. The type checker checks that the
+// module is imported and declares the type.
+#[derive(Debug, Clone)]
+pub struct TypePath {
+    pub module: Box<str>,
+    pub name: Box<str>,
+    pub position: FileSpan,
+}
+
+// Parses the type expected at the current position, reports an error when there is none.
+// Names that are not built in types refer to structs or enums, which the type checker tells
+// apart once every declaration is known.
+pub(in crate::parser) fn parse(stream: &mut TokenStream, file_ast: &mut FileAst) -> Option<Type> {
+    let token = stream.peek(0);
+    let llc_type = match token.kind {
+        TokenKind::Identifier { index } => {
+            if stream.peek(1).kind == TokenKind::Operator(Operator::NameSpaceNav) {
+                return parse_path(stream, file_ast, index);
+            }
+
+            let name = &file_ast.identifiers[index].name;
+            match llc_type::parse_type_name(name) {
+                Some(t) => t,
+                None => Type::Struct { name: name.clone() },
+            }
+        }
+        TokenKind::Operator(op @ (Operator::Times | Operator::Ampersand | Operator::And)) => {
+            stream.skip(1);
+            let target = parse(stream, file_ast)?;
+            return Some(match op {
+                Operator::Times => pointer(target, true),
+                Operator::Ampersand => pointer(target, false),
+                // && is lexed as a single operator, it is a reference to a reference here.
+                _ => pointer(pointer(target, false), false),
+            });
+        }
+        TokenKind::OpenParenthesis => return parse_block_type(stream, file_ast),
+        TokenKind::OpenBracket => return parse_sequence_type(stream, file_ast),
+        _ => {
+            let reason = format!("Expected a type but found {}.", file_ast.describe(token));
+            file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+            return None;
+        }
+    };
+
+    stream.skip(1);
+    Some(llc_type)
+}
+
+// module::Name, the type keeps the path until the type checker qualifies it by the path of the
+// module.
+fn parse_path(stream: &mut TokenStream, file_ast: &mut FileAst, module: usize) -> Option<Type> {
+    let begin = stream.peek(0).position;
+    stream.skip(2);
+
+    let token = stream.peek(0);
+    let name = match token.kind {
+        TokenKind::Identifier { index } => file_ast.identifiers[index].name.clone(),
+        _ => {
+            let reason = format!("Expected a type name after :: but found {}.", file_ast.describe(token));
+            file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+            return None;
+        }
+    };
+
+    let module = file_ast.identifiers[module].name.clone();
+    file_ast.type_paths.push(TypePath {
+        module: module.clone(),
+        name: name.clone(),
+        position: FileSpan::combine(&begin, &token.position),
+    });
+    stream.skip(1);
+    Some(Type::Struct { name: Box::from(format!("{}::{}", module, name)) })
+}
+
+fn pointer(target: Type, nullable: bool) -> Type {
+    Type::Pointer { target: Box::new(target), nullable }
+}
+
+// [T; N] is an array of N values of type T, [T] a slice of them.
+fn parse_sequence_type(stream: &mut TokenStream, file_ast: &mut FileAst) -> Option<Type> {
+    stream.skip(1);
+    let element = Box::new(parse(stream, file_ast)?);
+
+    let llc_type = match stream.skip_if(|t| t.kind == TokenKind::EOI) {
+        true => Type::Array { element, size: parse_array_size(stream, file_ast)? },
+        false => Type::Slice { element },
+    };
+
+    if !stream.skip_if(|t| t.kind == TokenKind::ClosingBracket) {
+        let token = stream.peek(0);
+        let reason = format!("Expected ] to close the type but found {}.", file_ast.describe(token));
+        file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+        return None;
+    }
+
+    Some(llc_type)
+}
+
+fn parse_array_size(stream: &mut TokenStream, file_ast: &mut FileAst) -> Option<usize> {
+    let size = stream.take_if(|t| match &t.kind {
+        TokenKind::Literal(LiteralValue { llc_type: Type::Integer { .. }, value }) => value.parse().ok(),
+        _ => None,
+    });
+
+    if size.is_none() {
+        let token = stream.peek(0);
+        let reason = format!("Expected the size of the array but found {}.", file_ast.describe(token));
+        file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+    }
+
+    size
+}
+
+// The values of a variadic block are written ..., which the lexer reads as .. and a dot.
+pub(in crate::parser) fn skip_ellipsis(stream: &mut TokenStream) -> bool {
+    let is_ellipsis = stream.peek(0).kind == TokenKind::Operator(Operator::Range)
+        && stream.peek(1).kind == TokenKind::Operator(Operator::Dot);
+    if is_ellipsis {
+        stream.skip(2);
+    }
+    is_ellipsis
+}
+
+// Block types are written like signatures without parameter names: (i32, i32) -> i32.
+fn parse_block_type(stream: &mut TokenStream, file_ast: &mut FileAst) -> Option<Type> {
+    stream.skip(1);
+    let mut input = vec![];
+    let mut variadic = false;
+
+    while !stream.skip_if(|t| t.kind == TokenKind::ClosingParenthesis) {
+        if skip_ellipsis(stream) {
+            variadic = true;
+            if !stream.skip_if(|t| t.kind == TokenKind::ClosingParenthesis) {
+                let token = stream.peek(0);
+                let reason = format!("Expected ) after ... but found {}.", file_ast.describe(token));
+                file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+                return None;
+            }
+            break;
+        }
+
+        input.push(parse(stream, file_ast)?);
+
+        if !stream.skip_if(|t| t.kind == TokenKind::Comma) && stream.peek(0).kind != TokenKind::ClosingParenthesis {
+            let token = stream.peek(0);
+            let reason = format!("Expected , or ) in the block type but found {}.", file_ast.describe(token));
+            file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+            return None;
+        }
+    }
+
+    let output = match stream.skip_if(|t| t.kind == TokenKind::Operator(Operator::Into)) {
+        true => Some(Box::new(parse(stream, file_ast)?)),
+        false => None,
+    };
+
+    Some(Type::Block {
+        input: match input.is_empty() {
+            true => None,
+            false => Some(input),
+        },
+        output,
+        variadic,
+    })
+}
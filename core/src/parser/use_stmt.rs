@@ -0,0 +1,49 @@
+use crate::{
+    common::{keyword::Keyword, operator::Operator, position::FileSpan, syntax_error::SyntaxError},
+    lexer::{token::TokenKind, token_stream::TokenStream},
+};
+
+use super::{
+    ast_node::{AstNode, AstNodeKind, NodeParent, ParsingResult},
+    identifier,
+    parser::FileAst,
+    statement::Statement,
+};
+
+// use foo::bar, the path names a module or a declaration of a module.
+pub(in crate::parser) fn parse(stream: &mut TokenStream, file_ast: &mut FileAst) -> ParsingResult {
+    let begin = match stream.take_if(|t| match t.kind {
+        TokenKind::Keyword(Keyword::Use) => Some(t.position),
+        _ => None,
+    }) {
+        Some(pos) => pos,
+        None => return ParsingResult::Other,
+    };
+
+    let mut path = vec![];
+
+    loop {
+        match identifier::parse(stream, file_ast) {
+            ParsingResult::Ok => path.push(file_ast.nodes.len() - 1),
+            _ => {
+                let token = stream.peek(0);
+                let reason = format!("Expected a module name but found {}.", file_ast.describe(token));
+                file_ast.errors.push(SyntaxError::from_token(token, Some(reason.into_boxed_str())));
+                return ParsingResult::Error;
+            }
+        }
+
+        if !stream.skip_if(|t| t.kind == TokenKind::Operator(Operator::NameSpaceNav)) {
+            break;
+        }
+    }
+
+    let end = file_ast.nodes[*path.last().unwrap()].position;
+    file_ast.nodes.push(AstNode {
+        kind: AstNodeKind::Statement(Statement::Use { path }),
+        position: FileSpan::combine(&begin, &end),
+        parent: NodeParent::Unchecked
+    });
+
+    ParsingResult::Ok
+}
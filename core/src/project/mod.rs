@@ -0,0 +1,599 @@
+pub mod module_error;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    common::{compile_error::CompileError, identifier::TypeState, source_map::SourceMap},
+    parser::{
+        ast_node::AstNodeKind, expression::Expression, parser::FileAst, statement::Statement,
+    },
+    type_system::{
+        exports::{Exports, Import},
+        llc_type::Type,
+        type_check,
+    },
+};
+
+use module_error::ModuleError;
+
+// Names of a module from the project root, foo::bar is the file foo/bar.llc.
+type ModulePath = Vec<Box<str>>;
+
+pub struct Project {
+    pub root: PathBuf,
+    // Sources of the modules, along with the ones given to the project which were not used.
+    pub sources: SourceMap,
+    // Modules in dependency order, a module comes after every module it uses and the entry
+    // module comes last.
+    pub modules: Vec<Module>,
+    // Modules which cannot be read or used, the errors of a module are kept in its tree.
+    pub errors: Vec<CompileError>,
+}
+
+pub struct Module {
+    pub path: ModulePath,
+    pub file_ast: FileAst,
+    // Modules used by this one, as indices in the modules of the project.
+    pub dependencies: Vec<usize>,
+    pub exports: Exports,
+    uses: Vec<Use>,
+}
+
+// A use statement resolved to a module, and to one of its declarations when it names one.
+struct Use {
+    index: usize,
+    module: usize,
+    declaration: Option<Box<str>>,
+}
+
+struct Loader {
+    root: PathBuf,
+    sources: SourceMap,
+    modules: Vec<Module>,
+    errors: Vec<CompileError>,
+    loaded: HashMap<ModulePath, usize>,
+    // Modules whose uses are being loaded, a use of one of them is an import cycle.
+    loading: Vec<ModulePath>,
+}
+
+impl Project {
+    // Loads the entry file and every module it uses, directly or not. Modules are only type
+    // checked when all of them could be parsed.
+    pub fn load(root: &Path, entry: &Path) -> Self {
+        Project::load_sources(root, entry, SourceMap::new())
+    }
+
+    // Like load, the sources are used rather than the files of the same path, which do not have
+    // to exist. Their names are the paths of the files, like root/foo/bar.llc for foo::bar.
+    pub fn load_sources(root: &Path, entry: &Path, sources: SourceMap) -> Self {
+        let mut loader = Loader {
+            root: root.to_path_buf(),
+            sources,
+            modules: vec![],
+            errors: vec![],
+            loaded: HashMap::new(),
+            loading: vec![],
+        };
+
+        let name = entry.file_stem().unwrap_or_default().to_string_lossy();
+        loader.load(vec![Box::from(name.as_ref())], entry.to_path_buf());
+
+        let mut project = Project {
+            root: loader.root,
+            sources: loader.sources,
+            modules: loader.modules,
+            errors: loader.errors,
+        };
+
+        if project.errors.is_empty() && project.modules.iter().all(|m| m.file_ast.errors.is_empty())
+        {
+            project.check_types();
+        }
+
+        project
+    }
+
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+            || self
+                .modules
+                .iter()
+                .any(|m| !m.file_ast.errors.is_empty() || !m.file_ast.type_errors.is_empty())
+    }
+
+    // Errors of every file, grouped by file in the order the modules were loaded.
+    pub fn compile_errors(&self) -> Vec<CompileError> {
+        let mut errors: Vec<CompileError> = self
+            .errors
+            .iter()
+            .filter(|e| !self.modules.iter().any(|m| m.file_ast.file_id == e.file()))
+            .cloned()
+            .collect();
+
+        for module in self.modules.iter() {
+            let file = module.file_ast.file_id;
+            errors.extend(self.errors.iter().filter(|e| e.file() == file).cloned());
+            errors.extend(module.file_ast.compile_errors());
+        }
+
+        errors
+    }
+
+    pub fn entry(&self) -> &Module {
+        self.modules.last().expect("Expected an entry module")
+    }
+
+    // Programs start at the main block of the entry module, which C calls without arguments and
+    // whose result is the exit code of the program.
+    pub fn check_entry_point(&self) -> Result<(), CompileError> {
+        let file_ast = &self.entry().file_ast;
+        let error = |position, reason: &str| CompileError::EntryPoint {
+            file: file_ast.file_id,
+            position,
+            reason: Box::from(reason),
+        };
+
+        let main = file_ast.root_nodes.iter().find_map(|n| {
+            let ident_index = match file_ast.nodes[*n].kind {
+                AstNodeKind::Statement(Statement::Declaration { ident_index, .. }) => ident_index,
+                _ => return None,
+            };
+            match file_ast.nodes[ident_index].kind {
+                AstNodeKind::Expression(Expression::Identifier { index })
+                    if &*file_ast.identifiers[index].name == "main" =>
+                {
+                    Some((ident_index, index))
+                }
+                _ => None,
+            }
+        });
+        let (ident_index, symbol) =
+            main.ok_or_else(|| error(None, "The entry module declares no main block."))?;
+
+        let position = Some(file_ast.nodes[ident_index].position);
+        let (input, output) = match &file_ast.identifiers[symbol].type_state {
+            TypeState::Ok(Type::Block { input, output, .. }) => (input, output),
+            _ => return Err(error(position, "main is not a block.")),
+        };
+
+        if input.as_ref().is_some_and(|i| !i.is_empty()) {
+            return Err(error(position, "The main block cannot take parameters."));
+        }
+
+        match output.as_deref() {
+            None
+            | Some(Type::Integer {
+                signed: true,
+                size: 32,
+            }) => Ok(()),
+            Some(t) => Err(error(
+                position,
+                &format!("The main block returns nothing or an i32, not a {}.", t),
+            )),
+        }
+    }
+
+    // Path of the module qualifying the names of its types, the types of the entry module
+    // keep their own names.
+    pub fn qualifier(&self, module: usize) -> Box<str> {
+        match module + 1 == self.modules.len() {
+            true => Box::from(""),
+            false => Box::from(self.modules[module].path.join("::")),
+        }
+    }
+
+    fn check_types(&mut self) {
+        for i in 0..self.modules.len() {
+            let qualifier = self.qualifier(i);
+            let (checked, rest) = self.modules.split_at_mut(i);
+            let module = &mut rest[0];
+
+            let imports: Vec<Import> = module
+                .uses
+                .iter()
+                .map(|u| Import {
+                    use_index: u.index,
+                    exports: &checked[u.module].exports,
+                    declaration: u.declaration.clone(),
+                })
+                .collect();
+
+            let dependencies: Vec<&Exports> =
+                transitive_dependencies(checked, &module.dependencies)
+                    .into_iter()
+                    .map(|d| &checked[d].exports)
+                    .collect();
+
+            module.exports =
+                type_check::check_file(&mut module.file_ast, &qualifier, &imports, &dependencies);
+        }
+    }
+}
+
+fn transitive_dependencies(modules: &[Module], dependencies: &[usize]) -> Vec<usize> {
+    let mut visited = vec![];
+    let mut pending = dependencies.to_vec();
+
+    while let Some(d) = pending.pop() {
+        if !visited.contains(&d) {
+            visited.push(d);
+            pending.extend(modules[d].dependencies.iter());
+        }
+    }
+
+    visited
+}
+
+impl Module {
+    // Name of the module in the generated C files, foo::bar is foo/bar.h and foo/bar.c.
+    pub fn file_stem(&self) -> String {
+        self.path.join("/")
+    }
+
+    // Module named by one of the use statements of the module.
+    pub fn used_module(&self, use_index: usize) -> Option<usize> {
+        self.uses
+            .iter()
+            .find(|u| u.index == use_index)
+            .map(|u| u.module)
+    }
+}
+
+impl Loader {
+    // Returns the index of the module, or None when its file cannot be read.
+    fn load(&mut self, path: ModulePath, file: PathBuf) -> Option<usize> {
+        let file_id = match self.sources.load(&file.to_string_lossy()) {
+            Ok(id) => id,
+            Err(error) => {
+                self.errors.push(error);
+                return None;
+            }
+        };
+
+        let file_ast = FileAst::parse(&self.sources, file_id);
+
+        self.loading.push(path.clone());
+        let mut uses = vec![];
+        let mut dependencies = vec![];
+
+        if file_ast.errors.is_empty() {
+            for node_index in file_ast.root_nodes.iter() {
+                if let Some(u) = self.load_use(&file_ast, *node_index) {
+                    if !dependencies.contains(&u.module) {
+                        dependencies.push(u.module);
+                    }
+                    uses.push(u);
+                }
+            }
+        }
+
+        self.loading.pop();
+        self.modules.push(Module {
+            path: path.clone(),
+            file_ast,
+            dependencies,
+            exports: Exports::default(),
+            uses,
+        });
+
+        let index = self.modules.len() - 1;
+        self.loaded.insert(path, index);
+        Some(index)
+    }
+
+    fn load_use(&mut self, file_ast: &FileAst, node_index: usize) -> Option<Use> {
+        let names: Vec<Box<str>> = match &file_ast.nodes[node_index].kind {
+            AstNodeKind::Statement(Statement::Use { path }) => path
+                .iter()
+                .map(|p| match file_ast.nodes[*p].kind {
+                    AstNodeKind::Expression(Expression::Identifier { index }) => {
+                        file_ast.identifiers[index].name.clone()
+                    }
+                    _ => panic!("Expected an identifier"),
+                })
+                .collect(),
+            _ => return None,
+        };
+
+        let position = file_ast.nodes[node_index].position;
+        let (path, file, declaration) = match self.find_module(&names) {
+            Some(found) => found,
+            None => {
+                self.errors.push(CompileError::Module(ModuleError::new(
+                    file_ast.file_id,
+                    position,
+                    &format!("Module {} cannot be found.", names.join("::")),
+                )));
+                return None;
+            }
+        };
+
+        if let Some(first) = self.loading.iter().position(|l| *l == path) {
+            let mut cycle: Vec<String> =
+                self.loading[first..].iter().map(|l| l.join("::")).collect();
+            cycle.push(path.join("::"));
+            self.errors.push(CompileError::Module(ModuleError::new(
+                file_ast.file_id,
+                position,
+                &format!("Import cycle between modules {}.", cycle.join(" -> ")),
+            )));
+            return None;
+        }
+
+        let module = match self.loaded.get(&path) {
+            Some(m) => *m,
+            None => self.load(path, file)?,
+        };
+
+        if let Some(declaration) = &declaration {
+            if !declares(&self.modules[module].file_ast, declaration) {
+                self.errors.push(CompileError::Module(ModuleError::new(
+                    file_ast.file_id,
+                    position,
+                    &format!(
+                        "Module {} has no declaration {}.",
+                        self.modules[module].path.join("::"),
+                        declaration
+                    ),
+                )));
+                return None;
+            }
+        }
+
+        Some(Use {
+            index: node_index,
+            module,
+            declaration,
+        })
+    }
+
+    // use foo::bar loads foo/bar.llc, or the declaration bar of foo.llc when there is no
+    // such module.
+    fn find_module(&self, names: &[Box<str>]) -> Option<(ModulePath, PathBuf, Option<Box<str>>)> {
+        let file = self.module_file(names);
+        if self.exists(&file) {
+            return Some((names.to_vec(), file, None));
+        }
+
+        let (declaration, module) = names.split_last()?;
+        if module.is_empty() {
+            return None;
+        }
+
+        let file = self.module_file(module);
+        match self.exists(&file) {
+            true => Some((module.to_vec(), file, Some(declaration.clone()))),
+            false => None,
+        }
+    }
+
+    fn exists(&self, file: &Path) -> bool {
+        self.sources.find(&file.to_string_lossy()).is_some() || file.is_file()
+    }
+
+    fn module_file(&self, names: &[Box<str>]) -> PathBuf {
+        let mut file = self.root.clone();
+        for n in names {
+            file.push(&**n);
+        }
+        file.set_extension("llc");
+        file
+    }
+}
+
+fn declares(file_ast: &FileAst, name: &str) -> bool {
+    file_ast
+        .root_nodes
+        .iter()
+        .any(|n| match file_ast.nodes[*n].kind {
+            AstNodeKind::Statement(Statement::Declaration { ident_index, .. }) => {
+                match file_ast.nodes[ident_index].kind {
+                    AstNodeKind::Expression(Expression::Identifier { index }) => {
+                        *file_ast.identifiers[index].name == *name
+                    }
+                    _ => false,
+                }
+            }
+            _ => false,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Loads a project from sources only, none of the files exist on the disk.
+    fn load(files: &[(&str, &str)]) -> Project {
+        let mut sources = SourceMap::new();
+        for (name, content) in files {
+            sources.add(&format!("memory/{}", name), content);
+        }
+        Project::load_sources(Path::new("memory"), Path::new("memory/main.llc"), sources)
+    }
+
+    fn messages(project: &Project) -> Vec<String> {
+        project
+            .compile_errors()
+            .iter()
+            .map(|e| {
+                format!(
+                    "{}: {}",
+                    project.sources.name(e.file()),
+                    e.diagnostic().message
+                )
+            })
+            .collect()
+    }
+
+    fn entry_point_error(source: &str) -> Option<String> {
+        let project = load(&[("main.llc", source)]);
+        assert!(!project.has_errors(), "{:?}", messages(&project));
+        project
+            .check_entry_point()
+            .err()
+            .map(|e| e.diagnostic().message.to_string())
+    }
+
+    #[test]
+    fn accepts_main_blocks_returning_nothing_or_an_i32() {
+        assert_eq!(entry_point_error("main := {};\n"), None);
+        assert_eq!(
+            entry_point_error("main := () -> i32 {\n    => 0;\n};\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn checks_the_entry_point() {
+        assert_eq!(
+            entry_point_error("start := {};\n").as_deref(),
+            Some("The entry module declares no main block.")
+        );
+        assert_eq!(
+            entry_point_error("main := 1;\n").as_deref(),
+            Some("main is not a block.")
+        );
+        assert_eq!(
+            entry_point_error("main := (code: i32) -> i32 {\n    => code;\n};\n").as_deref(),
+            Some("The main block cannot take parameters.")
+        );
+        assert_eq!(
+            entry_point_error("main := () -> bool {\n    => true;\n};\n").as_deref(),
+            Some("The main block returns nothing or an i32, not a bool.")
+        );
+    }
+
+    #[test]
+    fn loads_modules_in_dependency_order() {
+        let project = load(&[
+            ("main.llc", "use util;\nuse geometry::point::Point;\n\nmain := {\n    p := Point.{ x: 1, y: 2 };\n    x : i32 = -> util::two();\n};\n"),
+            ("util.llc", "two := () -> i32 {\n    => 2;\n};\n"),
+            ("geometry/point.llc", "Point := struct {\n    x: i32,\n    y: i32,\n};\n"),
+        ]);
+
+        assert!(!project.has_errors(), "{:?}", messages(&project));
+        let paths: Vec<String> = project.modules.iter().map(|m| m.file_stem()).collect();
+        assert_eq!(paths, ["util", "geometry/point", "main"]);
+        assert_eq!(project.entry().dependencies, [0, 1]);
+    }
+
+    #[test]
+    fn reports_a_missing_module_in_the_file_using_it() {
+        let project = load(&[("main.llc", "use missing;\n\nmain := {};\n")]);
+
+        assert!(project.has_errors());
+        assert_eq!(
+            messages(&project),
+            ["memory/main.llc: Module missing cannot be found."]
+        );
+        let error = &project.compile_errors()[0];
+        assert_eq!(error.diagnostic().position.position.begin.row, 1);
+    }
+
+    #[test]
+    fn reports_an_import_cycle() {
+        let project = load(&[
+            ("main.llc", "use a;\n\nmain := {};\n"),
+            ("a.llc", "use b;\n\nx := 1;\n"),
+            ("b.llc", "use a;\n\ny := 2;\n"),
+        ]);
+
+        assert_eq!(
+            messages(&project),
+            ["memory/b.llc: Import cycle between modules a -> b -> a."]
+        );
+    }
+
+    #[test]
+    fn reports_an_unreadable_entry_file() {
+        let project = load(&[]);
+
+        assert!(project.has_errors());
+        assert!(project.modules.is_empty());
+        let errors = project.compile_errors();
+        assert!(matches!(errors[..], [CompileError::Io { .. }]));
+        assert_eq!(project.sources.name(errors[0].file()), "memory/main.llc");
+    }
+
+    #[test]
+    fn accepts_types_named_through_their_module() {
+        let project = load(&[
+            (
+                "main.llc",
+                "use point;\n\nnorm := (p: point::Point) -> i32 {\n    => p.x + p.y;\n};\n",
+            ),
+            (
+                "point.llc",
+                "Point := struct {\n    x: i32,\n    y: i32,\n};\n",
+            ),
+        ]);
+
+        assert!(!project.has_errors(), "{:?}", messages(&project));
+    }
+
+    #[test]
+    fn accepts_struct_literals_named_through_their_module() {
+        let project = load(&[
+            ("main.llc", "use point;\n\nmain := {\n    p := point::Point.{ x: 1, y: 2 };\n    x : i32 = p.x;\n};\n"),
+            ("point.llc", "Point := struct {\n    x: i32,\n    y: i32,\n};\n"),
+        ]);
+
+        assert!(!project.has_errors(), "{:?}", messages(&project));
+    }
+
+    #[test]
+    fn assigns_declarations_named_through_their_module() {
+        let project = load(&[
+            ("main.llc", "use g;\n\nmain := {\n    g::counter = 1;\n};\n"),
+            ("g.llc", "counter : i32 = 0;\n"),
+        ]);
+
+        assert!(!project.has_errors(), "{:?}", messages(&project));
+    }
+
+    #[test]
+    fn scopes_root_declarations_to_their_module() {
+        let project = load(&[
+            ("main.llc", "use a;\nuse b;\n\nPoint := struct {\n    x: i32,\n};\n\norigin := Point.{ x: 0 };\n\nmain := {\n    p : a::Point = -> a::origin();\n    q : b::Point = -> b::origin();\n    r : Point = origin;\n};\n"),
+            ("a.llc", "Point := struct {\n    x: i32,\n};\n\norigin := () -> Point {\n    => Point.{ x: 1 };\n};\n"),
+            ("b.llc", "Point := struct {\n    y: i32,\n};\n\norigin := () -> Point {\n    => Point.{ y: 2 };\n};\n"),
+        ]);
+
+        assert!(!project.has_errors(), "{:?}", messages(&project));
+    }
+
+    #[test]
+    fn tells_types_of_the_same_name_apart() {
+        let project = load(&[
+            ("main.llc", "use a;\nuse b;\n\nmain := {\n    p : a::Point = -> b::origin();\n};\n"),
+            ("a.llc", "Point := struct {\n    x: i32,\n};\n"),
+            ("b.llc", "Point := struct {\n    x: i32,\n};\n\norigin := () -> Point {\n    => Point.{ x: 2 };\n};\n"),
+        ]);
+
+        assert_eq!(
+            messages(&project),
+            ["memory/main.llc: Declared type a::Point does not match the value of type b::Point."]
+        );
+    }
+
+    #[test]
+    fn checks_the_module_of_a_type_path() {
+        let project = load(&[
+            ("main.llc", "use g;\n\na := (p: nosuch::Point) {};\nb := (p: g::counter) {};\n\nmain := {\n    g::tick = g::tick;\n    q := g::counter.{ x: 1 };\n};\n"),
+            ("g.llc", "counter : i32 = 0;\ntick := () {};\n"),
+        ]);
+
+        assert_eq!(
+            messages(&project),
+            [
+                "memory/main.llc: nosuch is not a module.",
+                "memory/main.llc: g::counter is a value, not a type.",
+                "memory/main.llc: Block tick cannot be reassigned.",
+                "memory/main.llc: g::counter is not a struct.",
+            ]
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use std::fmt::Display;
+
+use crate::common::{
+    diagnostic::{self, Diagnostic, Severity},
+    position::FileSpan,
+    source_map::{FileId, SourceSpan},
+};
+
+#[derive(Debug, Clone)]
+pub struct ModuleError {
+    pub file: FileId,
+    pub position: FileSpan,
+    pub reason: Box<str>,
+}
+
+impl ModuleError {
+    pub(crate) fn new(file: FileId, position: FileSpan, reason: &str) -> Self {
+        ModuleError {
+            file,
+            position,
+            reason: Box::from(reason),
+        }
+    }
+
+    pub fn diagnostic(&self) -> Diagnostic {
+        Diagnostic::new(
+            Severity::Error,
+            diagnostic::MODULE_ERROR,
+            &self.reason,
+            SourceSpan::new(self.file, self.position),
+        )
+    }
+}
+
+impl Display for ModuleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let msg = if self.position.begin.row == self.position.end.row {
+            format!(
+                "At line {0}, col {1}:{2}. ModuleError: {3}",
+                self.position.begin.row,
+                self.position.begin.col,
+                self.position.end.col,
+                self.reason
+            )
+        } else {
+            format!("{0}. ModuleError: {1}", self.position, self.reason)
+        };
+
+        write!(f, "{}", msg)
+    }
+}
@@ -0,0 +1,38 @@
+use std::collections::HashMap;
+
+use super::llc_type::Type;
+
+// What the other modules can use of a type checked file, every root declaration is exported.
+#[derive(Default)]
+pub struct Exports {
+    // Path of the module, foo::bar, which qualifies the names of the types it declares.
+    pub module: Box<str>,
+    pub declarations: HashMap<Box<str>, Export>,
+}
+
+pub enum Export {
+    Value(Type),
+    Struct {
+        fields: Vec<(Box<str>, Type)>,
+    },
+    Enum {
+        variants: Vec<(Box<str>, Vec<Type>)>,
+    },
+}
+
+// A use statement of the checked file, resolved to the exports of the module it names.
+pub struct Import<'a> {
+    pub use_index: usize,
+    pub exports: &'a Exports,
+    // The imported declaration, the whole module is imported when there is none.
+    pub declaration: Option<Box<str>>,
+}
+
+// Name of a type declared by the module, foo::bar::Point for the struct Point of foo::bar. The
+// types of the entry module, which no other module can use, keep their own name.
+pub fn qualify(module: &str, name: &str) -> Box<str> {
+    match module.is_empty() {
+        true => Box::from(name),
+        false => Box::from(format!("{}::{}", module, name)),
+    }
+}
@@ -1,8 +1,93 @@
+use std::fmt::Display;
+
+use phf::phf_map;
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Type {
     String,
     Char,
+    Bool,
     Float {signed: bool, size: usize},
     Integer {signed: bool, size: usize},
-    Block {input: Option<Vec<Type>>, output: Option<Box<Type>>}
-}
\ No newline at end of file
+    // Variadic blocks are extern C functions taking any values after their parameters.
+    Block {input: Option<Vec<Type>>, output: Option<Box<Type>>, variadic: bool},
+    // User types are nominal, their content is found through the declaration of the name.
+    Struct {name: Box<str>},
+    Enum {name: Box<str>},
+    // *T may be null, &T is a reference and never is. A reference converts to a pointer.
+    Pointer {target: Box<Type>, nullable: bool},
+    // Type of the null literal, which converts to every nullable pointer.
+    Null,
+    // Arrays hold their elements, slices point to elements stored elsewhere and know how
+    // many there are.
+    Array {element: Box<Type>, size: usize},
+    Slice {element: Box<Type>},
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Type::String => write!(f, "string"),
+            Type::Char => write!(f, "char"),
+            Type::Bool => write!(f, "bool"),
+            Type::Struct { name } | Type::Enum { name } => write!(f, "{}", name),
+            Type::Pointer { target, nullable } => write!(
+                f,
+                "{}{}",
+                match nullable {
+                    true => "*",
+                    false => "&",
+                },
+                target
+            ),
+            Type::Null => write!(f, "null"),
+            Type::Array { element, size } => write!(f, "[{}; {}]", element, size),
+            Type::Slice { element } => write!(f, "[{}]", element),
+            Type::Float { size, .. } => write!(f, "f{}", size),
+            Type::Integer { signed, size } => write!(
+                f,
+                "{}{}",
+                match signed {
+                    true => "i",
+                    false => "u",
+                },
+                size
+            ),
+            Type::Block { input, output, variadic } => {
+                let mut params: Vec<String> = match input {
+                    Some(params) => params.iter().map(|p| p.to_string()).collect(),
+                    None => vec![],
+                };
+                if *variadic {
+                    params.push(String::from("..."));
+                }
+
+                write!(f, "({})", params.join(", "))?;
+                match output {
+                    Some(ret) => write!(f, " -> {} {{}}", ret),
+                    None => write!(f, " {{}}"),
+                }
+            }
+        }
+    }
+}
+
+pub fn parse_type_name(s: &str) -> Option<Type> {
+    TYPE_NAME_MAP.get(s).cloned()
+}
+
+static TYPE_NAME_MAP: phf::Map<&str, Type> = phf_map! {
+    "i8" => Type::Integer { signed: true, size: 8 },
+    "i16" => Type::Integer { signed: true, size: 16 },
+    "i32" => Type::Integer { signed: true, size: 32 },
+    "i64" => Type::Integer { signed: true, size: 64 },
+    "u8" => Type::Integer { signed: false, size: 8 },
+    "u16" => Type::Integer { signed: false, size: 16 },
+    "u32" => Type::Integer { signed: false, size: 32 },
+    "u64" => Type::Integer { signed: false, size: 64 },
+    "f32" => Type::Float { signed: true, size: 32 },
+    "f64" => Type::Float { signed: true, size: 64 },
+    "char" => Type::Char,
+    "bool" => Type::Bool,
+    "string" => Type::String,
+};
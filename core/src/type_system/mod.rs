@@ -1,3 +1,4 @@
+pub mod exports;
 pub mod type_error;
 pub mod type_check;
 pub mod llc_type;
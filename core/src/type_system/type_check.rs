@@ -1,76 +1,3896 @@
-// use crate::{
-//     common::identifier::{TypeState, Identifier},
-//     parser::{
-//         ast_node::{AstNodeKind, NodeParent},
-//         expression::Expression,
-//         parser::FileAst,
-//         statement::Statement,
-//     },
-// };
-// 
-// use super::llc_type::Type;
-// 
-// pub fn check_file(file_ast: &mut FileAst) {
-//     let mut index = 0;
-// 
-//     loop {
-//         if  index >= file_ast.root_nodes.len() -1{
-//             break; 
-//         }
-// 
-//         let node_index = file_ast.root_nodes[index];
-//         let node = &mut file_ast.nodes[node_index];
-// 
-//         node.parent = NodeParent::Root;
-//         match node.kind {
-//             AstNodeKind::Statement(Statement::Declaration { ident_index }) => {
-//                 check_declaration(file_ast, ident_index, node_index);
-//             }
-//             _ => panic!("Expected a declaration at file root."),
-//         }
-// 
-//         index += 1;
-//     }
-// }
-// 
-// fn check_declaration(file_ast: &mut FileAst, ident_index: usize, decl_index: usize) {
-//     let exp_type = check_expression(file_ast, ident_index + 1, decl_index - 1);
-//     let ident = match file_ast.nodes[ident_index].kind {
-//         AstNodeKind::Expression(Expression::Identifier { index }) => {
-//             &mut file_ast.identifiers[index]
-//         }
-//         _ => panic!("Expected an identifier"),
-//     };
-// 
-//     if ident.type_state != TypeState::Unchecked {
-//         return;
-//     }
-// 
-//     ident.type_state = exp_type;
-// }
-// 
-// fn check_expression(file_ast: &mut FileAst, begin_index: usize, exp_index: usize) -> TypeState {
-//     let kind = &file_ast.nodes[exp_index].kind; 
-//     match kind {
-//         AstNodeKind::Expression(Expression::Identifier { index }) => {
-//             check_identifier(file_ast, begin_index,*index) 
-//         }
-//         AstNodeKind::Expression(Expression::Literal(lit)) => TypeState::Ok(lit.value.llc_type.to_owned()),
-//         AstNodeKind::Expression(Expression::Block) => TypeState::Ok(Type::Block {
-//             input: None,
-//             output: None,
-//         }),
-//         AstNodeKind::Expression(_) => todo!(),
-//         _ => panic!("Expected an expression"),
-//     }
-// }
-// 
-// fn check_identifier(file_ast: &mut FileAst, ident_node_index: usize, ident_index: usize) -> TypeState {
-//     let decl_index = match &file_ast.identifiers[ident_index] {
-//         id @ Identifier {type_state: TypeState::Unchecked, ..} => id.declaration_idx,
-//         id @ _ => return id.type_state.to_owned()
-//     };
-// 
-//     check_declaration(file_ast, ident_node_index, decl_index);
-//     file_ast.identifiers[ident_index].type_state.to_owned()
-// }
+use std::{collections::HashMap, ops::RangeInclusive};
+
+use crate::{
+    common::{
+        diagnostic::{self, Fix},
+        identifier::{Identifier, TypeState},
+        literal::LiteralValue,
+        operator::Operator,
+        position::FileSpan,
+    },
+    parser::{
+        ast_node::{AstNode, AstNodeKind, NodeParent},
+        expression::Expression,
+        literal::Literal,
+        match_expression::Pattern,
+        parser::FileAst,
+        statement::Statement,
+    },
+};
+
+use super::{
+    exports::{self, Export, Exports, Import},
+    llc_type::Type,
+    type_error::TypeError,
+};
+
+// Number of characters to insert, delete, substitute or swap with the next one to change one
+// name into the other.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for i in 0..=a.len() {
+        for j in 0..=b.len() {
+            distances[i][j] = match (i, j) {
+                (0, j) => j,
+                (i, 0) => i,
+                (i, j) => {
+                    let substitution = distances[i - 1][j - 1] + (a[i - 1] != b[j - 1]) as usize;
+                    let mut distance = substitution
+                        .min(distances[i - 1][j] + 1)
+                        .min(distances[i][j - 1] + 1);
+                    if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                        distance = distance.min(distances[i - 2][j - 2] + 1);
+                    }
+                    distance
+                }
+            };
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
+
+// Values an integer type can hold.
+fn integer_range(signed: bool, size: usize) -> RangeInclusive<i128> {
+    match signed {
+        true => -(1 << (size - 1))..=(1 << (size - 1)) - 1,
+        false => 0..=(1 << size) - 1,
+    }
+}
+
+// Lengths of arrays and slices are sizes, which C stores in a size_t.
+fn length_type() -> Type {
+    Type::Integer {
+        signed: false,
+        size: 64,
+    }
+}
+
+// Types written in the source which a node holds.
+fn annotations(kind: &mut AstNodeKind) -> Vec<&mut Type> {
+    match kind {
+        AstNodeKind::Statement(Statement::Declaration {
+            annotation: Some(t),
+            ..
+        })
+        | AstNodeKind::Statement(Statement::Parameter { llc_type: t, .. })
+        | AstNodeKind::Statement(Statement::Field { llc_type: t, .. }) => vec![t],
+        AstNodeKind::Statement(Statement::Variant { payload, .. }) => payload.iter_mut().collect(),
+        AstNodeKind::Expression(Expression::Block {
+            signature: Some(signature),
+            ..
+        })
+        | AstNodeKind::Expression(Expression::Extern { signature, .. }) => {
+            signature.output.iter_mut().collect()
+        }
+        _ => vec![],
+    }
+}
+
+enum ExpressionType {
+    Value(Type),
+    NoValue,
+    Invalid,
+}
+
+struct Scope {
+    symbols: HashMap<Box<str>, usize>,
+    // Scopes opened by a block body, identifiers declared outside of it cannot be captured.
+    is_block: bool,
+}
+
+struct BlockContext {
+    returns: Vec<(usize, ExpressionType)>,
+    // Number of loops enclosing the statement being checked, break and continue need one.
+    loops: usize,
+}
+
+struct TypeChecker<'a> {
+    file_ast: &'a mut FileAst,
+    // Path of the module, qualifying the names of its types, empty for the entry module.
+    module: &'a str,
+    scopes: Vec<Scope>,
+    blocks: Vec<BlockContext>,
+    claimed: Vec<bool>,
+    // Struct definitions by qualified name, structs can only be declared at file root.
+    structs: HashMap<Box<str>, usize>,
+    // Enum definitions by qualified name, declared at file root like structs.
+    enums: HashMap<Box<str>, usize>,
+    // Symbols bound by use statements, to a whole module or to one of its declarations.
+    modules: HashMap<usize, &'a Exports>,
+    imported: HashMap<usize, &'a Export>,
+    // Exports of every module the file depends on, directly or not. The types they declare
+    // can appear in the imported declarations without being imported themselves.
+    dependencies: &'a [&'a Exports],
+    // References bound to the name of a pointer where it is not null, to the pointer symbol.
+    narrowings: HashMap<usize, usize>,
+    // Variables declared without a value which are not assigned yet on the path being checked.
+    uninitialized: Vec<usize>,
+    // Expressions whose value is declared, assigned or returned, the only ones which can be if
+    // expressions with statements in their branches or match expressions. C has no expression
+    // for them, they are lowered to statements storing their value.
+    branching_values: Vec<usize>,
+}
+
+pub fn check_file<'a>(
+    file_ast: &'a mut FileAst,
+    module: &'a str,
+    imports: &'a [Import<'a>],
+    dependencies: &'a [&'a Exports],
+) -> Exports {
+    let claimed = vec![false; file_ast.identifiers.len()];
+    let mut checker = TypeChecker {
+        file_ast,
+        module,
+        scopes: vec![Scope::new(false)],
+        blocks: vec![],
+        claimed,
+        structs: HashMap::new(),
+        enums: HashMap::new(),
+        modules: HashMap::new(),
+        imported: HashMap::new(),
+        dependencies,
+        narrowings: HashMap::new(),
+        uninitialized: vec![],
+        branching_values: vec![],
+    };
+
+    let root_nodes = checker.file_ast.root_nodes.clone();
+    let mut declarations = vec![];
+
+    for node_index in root_nodes.iter() {
+        match checker.file_ast.nodes[*node_index].kind {
+            AstNodeKind::Statement(Statement::Use { .. }) => {
+                match imports.iter().find(|i| i.use_index == *node_index) {
+                    Some(import) => checker.declare_import(import),
+                    None => {
+                        let position = checker.file_ast.nodes[*node_index].position;
+                        checker.push_error(TypeError::new(
+                            position,
+                            "Modules can only be used by the files of a project.",
+                        ));
+                    }
+                }
+            }
+            AstNodeKind::Statement(Statement::Declaration {
+                ident_index,
+                exp_index,
+                ..
+            }) => {
+                let symbol = match checker.declare(ident_index, *node_index) {
+                    Some(s) => s,
+                    None => continue,
+                };
+
+                if let Some(e) = exp_index.filter(|e| checker.is_struct_definition(*e)) {
+                    let name = checker.qualified(&checker.file_ast.identifiers[symbol].name);
+                    checker.structs.insert(name, e);
+                }
+
+                if let Some(e) = exp_index.filter(|e| checker.is_enum_definition(*e)) {
+                    let name = checker.qualified(&checker.file_ast.identifiers[symbol].name);
+                    checker.enums.insert(name, e);
+                }
+
+                declarations.push(*node_index);
+            }
+            _ => {
+                let position = checker.file_ast.nodes[*node_index].position;
+                checker.push_error(TypeError::new(
+                    position,
+                    "Expected a declaration at file root.",
+                ));
+            }
+        }
+    }
+
+    checker.check_type_paths();
+    checker.qualify_annotations();
+    for node_index in declarations.iter() {
+        checker.check_root_declaration(*node_index);
+    }
+
+    // Dereferences and conversions are inserted as new nodes.
+    checker.file_ast.link_parents();
+    checker.exports(&declarations)
+}
+
+impl Scope {
+    fn new(is_block: bool) -> Self {
+        Scope {
+            symbols: HashMap::new(),
+            is_block,
+        }
+    }
+}
+
+impl<'a> TypeChecker<'a> {
+    fn push_error(&mut self, error: TypeError) {
+        self.file_ast.type_errors.push(error);
+    }
+
+    fn identifier_index(&self, ident_node: usize) -> usize {
+        match self.file_ast.nodes[ident_node].kind {
+            AstNodeKind::Expression(Expression::Identifier { index }) => index,
+            _ => panic!("Expected an identifier"),
+        }
+    }
+
+    fn set_identifier_index(&mut self, ident_node: usize, index: usize) {
+        self.file_ast.nodes[ident_node].kind =
+            AstNodeKind::Expression(Expression::Identifier { index });
+    }
+
+    // Binds the identifier node to a new symbol in the innermost scope. The first declaration
+    // of a name keeps the identifier built by the lexer, the following ones get their own.
+    fn declare(&mut self, ident_node: usize, decl_index: usize) -> Option<usize> {
+        let index = self.identifier_index(ident_node);
+        let name = self.file_ast.identifiers[index].name.clone();
+
+        if let Some(previous) = self.scopes.last().unwrap().symbols.get(&name) {
+            let declaration = self.file_ast.identifiers[*previous].declaration_idx;
+            let previous_position = self.file_ast.nodes[declaration].position;
+            let position = self.file_ast.nodes[ident_node].position;
+            self.push_error(
+                TypeError::new(
+                    position,
+                    &format!("Identifier {} is already declared in this scope.", name),
+                )
+                .with_code(diagnostic::REDECLARATION)
+                .with_label(previous_position, "first declared here"),
+            );
+            return None;
+        }
+
+        let symbol = match self.claimed.get(index) {
+            Some(false) => {
+                self.claimed[index] = true;
+                index
+            }
+            _ => {
+                self.file_ast.identifiers.push(Identifier::new(&name));
+                self.file_ast.identifiers.len() - 1
+            }
+        };
+
+        self.file_ast.identifiers[symbol].declaration_idx = decl_index;
+        self.set_identifier_index(ident_node, symbol);
+        self.scopes.last_mut().unwrap().symbols.insert(name, symbol);
+
+        Some(symbol)
+    }
+
+    fn resolve(&mut self, ident_node: usize) -> Option<usize> {
+        let index = self.identifier_index(ident_node);
+        let name = self.file_ast.identifiers[index].name.clone();
+        let mut crossed_block = false;
+
+        for (depth, scope) in self.scopes.iter().enumerate().rev() {
+            if let Some(symbol) = scope.symbols.get(&name) {
+                let symbol = *symbol;
+                if crossed_block && depth != 0 {
+                    let position = self.file_ast.nodes[ident_node].position;
+                    self.push_error(TypeError::new(
+                        position,
+                        &format!(
+                            "Identifier {} is declared outside of this block and cannot be captured.",
+                            name
+                        ),
+                    ));
+                    return None;
+                }
+
+                self.set_identifier_index(ident_node, symbol);
+                return Some(symbol);
+            }
+
+            if scope.is_block {
+                crossed_block = true;
+            }
+        }
+
+        let position = self.file_ast.nodes[ident_node].position;
+        let mut error = TypeError::new(position, &format!("Identifier {} is not declared.", name))
+            .with_code(diagnostic::UNDECLARED_NAME);
+        if let Some(similar) = self.similar_name(&name) {
+            let message = format!("A similar identifier is declared: {}.", similar);
+            error = error.with_fix(Fix::replace(position, &similar, &message));
+        }
+        self.push_error(error);
+        None
+    }
+
+    // Symbol an identifier node refers to, without binding it or reporting anything.
+    fn lookup(&self, ident_node: usize) -> Option<usize> {
+        let index = match self.file_ast.nodes[ident_node].kind {
+            AstNodeKind::Expression(Expression::Identifier { index }) => index,
+            _ => return None,
+        };
+        let name = &self.file_ast.identifiers[index].name;
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.symbols.get(name).copied())
+    }
+
+    // Closest name in scope to a name which is not declared, when it is close enough to be a
+    // typo of it. Any short name is a typo of another one, they get no suggestion.
+    fn similar_name(&self, name: &str) -> Option<Box<str>> {
+        let length = name.chars().count();
+        if length < 3 {
+            return None;
+        }
+
+        let max_distance = (length / 3).max(1);
+        self.scopes
+            .iter()
+            .flat_map(|scope| scope.symbols.keys())
+            .map(|symbol| (edit_distance(name, symbol), symbol))
+            .filter(|(distance, _)| *distance <= max_distance)
+            .min_by_key(|(distance, symbol)| (*distance, *symbol))
+            .map(|(_, symbol)| symbol.clone())
+    }
+
+    // Root declarations can be used before they are declared, so they are checked lazily the
+    // first time one of their usages is encountered, outside of the current block context.
+    fn check_root_declaration(&mut self, decl_index: usize) {
+        let ident_node = match self.file_ast.nodes[decl_index].kind {
+            AstNodeKind::Statement(Statement::Declaration { ident_index, .. }) => ident_index,
+            _ => panic!("Expected a declaration"),
+        };
+
+        let symbol = self.identifier_index(ident_node);
+        if self.file_ast.identifiers[symbol].type_state != TypeState::Unchecked {
+            return;
+        }
+
+        let scopes = self.scopes.split_off(1);
+        let blocks = std::mem::take(&mut self.blocks);
+
+        self.check_declaration(decl_index, Some(symbol));
+
+        self.scopes.extend(scopes);
+        self.blocks = blocks;
+    }
+
+    fn check_declaration(&mut self, decl_index: usize, symbol: Option<usize>) {
+        let (ident_node, annotation, exp_index) = match &self.file_ast.nodes[decl_index].kind {
+            AstNodeKind::Statement(Statement::Declaration {
+                ident_index,
+                annotation,
+                exp_index,
+            }) => (*ident_index, annotation.clone(), *exp_index),
+            _ => panic!("Expected a declaration"),
+        };
+        let annotation = annotation.map(|t| self.resolve_type(&t));
+
+        if let (Some(s), Some(e)) = (symbol, exp_index) {
+            if self.is_struct_definition(e) {
+                let name = self.qualified(&self.file_ast.identifiers[s].name);
+                self.file_ast.identifiers[s].type_state = TypeState::Ok(Type::Struct { name });
+                self.check_struct_definition(s, e);
+                return;
+            }
+
+            if self.is_enum_definition(e) {
+                let name = self.qualified(&self.file_ast.identifiers[s].name);
+                self.file_ast.identifiers[s].type_state = TypeState::Ok(Type::Enum { name });
+                self.check_enum_definition(s, e);
+                return;
+            }
+
+            if self.is_extern(e) {
+                self.check_extern(s, e, annotation);
+                return;
+            }
+        }
+
+        if let Some(t) = &annotation {
+            let position = self.file_ast.nodes[ident_node].position;
+            self.check_type(t, position);
+        }
+
+        // A block may refer to itself, so its name is visible inside of its own body.
+        let is_block = exp_index.is_some_and(|e| {
+            matches!(
+                self.file_ast.nodes[e].kind,
+                AstNodeKind::Expression(Expression::Block { .. })
+            )
+        });
+
+        let is_root = symbol.is_some();
+        let declared_early = is_root || is_block;
+        let mut symbol = match symbol {
+            Some(s) => Some(s),
+            None if is_block => self.declare(ident_node, decl_index),
+            None => None,
+        };
+
+        // Annotated declarations and blocks with a signature have a known type before their
+        // value is checked, which allows blocks to be recursive.
+        if let Some(s) = symbol {
+            let known_type = match (&annotation, exp_index) {
+                (Some(t), _) => Some(t.clone()),
+                (None, Some(e)) => self.signature_type(e),
+                (None, None) => None,
+            };
+
+            self.file_ast.identifiers[s].type_state = match known_type {
+                Some(t) => TypeState::Ok(t),
+                None => TypeState::InProgress,
+            };
+        }
+
+        // Root declarations are C globals, their value has to be an expression.
+        if let (None, Some(e)) = (symbol, exp_index) {
+            self.branching_values.push(e);
+        }
+
+        let type_state = match exp_index {
+            Some(e) => {
+                let type_state = self.check_initializer(e, annotation);
+                if is_root && matches!(type_state, TypeState::Ok(_)) && !self.is_constant(e) {
+                    let position = self.file_ast.nodes[e].position;
+                    self.push_error(TypeError::new(
+                        position,
+                        "Root declarations are C globals, their value has to be a constant expression.",
+                    ));
+                }
+                type_state
+            }
+            None => {
+                let annotation = annotation.expect("Expected a type annotation");
+                if let Type::Pointer {
+                    nullable: false, ..
+                } = annotation
+                {
+                    let position = self.file_ast.nodes[decl_index].position;
+                    self.push_error(TypeError::new(
+                        position,
+                        "References must be initialized when they are declared.",
+                    ));
+                }
+                TypeState::Ok(annotation)
+            }
+        };
+
+        if !declared_early {
+            symbol = self.declare(ident_node, decl_index);
+            if let (Some(s), None) = (symbol, exp_index) {
+                self.uninitialized.push(s);
+            }
+        }
+
+        if let Some(s) = symbol {
+            self.file_ast.identifiers[s].type_state = type_state;
+        }
+    }
+
+    // Values C can compute before the program runs: literals and the arithmetic on them, the
+    // addresses of root declarations and of blocks, and the literals of structs, arrays and
+    // enums holding them.
+    fn is_constant(&self, exp_index: usize) -> bool {
+        match &self.file_ast.nodes[exp_index].kind {
+            AstNodeKind::Expression(
+                Expression::Literal(_)
+                | Expression::Block { .. }
+                | Expression::Extern { .. }
+                | Expression::StructDefinition { .. }
+                | Expression::EnumDefinition { .. },
+            ) => true,
+            AstNodeKind::Expression(Expression::Identifier { .. } | Expression::Path { .. }) => {
+                matches!(self.symbol_type(exp_index), Some(Type::Block { .. }))
+            }
+            AstNodeKind::Expression(Expression::Unary {
+                operator: Operator::Ampersand,
+                operand,
+            }) => self.is_global(*operand),
+            AstNodeKind::Expression(Expression::Unary {
+                operator: Operator::Times,
+                ..
+            }) => false,
+            AstNodeKind::Expression(Expression::Unary { operand, .. }) => {
+                self.is_constant(*operand)
+            }
+            AstNodeKind::Expression(Expression::Binary { lhs, rhs, .. }) => {
+                self.is_constant(*lhs) && self.is_constant(*rhs)
+            }
+            AstNodeKind::Expression(Expression::If {
+                condition,
+                then_statements,
+                else_statements: Some(else_statements),
+            }) => {
+                self.is_constant(*condition)
+                    && [then_statements, else_statements]
+                        .iter()
+                        .all(|b| self.branch_value(b).is_some_and(|v| self.is_constant(v)))
+            }
+            AstNodeKind::Expression(Expression::StructLiteral { fields, .. }) => {
+                fields.iter().all(|f| match self.file_ast.nodes[*f].kind {
+                    AstNodeKind::Statement(Statement::FieldValue { exp_index, .. }) => {
+                        self.is_constant(exp_index)
+                    }
+                    _ => panic!("Expected a field value"),
+                })
+            }
+            AstNodeKind::Expression(
+                Expression::ArrayLiteral { elements: values }
+                | Expression::EnumVariant { args: values, .. },
+            ) => values.iter().all(|v| self.is_constant(*v)),
+            _ => false,
+        }
+    }
+
+    // Whether the identifier or path names a root declaration, of this module or of another.
+    fn is_global(&self, exp_index: usize) -> bool {
+        let ident_node = match self.file_ast.nodes[exp_index].kind {
+            AstNodeKind::Expression(Expression::Identifier { .. }) => exp_index,
+            AstNodeKind::Expression(Expression::Path { name, .. }) => name,
+            _ => return false,
+        };
+        let symbol = self.identifier_index(ident_node);
+        let declaration = self.file_ast.identifiers[symbol].declaration_idx;
+        self.file_ast.root_nodes.contains(&declaration)
+    }
+
+    fn symbol_type(&self, exp_index: usize) -> Option<&Type> {
+        let ident_node = match self.file_ast.nodes[exp_index].kind {
+            AstNodeKind::Expression(Expression::Path { name, .. }) => name,
+            _ => exp_index,
+        };
+        let symbol = self.identifier_index(ident_node);
+        match &self.file_ast.identifiers[symbol].type_state {
+            TypeState::Ok(t) => Some(t),
+            _ => None,
+        }
+    }
+
+    // The annotation, when there is one, is the type of the declaration even if the value
+    // does not match it, so the mismatch is only reported once.
+    fn check_initializer(&mut self, exp_index: usize, annotation: Option<Type>) -> TypeState {
+        let position = self.file_ast.nodes[exp_index].position;
+        let value_type = match self.check_expression(exp_index) {
+            ExpressionType::Value(t) => t,
+            ExpressionType::Invalid => {
+                return match annotation {
+                    Some(t) => TypeState::Ok(t),
+                    None => TypeState::Invalid,
+                }
+            }
+            ExpressionType::NoValue => {
+                self.push_error(TypeError::new(
+                    position,
+                    "Expression does not produce a value and cannot be declared.",
+                ));
+                return match annotation {
+                    Some(t) => TypeState::Ok(t),
+                    None => TypeState::Invalid,
+                };
+            }
+        };
+
+        let expected = match (annotation, &value_type) {
+            (Some(t), _) => t,
+            (None, Type::Null) => {
+                self.push_error(TypeError::new(
+                    position,
+                    "The type of null cannot be inferred, the declaration needs a pointer type.",
+                ));
+                return TypeState::Invalid;
+            }
+            (None, _) => return TypeState::Ok(value_type),
+        };
+
+        if !self.convert(exp_index, &value_type, &expected) {
+            let reason = self.range_error(exp_index, &expected).unwrap_or(format!(
+                "Declared type {} does not match the value of type {}.",
+                expected, value_type
+            ));
+            self.push_error(
+                TypeError::new(position, &reason).with_code(diagnostic::MISMATCHED_TYPES),
+            );
+        }
+
+        TypeState::Ok(expected)
+    }
+
+    fn check_statement(&mut self, index: usize) {
+        match &self.file_ast.nodes[index].kind {
+            AstNodeKind::Statement(Statement::Declaration { .. }) => {
+                self.check_declaration(index, None);
+            }
+            AstNodeKind::Statement(Statement::Assignment { target, exp_index }) => {
+                let (target, exp_index) = (*target, *exp_index);
+                self.check_assignment(target, exp_index);
+            }
+            AstNodeKind::Statement(Statement::Return { value }) => {
+                let value = *value;
+                let exp_type = match value {
+                    Some(v) => {
+                        self.branching_values.push(v);
+                        self.check_expression(v)
+                    }
+                    None => ExpressionType::NoValue,
+                };
+
+                match self.blocks.last_mut() {
+                    Some(block) => block.returns.push((index, exp_type)),
+                    None => {
+                        let position = self.file_ast.nodes[index].position;
+                        self.push_error(TypeError::new(
+                            position,
+                            "Return statements are only allowed inside of a block.",
+                        ));
+                    }
+                }
+            }
+            AstNodeKind::Statement(Statement::Parameter {
+                ident_index,
+                llc_type,
+            }) => {
+                let (ident_index, llc_type) = (*ident_index, self.resolve_type(llc_type));
+                let position = self.file_ast.nodes[index].position;
+                self.check_type(&llc_type, position);
+                if let Some(symbol) = self.declare(ident_index, index) {
+                    self.file_ast.identifiers[symbol].type_state = TypeState::Ok(llc_type);
+                }
+            }
+            AstNodeKind::Statement(Statement::While {
+                condition,
+                statements,
+            }) => {
+                let (condition, statements) = (*condition, statements.clone());
+                self.check_condition(condition);
+                let narrowed = match self.null_check(condition) {
+                    Some((symbol, true)) => Some(symbol),
+                    _ => None,
+                };
+
+                let narrowing = self.narrow(narrowed);
+                self.check_loop_body(&statements);
+                if narrowing {
+                    self.scopes.pop();
+                }
+            }
+            AstNodeKind::Statement(Statement::For { .. }) => self.check_for(index),
+            AstNodeKind::Statement(Statement::Break | Statement::Continue) => {
+                if self.blocks.last().is_none_or(|b| b.loops == 0) {
+                    let position = self.file_ast.nodes[index].position;
+                    let keyword = match self.file_ast.nodes[index].kind {
+                        AstNodeKind::Statement(Statement::Break) => "break",
+                        _ => "continue",
+                    };
+                    self.push_error(TypeError::new(
+                        position,
+                        &format!("{} can only be used inside of a loop.", keyword),
+                    ));
+                }
+            }
+            AstNodeKind::Statement(Statement::Field { .. } | Statement::FieldValue { .. }) => {
+                panic!("Unexpected struct field")
+            }
+            AstNodeKind::Statement(Statement::Use { .. }) => {
+                let position = self.file_ast.nodes[index].position;
+                self.push_error(TypeError::new(
+                    position,
+                    "Modules can only be used at file root.",
+                ));
+            }
+            AstNodeKind::Statement(Statement::Variant { .. }) => panic!("Unexpected enum variant"),
+            AstNodeKind::Statement(Statement::MatchArm { .. }) => panic!("Unexpected match arm"),
+            AstNodeKind::Statement(Statement::Expression) => {}
+            AstNodeKind::Expression(Expression::If { .. }) => {
+                self.check_if(index, false);
+            }
+            AstNodeKind::Expression(Expression::Match { .. }) => {
+                self.check_match(index, false);
+            }
+            AstNodeKind::Expression(_) => {
+                self.check_expression(index);
+            }
+        }
+    }
+
+    // The value is checked first, it still sees a narrowed pointer as a reference.
+    fn check_assignment(&mut self, target: usize, exp_index: usize) {
+        self.branching_values.push(exp_index);
+        let value_type = self.check_expression(exp_index);
+
+        let target_type = match &self.file_ast.nodes[target].kind {
+            AstNodeKind::Expression(Expression::Identifier { .. }) => {
+                self.check_variable_target(target)
+            }
+            AstNodeKind::Expression(Expression::Path { .. }) => self.check_path_target(target),
+            AstNodeKind::Expression(
+                Expression::FieldAccess { .. }
+                | Expression::Unary {
+                    operator: Operator::Times,
+                    ..
+                },
+            ) if self.is_variable_field(target) => self.check_expression(target),
+            // Whether an element is in memory depends on the type of the indexed value.
+            AstNodeKind::Expression(Expression::Index { .. }) => {
+                match self.check_expression(target) {
+                    ExpressionType::Invalid => ExpressionType::Invalid,
+                    t if self.is_variable_field(target) => t,
+                    _ => {
+                        self.push_target_error(target);
+                        ExpressionType::Invalid
+                    }
+                }
+            }
+            _ => {
+                self.push_target_error(target);
+                ExpressionType::Invalid
+            }
+        };
+
+        let target_type = match target_type {
+            ExpressionType::Value(t) => t,
+            _ => return,
+        };
+
+        let reason = match value_type {
+            ExpressionType::Value(t) if self.convert(exp_index, &t, &target_type) => return,
+            ExpressionType::Invalid => return,
+            ExpressionType::Value(t) => {
+                self.range_error(exp_index, &target_type).unwrap_or(format!(
+                    "Cannot assign a value of type {} to a variable of type {}.",
+                    t, target_type
+                ))
+            }
+            ExpressionType::NoValue => {
+                String::from("Expression does not produce a value and cannot be assigned.")
+            }
+        };
+
+        let position = self.file_ast.nodes[exp_index].position;
+        self.push_error(TypeError::new(position, &reason).with_code(diagnostic::MISMATCHED_TYPES));
+    }
+
+    fn push_target_error(&mut self, target: usize) {
+        let position = self.file_ast.nodes[target].position;
+        self.push_error(TypeError::new(
+            position,
+            "Only variables, their fields, elements and dereferenced pointers can be assigned.",
+        ));
+    }
+
+    // Root declarations of other modules are C globals, except for their blocks which are
+    // functions.
+    fn check_path_target(&mut self, target: usize) -> ExpressionType {
+        let target_type = self.check_expression(target);
+        if let ExpressionType::Value(Type::Block { .. }) = target_type {
+            let position = self.file_ast.nodes[target].position;
+            let name = match self.file_ast.nodes[target].kind {
+                AstNodeKind::Expression(Expression::Path { name, .. }) => self.field_name(name),
+                _ => panic!("Expected a path"),
+            };
+            self.push_error(TypeError::new(
+                position,
+                &format!("Block {} cannot be reassigned.", name),
+            ));
+            return ExpressionType::Invalid;
+        }
+
+        target_type
+    }
+
+    fn check_variable_target(&mut self, ident_node: usize) -> ExpressionType {
+        let symbol = match self.resolve(ident_node) {
+            Some(s) => self.end_narrowing(s),
+            None => return ExpressionType::Invalid,
+        };
+
+        // Blocks are lowered to functions, their name is not a variable.
+        let declaration = self.file_ast.identifiers[symbol].declaration_idx;
+        if let AstNodeKind::Statement(Statement::Declaration {
+            exp_index: Some(e), ..
+        }) = self.file_ast.nodes[declaration].kind
+        {
+            if let AstNodeKind::Expression(Expression::Block { .. }) = self.file_ast.nodes[e].kind {
+                let position = self.file_ast.nodes[ident_node].position;
+                let name = self.file_ast.identifiers[symbol].name.clone();
+                self.push_error(TypeError::new(
+                    position,
+                    &format!("Block {} cannot be reassigned.", name),
+                ));
+                return ExpressionType::Invalid;
+            }
+        }
+
+        self.uninitialized.retain(|s| *s != symbol);
+        self.check_identifier(ident_node)
+    }
+
+    // Fields can only be assigned through a variable, a.b.c = v but not -> f().c = v. Any
+    // dereferenced value lives in memory, *(-> f()) = v is allowed, and so do the elements of
+    // a slice. Elements of an array are like its fields.
+    fn is_variable_field(&self, index: usize) -> bool {
+        match &self.file_ast.nodes[index].kind {
+            AstNodeKind::Expression(
+                Expression::Identifier { .. }
+                | Expression::Unary {
+                    operator: Operator::Times,
+                    ..
+                },
+            ) => true,
+            AstNodeKind::Expression(Expression::Index {
+                sequence: Some(Type::Slice { .. }),
+                ..
+            }) => true,
+            AstNodeKind::Expression(
+                Expression::FieldAccess { object, .. } | Expression::Index { object, .. },
+            ) => self.is_variable_field(*object),
+            _ => false,
+        }
+    }
+
+    fn check_expression(&mut self, index: usize) -> ExpressionType {
+        match &self.file_ast.nodes[index].kind {
+            AstNodeKind::Expression(Expression::Literal(lit)) => {
+                ExpressionType::Value(lit.value.llc_type.clone())
+            }
+            AstNodeKind::Expression(Expression::Identifier { .. }) => self.check_identifier(index),
+            AstNodeKind::Expression(Expression::FunctionCall { .. }) => self.check_call(index),
+            AstNodeKind::Expression(Expression::Block { .. }) => self.check_block(index),
+            AstNodeKind::Expression(Expression::Binary { operator, lhs, rhs }) => {
+                let (operator, lhs, rhs) = (*operator, *lhs, *rhs);
+                self.check_binary(index, operator, lhs, rhs)
+            }
+            AstNodeKind::Expression(Expression::Unary { operator, operand }) => {
+                let (operator, operand) = (*operator, *operand);
+                self.check_unary(index, operator, operand)
+            }
+            AstNodeKind::Expression(Expression::If { .. }) => self.check_if(index, true),
+            AstNodeKind::Expression(Expression::StructDefinition { .. }) => {
+                let position = self.file_ast.nodes[index].position;
+                self.push_error(TypeError::new(
+                    position,
+                    "Structs can only be declared at file root.",
+                ));
+                ExpressionType::Invalid
+            }
+            AstNodeKind::Expression(Expression::StructLiteral { .. }) => {
+                self.check_struct_literal(index)
+            }
+            // Variants without payload look like field accesses on the name of the enum.
+            AstNodeKind::Expression(Expression::FieldAccess { object, field })
+                if self.is_enum_name(*object) =>
+            {
+                let (enum_index, variant) = (*object, *field);
+                self.file_ast.nodes[index].kind =
+                    AstNodeKind::Expression(Expression::EnumVariant {
+                        enum_index,
+                        variant,
+                        args: vec![],
+                    });
+                self.check_enum_variant(index)
+            }
+            AstNodeKind::Expression(Expression::FieldAccess { object, field }) => {
+                let (object, field) = (*object, *field);
+                self.check_field_access(index, object, field)
+            }
+            AstNodeKind::Expression(Expression::EnumDefinition { .. }) => {
+                let position = self.file_ast.nodes[index].position;
+                self.push_error(TypeError::new(
+                    position,
+                    "Enums can only be declared at file root.",
+                ));
+                ExpressionType::Invalid
+            }
+            AstNodeKind::Expression(Expression::Extern { .. }) => {
+                let position = self.file_ast.nodes[index].position;
+                self.push_error(TypeError::new(
+                    position,
+                    "Extern blocks can only be declared at file root.",
+                ));
+                ExpressionType::Invalid
+            }
+            AstNodeKind::Expression(Expression::EnumVariant { .. }) => {
+                self.check_enum_variant(index)
+            }
+            AstNodeKind::Expression(Expression::Match { .. }) => self.check_match(index, true),
+            AstNodeKind::Expression(Expression::Path { module, name }) => {
+                let (module, name) = (*module, *name);
+                self.check_path(module, name)
+            }
+            AstNodeKind::Expression(Expression::ArrayLiteral { .. }) => {
+                self.check_array_literal(index)
+            }
+            AstNodeKind::Expression(Expression::Index {
+                object, index: i, ..
+            }) => {
+                let (object, i) = (*object, *i);
+                self.check_index(index, object, i)
+            }
+            AstNodeKind::Expression(Expression::Slicing {
+                object, start, end, ..
+            }) => {
+                let (object, start, end) = (*object, *start, *end);
+                self.check_slicing(index, object, start, end)
+            }
+            AstNodeKind::Statement(_) => panic!("Expected an expression"),
+        }
+    }
+
+    // Whether the value of the expression can be used where a value of type expected is. A
+    // reference can be used as a pointer to the same type, but a pointer is not a reference.
+    fn is_convertible(&self, exp_index: usize, value_type: &Type, expected: &Type) -> bool {
+        match (value_type, expected) {
+            (t, e) if t == e => true,
+            (
+                Type::Pointer {
+                    target,
+                    nullable: false,
+                },
+                Type::Pointer {
+                    target: expected_target,
+                    nullable: true,
+                },
+            ) => target == expected_target,
+            _ => self.is_literal_of(exp_index, expected),
+        }
+    }
+
+    // Numeric literals have no fixed size, they can be used wherever a number of a compatible
+    // kind is expected, as long as integers are in the range of the expected type. So can the
+    // arithmetic on them. null can be used wherever a pointer is.
+    fn is_literal_of(&self, exp_index: usize, expected: &Type) -> bool {
+        match &self.file_ast.nodes[exp_index].kind {
+            AstNodeKind::Expression(Expression::Literal(lit)) => {
+                match (&lit.value.llc_type, expected) {
+                    (Type::Integer { .. }, Type::Integer { .. }) => {
+                        self.range_error(exp_index, expected).is_none()
+                    }
+                    (Type::Integer { .. }, Type::Float { .. })
+                    | (Type::Float { .. }, Type::Float { .. })
+                    | (Type::Null, Type::Pointer { nullable: true, .. }) => true,
+                    _ => false,
+                }
+            }
+            AstNodeKind::Expression(Expression::ArrayLiteral { elements }) => match expected {
+                Type::Array { element, size } => {
+                    elements.len() == *size
+                        && elements.iter().all(|e| self.is_literal_of(*e, element))
+                }
+                _ => false,
+            },
+            AstNodeKind::Expression(Expression::Unary {
+                operator: Operator::Minus,
+                operand,
+            }) => match expected {
+                Type::Integer { .. } => self.fits(exp_index, expected),
+                _ => self.is_literal_of(*operand, expected),
+            },
+            AstNodeKind::Expression(Expression::Binary {
+                operator:
+                    Operator::Plus
+                    | Operator::Minus
+                    | Operator::Times
+                    | Operator::Divide
+                    | Operator::Modulus,
+                lhs,
+                rhs,
+            }) => match expected {
+                Type::Integer { .. } => self.fits(exp_index, expected),
+                _ => self.is_literal_of(*lhs, expected) && self.is_literal_of(*rhs, expected),
+            },
+            AstNodeKind::Expression(Expression::If {
+                then_statements,
+                else_statements: Some(else_statements),
+                ..
+            }) => [then_statements, else_statements].iter().all(|branch| {
+                self.branch_value(branch)
+                    .is_some_and(|v| self.is_literal_of(v, expected))
+            }),
+            AstNodeKind::Expression(Expression::Match { arms, .. }) => {
+                arms.iter()
+                    .all(|arm| match &self.file_ast.nodes[*arm].kind {
+                        AstNodeKind::Statement(Statement::MatchArm { statements, .. }) => self
+                            .branch_value(statements)
+                            .is_some_and(|v| self.is_literal_of(v, expected)),
+                        _ => panic!("Expected a match arm"),
+                    })
+            }
+            _ => false,
+        }
+    }
+
+    // Like is_convertible, the float literals of a value converted to a float type take that
+    // type, so that they are written in C as a float or a double.
+    fn convert(&mut self, exp_index: usize, value_type: &Type, expected: &Type) -> bool {
+        let convertible = self.is_convertible(exp_index, value_type, expected);
+        if convertible {
+            self.convert_literals(exp_index, expected);
+        }
+        convertible
+    }
+
+    fn convert_literals(&mut self, exp_index: usize, expected: &Type) {
+        let values: Vec<(usize, Type)> = match &mut self.file_ast.nodes[exp_index].kind {
+            AstNodeKind::Expression(Expression::Literal(lit)) => {
+                if matches!(
+                    (&lit.value.llc_type, expected),
+                    (Type::Float { .. }, Type::Float { .. })
+                ) {
+                    lit.value.llc_type = expected.clone();
+                }
+                return;
+            }
+            AstNodeKind::Expression(Expression::Unary {
+                operator: Operator::Minus,
+                operand,
+            }) => vec![(*operand, expected.clone())],
+            AstNodeKind::Expression(Expression::Binary {
+                operator:
+                    Operator::Plus
+                    | Operator::Minus
+                    | Operator::Times
+                    | Operator::Divide
+                    | Operator::Modulus,
+                lhs,
+                rhs,
+            }) => vec![(*lhs, expected.clone()), (*rhs, expected.clone())],
+            AstNodeKind::Expression(Expression::ArrayLiteral { elements }) => match expected {
+                Type::Array { element, .. } => {
+                    elements.iter().map(|e| (*e, (**element).clone())).collect()
+                }
+                _ => return,
+            },
+            AstNodeKind::Expression(Expression::If {
+                then_statements,
+                else_statements: Some(else_statements),
+                ..
+            }) => [then_statements.clone(), else_statements.clone()]
+                .iter()
+                .filter_map(|branch| self.branch_value(branch))
+                .map(|v| (v, expected.clone()))
+                .collect(),
+            AstNodeKind::Expression(Expression::Match { arms, .. }) => arms
+                .clone()
+                .iter()
+                .filter_map(|arm| match &self.file_ast.nodes[*arm].kind {
+                    AstNodeKind::Statement(Statement::MatchArm { statements, .. }) => {
+                        self.branch_value(statements)
+                    }
+                    _ => None,
+                })
+                .map(|v| (v, expected.clone()))
+                .collect(),
+            _ => return,
+        };
+
+        for (value, expected) in values {
+            self.convert_literals(value, &expected);
+        }
+    }
+
+    // First type of the values all the others can be converted to.
+    fn common_type(&mut self, values: &[(usize, Type)]) -> Option<Type> {
+        let candidate = values
+            .iter()
+            .map(|(_, candidate)| candidate)
+            .find(|candidate| {
+                values
+                    .iter()
+                    .all(|(v, t)| self.is_convertible(*v, t, candidate))
+            })?
+            .clone();
+
+        for (v, t) in values {
+            self.convert(*v, t, &candidate);
+        }
+        Some(candidate)
+    }
+
+    // Whether the expression is a constant integer in the range of the expected type.
+    fn fits(&self, exp_index: usize, expected: &Type) -> bool {
+        self.c_constant(exp_index).is_some() && self.range_error(exp_index, expected).is_none()
+    }
+
+    fn check_binary(
+        &mut self,
+        index: usize,
+        operator: Operator,
+        lhs: usize,
+        rhs: usize,
+    ) -> ExpressionType {
+        let lhs_type = self.check_expression(lhs);
+        let rhs_type = self.check_expression(rhs);
+        let position = self.file_ast.nodes[index].position;
+
+        let (lhs_type, rhs_type) = match (lhs_type, rhs_type) {
+            (ExpressionType::Value(l), ExpressionType::Value(r)) => (l, r),
+            (ExpressionType::Invalid, _) | (_, ExpressionType::Invalid) => {
+                return ExpressionType::Invalid
+            }
+            _ => {
+                self.push_error(TypeError::new(
+                    position,
+                    &format!(
+                        "Operator {} expects a value on both sides.",
+                        operator.symbol()
+                    ),
+                ));
+                return ExpressionType::Invalid;
+            }
+        };
+
+        let operand_type = if self.convert(rhs, &rhs_type, &lhs_type) {
+            lhs_type
+        } else if self.convert(lhs, &lhs_type, &rhs_type) {
+            rhs_type
+        } else {
+            self.push_error(TypeError::new(
+                position,
+                &format!(
+                    "Operator {} cannot be applied to values of type {} and {}.",
+                    operator.symbol(),
+                    lhs_type,
+                    rhs_type
+                ),
+            ));
+            return ExpressionType::Invalid;
+        };
+
+        let allowed = match operator {
+            Operator::Modulus => matches!(operand_type, Type::Integer { .. }),
+            Operator::Equality | Operator::NotEqual => !matches!(
+                operand_type,
+                Type::String
+                    | Type::Block { .. }
+                    | Type::Struct { .. }
+                    | Type::Enum { .. }
+                    | Type::Array { .. }
+                    | Type::Slice { .. }
+            ),
+            Operator::And | Operator::Or => operand_type == Type::Bool,
+            _ => matches!(operand_type, Type::Integer { .. } | Type::Float { .. }),
+        };
+
+        if !allowed {
+            self.push_error(TypeError::new(
+                position,
+                &format!(
+                    "Operator {} cannot be applied to values of type {}.",
+                    operator.symbol(),
+                    operand_type
+                ),
+            ));
+            return ExpressionType::Invalid;
+        }
+
+        // C computes the arithmetic on constants while compiling, it cannot overflow.
+        let arithmetic = matches!(
+            operator,
+            Operator::Plus
+                | Operator::Minus
+                | Operator::Times
+                | Operator::Divide
+                | Operator::Modulus
+        );
+        if arithmetic
+            && self.c_constant(lhs).is_some()
+            && self.c_constant(rhs).is_some()
+            && self.c_constant(index).is_none()
+        {
+            self.push_error(TypeError::new(
+                position,
+                "Constant expression overflows its C type or divides by zero.",
+            ));
+            return ExpressionType::Invalid;
+        }
+
+        match operator {
+            Operator::Equality
+            | Operator::NotEqual
+            | Operator::GreaterThan
+            | Operator::LessThan
+            | Operator::GreaterOrEqual
+            | Operator::LessOrEqual
+            | Operator::And
+            | Operator::Or => ExpressionType::Value(Type::Bool),
+            _ => ExpressionType::Value(operand_type),
+        }
+    }
+
+    fn check_unary(&mut self, index: usize, operator: Operator, operand: usize) -> ExpressionType {
+        // A reference to a variable may be how it gets its value, like from a C function.
+        if let (Operator::Ampersand, Some(symbol)) = (operator, self.lookup(operand)) {
+            self.uninitialized.retain(|s| *s != symbol);
+        }
+
+        let operand_type = match self.check_expression(operand) {
+            ExpressionType::Value(t) => t,
+            ExpressionType::Invalid => return ExpressionType::Invalid,
+            ExpressionType::NoValue => {
+                let position = self.file_ast.nodes[index].position;
+                self.push_error(TypeError::new(
+                    position,
+                    &format!("Operator {} expects a value.", operator.symbol()),
+                ));
+                return ExpressionType::Invalid;
+            }
+        };
+
+        match (operator, operand_type) {
+            (Operator::Ampersand, _) if !self.is_variable_field(operand) => {
+                let position = self.file_ast.nodes[index].position;
+                self.push_error(TypeError::new(
+                    position,
+                    "Only variables, their fields and dereferenced pointers can be referenced.",
+                ));
+                ExpressionType::Invalid
+            }
+            (Operator::Ampersand, t) => ExpressionType::Value(Type::Pointer {
+                target: Box::new(t),
+                nullable: false,
+            }),
+            (
+                Operator::Times,
+                Type::Pointer {
+                    target,
+                    nullable: false,
+                },
+            ) => ExpressionType::Value(*target),
+            (Operator::Times, t @ (Type::Pointer { .. } | Type::Null)) => {
+                let position = self.file_ast.nodes[index].position;
+                self.push_null_error(position, &t);
+                ExpressionType::Invalid
+            }
+            (Operator::Not, Type::Bool) => ExpressionType::Value(Type::Bool),
+            (Operator::Minus, t @ (Type::Integer { signed: true, .. } | Type::Float { .. })) => {
+                ExpressionType::Value(t)
+            }
+            (_, operand_type) => {
+                let position = self.file_ast.nodes[index].position;
+                self.push_error(TypeError::new(
+                    position,
+                    &format!(
+                        "Operator {} cannot be applied to a value of type {}.",
+                        operator.symbol(),
+                        operand_type
+                    ),
+                ));
+                ExpressionType::Invalid
+            }
+        }
+    }
+
+    fn check_condition(&mut self, condition: usize) {
+        let reason = match self.check_expression(condition) {
+            ExpressionType::Value(Type::Bool) | ExpressionType::Invalid => return,
+            ExpressionType::Value(t) => format!("Condition must be a bool but found {}.", t),
+            ExpressionType::NoValue => String::from("Condition does not produce a value."),
+        };
+
+        let position = self.file_ast.nodes[condition].position;
+        self.push_error(TypeError::new(position, &reason));
+    }
+
+    // The body may not run, the variables it initializes are not initialized after the loop.
+    fn check_loop_body(&mut self, statements: &[usize]) {
+        let uninitialized = self.uninitialized.clone();
+        self.scopes.push(Scope::new(false));
+        if let Some(block) = self.blocks.last_mut() {
+            block.loops += 1;
+        }
+
+        for s in statements {
+            self.check_statement(*s);
+        }
+
+        if let Some(block) = self.blocks.last_mut() {
+            block.loops -= 1;
+        }
+        self.scopes.pop();
+        self.uninitialized = uninitialized;
+    }
+
+    // A variable is initialized after branches when every branch reaching their end, which
+    // does not always return, initializes it.
+    fn join_branches(&mut self, before: Vec<usize>, branches: Vec<(Vec<usize>, Vec<usize>)>) {
+        let reaching: Vec<Vec<usize>> = branches
+            .into_iter()
+            .filter(|(statements, _)| !self.always_returns(statements))
+            .map(|(_, uninitialized)| uninitialized)
+            .collect();
+
+        self.uninitialized = match reaching.is_empty() {
+            true => before,
+            false => before
+                .into_iter()
+                .filter(|s| reaching.iter().any(|r| r.contains(s)))
+                .collect(),
+        };
+    }
+
+    fn check_for(&mut self, index: usize) {
+        let (ident_index, start, end, statements) = match &self.file_ast.nodes[index].kind {
+            AstNodeKind::Statement(Statement::For {
+                ident_index,
+                start,
+                end,
+                statements,
+            }) => (*ident_index, *start, *end, statements.clone()),
+            _ => panic!("Expected a for loop"),
+        };
+
+        let start_type = self.check_expression(start);
+        let end_type = self.check_expression(end);
+
+        let range_type = match (start_type, end_type) {
+            (ExpressionType::Invalid, _) | (_, ExpressionType::Invalid) => TypeState::Invalid,
+            (
+                ExpressionType::Value(s @ Type::Integer { .. }),
+                ExpressionType::Value(e @ Type::Integer { .. }),
+            ) => {
+                if s == e || self.is_literal_of(end, &s) {
+                    TypeState::Ok(s)
+                } else if self.is_literal_of(start, &e) {
+                    TypeState::Ok(e)
+                } else {
+                    let position = self.file_ast.nodes[index].position;
+                    self.push_error(TypeError::new(
+                        position,
+                        &format!("Range bounds have different types: {} and {}.", s, e),
+                    ));
+                    TypeState::Invalid
+                }
+            }
+            _ => {
+                let position = self.file_ast.nodes[index].position;
+                self.push_error(TypeError::new(position, "Range bounds must be integers."));
+                TypeState::Invalid
+            }
+        };
+
+        // The loop variable lives in a scope around the body, so the body can shadow it.
+        self.scopes.push(Scope::new(false));
+        if let Some(symbol) = self.declare(ident_index, index) {
+            self.file_ast.identifiers[symbol].type_state = range_type;
+        }
+
+        self.check_loop_body(&statements);
+        self.scopes.pop();
+    }
+
+    // The value of a branch is its last statement, when that statement is an expression.
+    fn branch_value(&self, statements: &[usize]) -> Option<usize> {
+        let last = *statements.last()?;
+        match self.file_ast.nodes[last].kind {
+            AstNodeKind::Expression(_) => Some(last),
+            AstNodeKind::Statement(_) => None,
+        }
+    }
+
+    // Branches only have to agree on their type when the if expression is used as a value.
+    fn check_if(&mut self, index: usize, as_value: bool) -> ExpressionType {
+        let (condition, then_statements, else_statements) = match &self.file_ast.nodes[index].kind {
+            AstNodeKind::Expression(Expression::If {
+                condition,
+                then_statements,
+                else_statements,
+            }) => (*condition, then_statements.clone(), else_statements.clone()),
+            _ => panic!("Expected an if expression"),
+        };
+
+        if as_value {
+            self.check_branching_value(index, "If expressions with statements in their branches");
+        }
+        self.check_condition(condition);
+        let (then_narrowed, else_narrowed) = match self.null_check(condition) {
+            Some((symbol, true)) => (Some(symbol), None),
+            Some((symbol, false)) => (None, Some(symbol)),
+            None => (None, None),
+        };
+
+        let before = self.uninitialized.clone();
+        let then_type = self.check_narrowed_branch(&then_statements, as_value, then_narrowed);
+        let then_uninitialized = std::mem::replace(&mut self.uninitialized, before.clone());
+        let else_statements = match else_statements {
+            Some(s) => s,
+            None if as_value => {
+                let position = self.file_ast.nodes[index].position;
+                self.push_error(TypeError::new(
+                    position,
+                    "If expression must have an else branch to be used as a value.",
+                ));
+                return ExpressionType::Invalid;
+            }
+            None => return ExpressionType::NoValue,
+        };
+
+        let else_type = self.check_narrowed_branch(&else_statements, as_value, else_narrowed);
+        let else_uninitialized = std::mem::take(&mut self.uninitialized);
+        self.join_branches(
+            before,
+            vec![
+                (then_statements.clone(), then_uninitialized),
+                (else_statements.clone(), else_uninitialized),
+            ],
+        );
+        if !as_value {
+            return ExpressionType::NoValue;
+        }
+
+        let branches = vec![
+            (self.branch_value(&then_statements), then_type),
+            (self.branch_value(&else_statements), else_type),
+        ];
+        self.unify_branches(index, "if expression", branches)
+    }
+
+    // If expressions whose branches hold more than a value and match expressions can only be
+    // values which are declared, assigned or returned, and so can the values of their branches.
+    fn check_branching_value(&mut self, index: usize, kind: &str) {
+        if !self.branching_values.contains(&index) {
+            if self.is_branching(index) {
+                let position = self.file_ast.nodes[index].position;
+                self.push_error(TypeError::new(
+                    position,
+                    &format!("{} can only be declared, assigned or returned.", kind),
+                ));
+            }
+            return;
+        }
+
+        let branches: Vec<Vec<usize>> = match &self.file_ast.nodes[index].kind {
+            AstNodeKind::Expression(Expression::If {
+                then_statements,
+                else_statements,
+                ..
+            }) => [Some(then_statements), else_statements.as_ref()]
+                .into_iter()
+                .flatten()
+                .cloned()
+                .collect(),
+            AstNodeKind::Expression(Expression::Match { arms, .. }) => arms
+                .iter()
+                .map(|a| match &self.file_ast.nodes[*a].kind {
+                    AstNodeKind::Statement(Statement::MatchArm { statements, .. }) => {
+                        statements.clone()
+                    }
+                    _ => panic!("Expected a match arm"),
+                })
+                .collect(),
+            _ => panic!("Expected an if or match expression"),
+        };
+
+        let values: Vec<usize> = branches
+            .iter()
+            .filter_map(|b| self.branch_value(b))
+            .collect();
+        self.branching_values.extend(values);
+    }
+
+    fn is_branching(&self, index: usize) -> bool {
+        match &self.file_ast.nodes[index].kind {
+            AstNodeKind::Expression(Expression::Match { .. }) => true,
+            AstNodeKind::Expression(Expression::If {
+                then_statements,
+                else_statements: Some(else_statements),
+                ..
+            }) => [then_statements, else_statements]
+                .iter()
+                .any(|branch| match branch.as_slice() {
+                    [value] => match self.file_ast.nodes[*value].kind {
+                        AstNodeKind::Expression(_) => self.is_branching(*value),
+                        AstNodeKind::Statement(_) => true,
+                    },
+                    _ => true,
+                }),
+            _ => false,
+        }
+    }
+
+    // Conditions comparing a pointer variable to null, p != null or null == p, tell in which
+    // branch the pointer is not null. Returns the symbol and whether it is the then branch.
+    fn null_check(&self, condition: usize) -> Option<(usize, bool)> {
+        let (operator, lhs, rhs) = match self.file_ast.nodes[condition].kind {
+            AstNodeKind::Expression(Expression::Binary { operator, lhs, rhs }) => {
+                (operator, lhs, rhs)
+            }
+            _ => return None,
+        };
+
+        let is_null = |index: usize| {
+            matches!(
+                &self.file_ast.nodes[index].kind,
+                AstNodeKind::Expression(Expression::Literal(lit)) if lit.value.llc_type == Type::Null
+            )
+        };
+
+        let pointer = match (is_null(lhs), is_null(rhs)) {
+            (false, true) => lhs,
+            (true, false) => rhs,
+            _ => return None,
+        };
+
+        let symbol = match self.file_ast.nodes[pointer].kind {
+            AstNodeKind::Expression(Expression::Identifier { index }) => index,
+            _ => return None,
+        };
+
+        match operator {
+            Operator::NotEqual => Some((symbol, true)),
+            Operator::Equality => Some((symbol, false)),
+            _ => None,
+        }
+    }
+
+    // Where a pointer is not null, its name is bound to a reference in a new scope. Both are
+    // the same variable in C. Returns whether the scope was pushed.
+    fn narrow(&mut self, narrowed: Option<usize>) -> bool {
+        let target = narrowed.and_then(|s| match &self.file_ast.identifiers[s].type_state {
+            TypeState::Ok(Type::Pointer {
+                target,
+                nullable: true,
+            }) => Some((s, target.clone())),
+            _ => None,
+        });
+
+        let (symbol, target) = match target {
+            Some(t) => t,
+            None => return false,
+        };
+
+        let original = &self.file_ast.identifiers[symbol];
+        let mut reference = Identifier::new(&original.name);
+        reference.declaration_idx = original.declaration_idx;
+        reference.type_state = TypeState::Ok(Type::Pointer {
+            target,
+            nullable: false,
+        });
+
+        let name = reference.name.clone();
+        self.file_ast.identifiers.push(reference);
+        let reference = self.file_ast.identifiers.len() - 1;
+        self.narrowings.insert(reference, symbol);
+
+        let mut scope = Scope::new(false);
+        scope.symbols.insert(name, reference);
+        self.scopes.push(scope);
+        true
+    }
+
+    fn check_narrowed_branch(
+        &mut self,
+        statements: &[usize],
+        as_value: bool,
+        narrowed: Option<usize>,
+    ) -> ExpressionType {
+        let narrowing = self.narrow(narrowed);
+        let branch_type = self.check_branch(statements, as_value);
+        if narrowing {
+            self.scopes.pop();
+        }
+        branch_type
+    }
+
+    // A narrowed pointer may be null again once it is assigned, its name is bound back to the
+    // pointer for the rest of the scope.
+    fn end_narrowing(&mut self, symbol: usize) -> usize {
+        let pointer = match self.narrowings.get(&symbol) {
+            Some(p) => *p,
+            None => return symbol,
+        };
+
+        let name = self.file_ast.identifiers[symbol].name.clone();
+        if let Some(scope) = self
+            .scopes
+            .iter_mut()
+            .rev()
+            .find(|scope| scope.symbols.get(&name) == Some(&symbol))
+        {
+            scope.symbols.insert(name, pointer);
+        }
+        pointer
+    }
+
+    fn push_null_error(&mut self, position: FileSpan, llc_type: &Type) {
+        self.push_error(TypeError::new(
+            position,
+            &format!(
+                "Value of type {} may be null, compare it to null before dereferencing it.",
+                llc_type
+            ),
+        ));
+    }
+
+    // The branches of an expression used as a value must all produce a value of the same
+    // type, numeric literals take the type of the other branches.
+    fn unify_branches(
+        &mut self,
+        index: usize,
+        expression: &str,
+        branches: Vec<(Option<usize>, ExpressionType)>,
+    ) -> ExpressionType {
+        if branches
+            .iter()
+            .any(|(_, t)| matches!(t, ExpressionType::Invalid))
+        {
+            return ExpressionType::Invalid;
+        }
+
+        if branches
+            .iter()
+            .all(|(_, t)| matches!(t, ExpressionType::NoValue))
+        {
+            return ExpressionType::NoValue;
+        }
+
+        let position = self.file_ast.nodes[index].position;
+        let mut values = vec![];
+        for (value, branch_type) in branches {
+            match (value, branch_type) {
+                (Some(v), ExpressionType::Value(t)) => values.push((v, t)),
+                _ => {
+                    self.push_error(TypeError::new(
+                        position,
+                        &format!("Only some branches of the {} produce a value.", expression),
+                    ));
+                    return ExpressionType::Invalid;
+                }
+            }
+        }
+
+        if let Some(candidate) = self.common_type(&values) {
+            return ExpressionType::Value(candidate);
+        }
+
+        let first = &values[0].1;
+        let other = values.iter().map(|(_, t)| t).find(|t| *t != first).unwrap();
+        self.push_error(TypeError::new(
+            position,
+            &format!(
+                "Branches of the {} have different types: {} and {}.",
+                expression, first, other
+            ),
+        ));
+        ExpressionType::Invalid
+    }
+
+    // Branches open a scope but not a block, so they can use the locals around them.
+    fn check_branch(&mut self, statements: &[usize], as_value: bool) -> ExpressionType {
+        let value = match as_value {
+            true => self.branch_value(statements),
+            false => None,
+        };
+
+        self.scopes.push(Scope::new(false));
+
+        let mut value_type = ExpressionType::NoValue;
+        for s in statements {
+            match value {
+                Some(v) if v == *s => value_type = self.check_expression(v),
+                _ => self.check_statement(*s),
+            }
+        }
+
+        self.scopes.pop();
+        value_type
+    }
+
+    fn check_call(&mut self, index: usize) -> ExpressionType {
+        let (callee, args) = match &self.file_ast.nodes[index].kind {
+            AstNodeKind::Expression(Expression::FunctionCall { callee, args }) => {
+                (*callee, args.clone())
+            }
+            _ => panic!("Expected a function call"),
+        };
+
+        let callee_type = self.check_expression(callee);
+        let arg_types: Vec<ExpressionType> =
+            args.iter().map(|a| self.check_expression(*a)).collect();
+
+        // Blocks can be called through a reference, which is a function pointer in C.
+        let callee_type = match callee_type {
+            ExpressionType::Value(Type::Pointer {
+                target,
+                nullable: false,
+            }) if matches!(*target, Type::Block { .. }) => ExpressionType::Value(*target),
+            t => t,
+        };
+
+        let (input, output, variadic) = match callee_type {
+            ExpressionType::Value(Type::Block {
+                input,
+                output,
+                variadic,
+            }) => (input.unwrap_or_default(), output, variadic),
+            ExpressionType::Value(t @ (Type::Pointer { nullable: true, .. } | Type::Null)) => {
+                let position = self.file_ast.nodes[callee].position;
+                self.push_null_error(position, &t);
+                return ExpressionType::Invalid;
+            }
+            ExpressionType::Value(t) => {
+                let position = self.file_ast.nodes[callee].position;
+                self.push_error(TypeError::new(
+                    position,
+                    &format!("Value of type {} is not a block and cannot be called.", t),
+                ));
+                return ExpressionType::Invalid;
+            }
+            _ => return ExpressionType::Invalid,
+        };
+
+        if input.len() > args.len() || (input.len() < args.len() && !variadic) {
+            let position = self.file_ast.nodes[index].position;
+            self.push_error(
+                TypeError::new(
+                    position,
+                    &format!(
+                        "Block expects {}{} argument(s) but {} were given.",
+                        match variadic {
+                            true => "at least ",
+                            false => "",
+                        },
+                        input.len(),
+                        args.len()
+                    ),
+                )
+                .with_code(diagnostic::ARITY_MISMATCH),
+            );
+        }
+
+        // The values after the parameters of a variadic block are passed to C as they are.
+        for (arg, arg_type) in args.iter().zip(arg_types.iter()).skip(input.len()) {
+            let reason = match arg_type {
+                ExpressionType::Value(
+                    t @ (Type::Block { .. } | Type::Array { .. } | Type::Null),
+                ) => {
+                    format!("Values of type {} cannot be passed to a variadic block.", t)
+                }
+                ExpressionType::NoValue => {
+                    String::from("Expected an argument but the expression has no value.")
+                }
+                _ => continue,
+            };
+
+            let position = self.file_ast.nodes[*arg].position;
+            self.push_error(TypeError::new(position, &reason));
+        }
+
+        for ((arg, arg_type), expected) in args.iter().zip(arg_types).zip(input.iter()) {
+            let reason = match arg_type {
+                ExpressionType::Value(t) if self.convert(*arg, &t, expected) => continue,
+                ExpressionType::Invalid => continue,
+                ExpressionType::Value(t) => self.range_error(*arg, expected).unwrap_or(format!(
+                    "Expected an argument of type {} but found {}.",
+                    expected, t
+                )),
+                ExpressionType::NoValue => format!(
+                    "Expected an argument of type {} but the expression has no value.",
+                    expected
+                ),
+            };
+
+            let position = self.file_ast.nodes[*arg].position;
+            self.push_error(
+                TypeError::new(position, &reason).with_code(diagnostic::MISMATCHED_TYPES),
+            );
+        }
+
+        match output {
+            Some(t) => ExpressionType::Value(*t),
+            None => ExpressionType::NoValue,
+        }
+    }
+
+    fn check_identifier(&mut self, ident_node: usize) -> ExpressionType {
+        let symbol = match self.resolve(ident_node) {
+            Some(s) => s,
+            None => return ExpressionType::Invalid,
+        };
+
+        let user_type = match (self.is_struct_symbol(symbol), self.is_enum_symbol(symbol)) {
+            (true, _) => Some("a struct"),
+            (_, true) => Some("an enum"),
+            _ if self.modules.contains_key(&symbol) => Some("a module"),
+            _ => None,
+        };
+
+        if let Some(user_type) = user_type {
+            let position = self.file_ast.nodes[ident_node].position;
+            let name = self.file_ast.identifiers[symbol].name.clone();
+            self.push_error(TypeError::new(
+                position,
+                &format!("{} is {} and cannot be used as a value.", name, user_type),
+            ));
+            return ExpressionType::Invalid;
+        }
+
+        if self.file_ast.identifiers[symbol].type_state == TypeState::Unchecked {
+            let decl_index = self.file_ast.identifiers[symbol].declaration_idx;
+            self.check_root_declaration(decl_index);
+        }
+
+        // The variable is only reported once, as if the read had initialized it.
+        if let Some(i) = self.uninitialized.iter().position(|s| *s == symbol) {
+            self.uninitialized.remove(i);
+            let position = self.file_ast.nodes[ident_node].position;
+            let declaration = self.file_ast.identifiers[symbol].declaration_idx;
+            let declaration_position = self.file_ast.nodes[declaration].position;
+            let name = self.file_ast.identifiers[symbol].name.clone();
+            self.push_error(
+                TypeError::new(
+                    position,
+                    &format!("Variable {} is read before it is initialized.", name),
+                )
+                .with_label(declaration_position, "declared here without a value"),
+            );
+        }
+
+        match &self.file_ast.identifiers[symbol].type_state {
+            TypeState::Ok(t) => ExpressionType::Value(t.clone()),
+            TypeState::Invalid => ExpressionType::Invalid,
+            TypeState::InProgress | TypeState::Unchecked => {
+                let position = self.file_ast.nodes[ident_node].position;
+                let name = self.file_ast.identifiers[symbol].name.clone();
+                self.push_error(TypeError::new(
+                    position,
+                    &format!(
+                        "Cyclic declaration, the type of {} depends on itself.",
+                        name
+                    ),
+                ));
+                self.file_ast.identifiers[symbol].type_state = TypeState::Invalid;
+                ExpressionType::Invalid
+            }
+        }
+    }
+
+    fn signature_type(&self, block_index: usize) -> Option<Type> {
+        let signature = match &self.file_ast.nodes[block_index].kind {
+            AstNodeKind::Expression(
+                Expression::Block {
+                    signature: Some(signature),
+                    ..
+                }
+                | Expression::Extern { signature, .. },
+            ) => signature,
+            _ => return None,
+        };
+
+        let input: Vec<Type> = signature
+            .params
+            .iter()
+            .map(|p| match &self.file_ast.nodes[*p].kind {
+                AstNodeKind::Statement(Statement::Parameter { llc_type, .. }) => {
+                    self.resolve_type(llc_type)
+                }
+                _ => panic!("Expected a parameter"),
+            })
+            .collect();
+
+        Some(Type::Block {
+            input: match input.is_empty() {
+                true => None,
+                false => Some(input),
+            },
+            output: signature
+                .output
+                .as_ref()
+                .map(|t| Box::new(self.resolve_type(t))),
+            variadic: signature.variadic,
+        })
+    }
+
+    fn return_value(&self, return_index: usize) -> Option<usize> {
+        match self.file_ast.nodes[return_index].kind {
+            AstNodeKind::Statement(Statement::Return { value }) => value,
+            _ => panic!("Expected a return statement"),
+        }
+    }
+
+    fn check_block(&mut self, index: usize) -> ExpressionType {
+        let (params, statements) = match &self.file_ast.nodes[index].kind {
+            AstNodeKind::Expression(Expression::Block {
+                signature,
+                statements,
+            }) => (
+                match signature {
+                    Some(s) => s.params.clone(),
+                    None => vec![],
+                },
+                statements.clone(),
+            ),
+            _ => panic!("Expected a block"),
+        };
+
+        self.scopes.push(Scope::new(true));
+        self.blocks.push(BlockContext {
+            returns: vec![],
+            loops: 0,
+        });
+
+        if let Some(Type::Block {
+            output: Some(output),
+            ..
+        }) = self.signature_type(index)
+        {
+            let position = self.file_ast.nodes[index].position;
+            self.check_type(&output, position);
+        }
+
+        for p in params.iter().chain(statements.iter()) {
+            self.check_statement(*p);
+        }
+
+        self.scopes.pop();
+        let block = self.blocks.pop().unwrap();
+
+        match self.signature_type(index) {
+            Some(t) => self.check_declared_returns(index, t, block.returns, &statements),
+            None => self.check_inferred_returns(index, block.returns, &statements),
+        }
+    }
+
+    // Whether the statements end with a return whatever the path taken. An if only does when
+    // both of its branches do and a match when every arm does, a loop may not run at all.
+    fn always_returns(&self, statements: &[usize]) -> bool {
+        statements
+            .iter()
+            .any(|s| match &self.file_ast.nodes[*s].kind {
+                AstNodeKind::Statement(Statement::Return { .. }) => true,
+                AstNodeKind::Expression(Expression::If {
+                    then_statements,
+                    else_statements: Some(else_statements),
+                    ..
+                }) => self.always_returns(then_statements) && self.always_returns(else_statements),
+                AstNodeKind::Expression(Expression::Match { arms, .. }) => {
+                    arms.iter().all(|a| match &self.file_ast.nodes[*a].kind {
+                        AstNodeKind::Statement(Statement::MatchArm { statements, .. }) => {
+                            self.always_returns(statements)
+                        }
+                        _ => panic!("Expected a match arm"),
+                    })
+                }
+                _ => false,
+            })
+    }
+
+    fn push_missing_return(&mut self, index: usize, output: &Type) {
+        let position = self.file_ast.nodes[index].position;
+        self.push_error(TypeError::new(
+            position,
+            &format!(
+                "Block must return a value of type {} on every path, some end without a return statement.",
+                output
+            ),
+        ));
+    }
+
+    fn check_declared_returns(
+        &mut self,
+        index: usize,
+        block_type: Type,
+        returns: Vec<(usize, ExpressionType)>,
+        statements: &[usize],
+    ) -> ExpressionType {
+        let output = match &block_type {
+            Type::Block { output, .. } => output.as_deref(),
+            _ => panic!("Expected a block type"),
+        };
+
+        match output {
+            Some(t) if returns.is_empty() => {
+                let position = self.file_ast.nodes[index].position;
+                self.push_error(TypeError::new(
+                    position,
+                    &format!("Block must return a value of type {}.", t),
+                ));
+            }
+            Some(t) if !self.always_returns(statements) => self.push_missing_return(index, t),
+            _ => {}
+        }
+
+        for (ret_index, exp_type) in returns {
+            let mismatch = match (output, exp_type) {
+                (_, ExpressionType::Invalid) | (None, ExpressionType::NoValue) => None,
+                (Some(e), ExpressionType::Value(f)) => {
+                    let value = self.return_value(ret_index).unwrap();
+                    match self.convert(value, &f, e) {
+                        true => None,
+                        false => Some(self.range_error(value, e).unwrap_or(format!(
+                            "Expected a return value of type {} but found {}.",
+                            e, f
+                        ))),
+                    }
+                }
+                (Some(e), ExpressionType::NoValue) => {
+                    Some(format!("Expected a return value of type {}.", e))
+                }
+                (None, ExpressionType::Value(_)) => Some(String::from(
+                    "Block has no return type, expected a return statement without value.",
+                )),
+            };
+
+            if let Some(reason) = mismatch {
+                let position = self.file_ast.nodes[ret_index].position;
+                self.push_error(
+                    TypeError::new(position, &reason).with_code(diagnostic::MISMATCHED_TYPES),
+                );
+            }
+        }
+
+        ExpressionType::Value(block_type)
+    }
+
+    fn check_inferred_returns(
+        &mut self,
+        index: usize,
+        returns: Vec<(usize, ExpressionType)>,
+        statements: &[usize],
+    ) -> ExpressionType {
+        let mut output: Option<(usize, ExpressionType)> = None;
+        for (ret_index, exp_type) in returns {
+            let expected = match &output {
+                None => {
+                    output = Some((ret_index, exp_type));
+                    continue;
+                }
+                Some((_, expected)) => expected,
+            };
+
+            let mismatch = match (expected, &exp_type) {
+                (ExpressionType::Invalid, _) | (_, ExpressionType::Invalid) => None,
+                (ExpressionType::NoValue, ExpressionType::NoValue) => None,
+                (ExpressionType::Value(e), ExpressionType::Value(f)) if e == f => None,
+                (ExpressionType::Value(e), ExpressionType::Value(f)) => Some(format!(
+                    "Expected a return value of type {} but found {}.",
+                    e, f
+                )),
+                (ExpressionType::Value(e), ExpressionType::NoValue) => {
+                    Some(format!("Expected a return value of type {}.", e))
+                }
+                (ExpressionType::NoValue, ExpressionType::Value(_)) => {
+                    Some(String::from("Expected a return statement without value."))
+                }
+            };
+
+            if let Some(reason) = mismatch {
+                let position = self.file_ast.nodes[ret_index].position;
+                self.push_error(
+                    TypeError::new(position, &reason).with_code(diagnostic::MISMATCHED_TYPES),
+                );
+            }
+        }
+
+        if let Some((_, ExpressionType::Value(t))) = &output {
+            if !self.always_returns(statements) {
+                self.push_missing_return(index, t);
+            }
+        }
+
+        match output {
+            Some((_, ExpressionType::Invalid)) => ExpressionType::Invalid,
+            Some((_, ExpressionType::Value(t))) => ExpressionType::Value(Type::Block {
+                input: None,
+                output: Some(Box::new(t)),
+                variadic: false,
+            }),
+            _ => ExpressionType::Value(Type::Block {
+                input: None,
+                output: None,
+                variadic: false,
+            }),
+        }
+    }
+
+    fn is_struct_definition(&self, index: usize) -> bool {
+        matches!(
+            self.file_ast.nodes[index].kind,
+            AstNodeKind::Expression(Expression::StructDefinition { .. })
+        )
+    }
+
+    fn is_enum_definition(&self, index: usize) -> bool {
+        matches!(
+            self.file_ast.nodes[index].kind,
+            AstNodeKind::Expression(Expression::EnumDefinition { .. })
+        )
+    }
+
+    fn is_extern(&self, index: usize) -> bool {
+        matches!(
+            self.file_ast.nodes[index].kind,
+            AstNodeKind::Expression(Expression::Extern { .. })
+        )
+    }
+
+    // Extern blocks are C functions, their signature is their type and they have no body.
+    fn check_extern(&mut self, symbol: usize, index: usize, annotation: Option<Type>) {
+        let extern_type = self
+            .signature_type(index)
+            .expect("Expected an extern signature");
+        let position = self.file_ast.nodes[index].position;
+
+        self.file_ast.identifiers[symbol].type_state = match self.check_type(&extern_type, position)
+        {
+            true => TypeState::Ok(extern_type.clone()),
+            false => TypeState::Invalid,
+        };
+
+        if annotation.is_some_and(|t| t != extern_type) {
+            self.push_error(
+                TypeError::new(
+                    position,
+                    &format!(
+                        "Declared type does not match the extern block of type {}.",
+                        extern_type
+                    ),
+                )
+                .with_code(diagnostic::MISMATCHED_TYPES),
+            );
+        }
+    }
+
+    fn is_struct_symbol(&self, symbol: usize) -> bool {
+        self.definition_of_symbol(symbol, &self.structs).is_some()
+            || matches!(self.imported.get(&symbol), Some(Export::Struct { .. }))
+    }
+
+    fn is_enum_symbol(&self, symbol: usize) -> bool {
+        self.definition_of_symbol(symbol, &self.enums).is_some()
+            || matches!(self.imported.get(&symbol), Some(Export::Enum { .. }))
+    }
+
+    // A name may be shadowed by a local, the symbol must be the declaration of the definition.
+    fn definition_of_symbol(
+        &self,
+        symbol: usize,
+        definitions: &HashMap<Box<str>, usize>,
+    ) -> Option<usize> {
+        let identifier = &self.file_ast.identifiers[symbol];
+        match definitions.get(&self.qualified(&identifier.name)) {
+            Some(definition) => match self.file_ast.nodes[identifier.declaration_idx].kind {
+                AstNodeKind::Statement(Statement::Declaration {
+                    exp_index: Some(e), ..
+                }) if e == *definition => Some(e),
+                _ => None,
+            },
+            None => None,
+        }
+    }
+
+    fn struct_fields(&self, name: &str) -> Vec<(Box<str>, Type)> {
+        let definition = match (self.structs.get(name), self.external_type(name)) {
+            (Some(d), _) => *d,
+            (None, Some(Export::Struct { fields })) => return fields.clone(),
+            _ => return vec![],
+        };
+
+        let fields = match &self.file_ast.nodes[definition].kind {
+            AstNodeKind::Expression(Expression::StructDefinition { fields }) => fields,
+            _ => panic!("Expected a struct definition"),
+        };
+
+        fields
+            .iter()
+            .map(|f| match &self.file_ast.nodes[*f].kind {
+                AstNodeKind::Statement(Statement::Field {
+                    ident_index,
+                    llc_type,
+                }) => (self.field_name(*ident_index), self.resolve_type(llc_type)),
+                _ => panic!("Expected a struct field"),
+            })
+            .collect()
+    }
+
+    // Field names are not symbols, they keep the identifier built by the lexer.
+    fn field_name(&self, ident_node: usize) -> Box<str> {
+        let index = self.identifier_index(ident_node);
+        self.file_ast.identifiers[index].name.clone()
+    }
+
+    // Types written in the source may name structs or enums, which have to be declared. The
+    // paths which name no type are reported by check_type_paths.
+    fn check_type(&mut self, llc_type: &Type, position: FileSpan) -> bool {
+        match llc_type {
+            Type::Struct { name } | Type::Enum { name }
+                if name.contains("::")
+                    && !self.structs.contains_key(name)
+                    && self.external_type(name).is_none() =>
+            {
+                false
+            }
+            Type::Struct { name } | Type::Enum { name }
+                if !self.structs.contains_key(name)
+                    && !self.enums.contains_key(name)
+                    && self.external_type(name).is_none() =>
+            {
+                self.push_error(
+                    TypeError::new(position, &format!("Unknown type {}.", name))
+                        .with_code(diagnostic::UNDECLARED_NAME),
+                );
+                false
+            }
+            Type::Pointer { target, .. }
+            | Type::Array {
+                element: target, ..
+            }
+            | Type::Slice { element: target } => self.check_type(target, position),
+            Type::Block { input, output, .. } => input
+                .iter()
+                .flatten()
+                .chain(output.as_deref())
+                .all(|t| self.check_type(t, position)),
+            _ => true,
+        }
+    }
+
+    fn check_struct_definition(&mut self, symbol: usize, index: usize) {
+        let name = self.file_ast.identifiers[symbol].name.clone();
+        let fields = match &self.file_ast.nodes[index].kind {
+            AstNodeKind::Expression(Expression::StructDefinition { fields }) => fields.clone(),
+            _ => panic!("Expected a struct definition"),
+        };
+
+        let mut names: Vec<Box<str>> = vec![];
+        for field in fields {
+            let (ident_index, llc_type) = match &self.file_ast.nodes[field].kind {
+                AstNodeKind::Statement(Statement::Field {
+                    ident_index,
+                    llc_type,
+                }) => (*ident_index, self.resolve_type(llc_type)),
+                _ => panic!("Expected a struct field"),
+            };
+
+            let position = self.file_ast.nodes[field].position;
+            let field_name = self.field_name(ident_index);
+            if names.contains(&field_name) {
+                self.push_error(
+                    TypeError::new(
+                        position,
+                        &format!(
+                            "Field {} is already declared in struct {}.",
+                            field_name, name
+                        ),
+                    )
+                    .with_code(diagnostic::REDECLARATION),
+                );
+            }
+            names.push(field_name);
+
+            if self.check_type(&llc_type, position)
+                && self.contains_type(&llc_type, &name, &mut vec![])
+            {
+                self.push_error(TypeError::new(
+                    position,
+                    &format!("Struct {} cannot contain itself.", name),
+                ));
+            }
+        }
+    }
+
+    // Whether a value of the given type holds a value of the named type, directly or not.
+    fn contains_type(&self, llc_type: &Type, name: &str, visited: &mut Vec<Box<str>>) -> bool {
+        let inner_name = match llc_type {
+            Type::Struct { name } | Type::Enum { name } => name,
+            Type::Array { element, .. } => return self.contains_type(element, name, visited),
+            _ => return false,
+        };
+
+        if **inner_name == *name {
+            return true;
+        }
+
+        if visited.contains(inner_name) {
+            return false;
+        }
+        visited.push(inner_name.clone());
+
+        let inner_types: Vec<Type> = match llc_type {
+            Type::Struct { .. } => self
+                .struct_fields(inner_name)
+                .into_iter()
+                .map(|(_, t)| t)
+                .collect(),
+            _ => self
+                .enum_variants(inner_name)
+                .into_iter()
+                .flat_map(|(_, payload)| payload)
+                .collect(),
+        };
+
+        inner_types
+            .iter()
+            .any(|t| self.contains_type(t, name, visited))
+    }
+
+    fn check_struct_literal(&mut self, index: usize) -> ExpressionType {
+        let (type_index, fields) = match &self.file_ast.nodes[index].kind {
+            AstNodeKind::Expression(Expression::StructLiteral { type_index, fields }) => {
+                (*type_index, fields.clone())
+            }
+            _ => panic!("Expected a struct literal"),
+        };
+
+        let name = match self.file_ast.nodes[type_index].kind {
+            AstNodeKind::Expression(Expression::Path { module, name }) => {
+                self.struct_path(module, name)
+            }
+            _ => self.struct_name(type_index),
+        };
+        let name = match name {
+            Some(n) => n,
+            None => return ExpressionType::Invalid,
+        };
+
+        let expected_fields = self.struct_fields(&name);
+        let mut initialized: Vec<Box<str>> = vec![];
+
+        for field in fields {
+            let (ident_index, exp_index) = match self.file_ast.nodes[field].kind {
+                AstNodeKind::Statement(Statement::FieldValue {
+                    ident_index,
+                    exp_index,
+                }) => (ident_index, exp_index),
+                _ => panic!("Expected a field value"),
+            };
+
+            let value_type = self.check_expression(exp_index);
+            let field_name = self.field_name(ident_index);
+            let position = self.file_ast.nodes[field].position;
+
+            let expected = match expected_fields.iter().find(|(n, _)| *n == field_name) {
+                Some((_, t)) => t,
+                None => {
+                    self.push_error(TypeError::new(
+                        position,
+                        &format!("Struct {} has no field {}.", name, field_name),
+                    ));
+                    continue;
+                }
+            };
+
+            if initialized.contains(&field_name) {
+                self.push_error(TypeError::new(
+                    position,
+                    &format!("Field {} is initialized more than once.", field_name),
+                ));
+            }
+            initialized.push(field_name.clone());
+
+            let reason = match value_type {
+                ExpressionType::Value(t) if self.convert(exp_index, &t, expected) => continue,
+                ExpressionType::Invalid => continue,
+                ExpressionType::Value(t) => format!(
+                    "Field {} expects a value of type {} but found {}.",
+                    field_name, expected, t
+                ),
+                ExpressionType::NoValue => format!(
+                    "Field {} expects a value of type {} but the expression has no value.",
+                    field_name, expected
+                ),
+            };
+
+            let position = self.file_ast.nodes[exp_index].position;
+            self.push_error(
+                TypeError::new(position, &reason).with_code(diagnostic::MISMATCHED_TYPES),
+            );
+        }
+
+        let missing: Vec<&str> = expected_fields
+            .iter()
+            .filter(|(n, _)| !initialized.contains(n))
+            .map(|(n, _)| &**n)
+            .collect();
+
+        if !missing.is_empty() {
+            let position = self.file_ast.nodes[index].position;
+            self.push_error(TypeError::new(
+                position,
+                &format!("Missing fields {} in {} literal.", missing.join(", "), name),
+            ));
+        }
+
+        ExpressionType::Value(Type::Struct { name })
+    }
+
+    // Name of the struct a struct literal starts with, Name or module::Name.
+    fn struct_name(&mut self, type_index: usize) -> Option<Box<str>> {
+        let symbol = self.resolve(type_index)?;
+        let name = self.file_ast.identifiers[symbol].name.clone();
+        if self.is_struct_symbol(symbol) {
+            return Some(self.type_name(symbol));
+        }
+
+        let position = self.file_ast.nodes[type_index].position;
+        self.push_error(TypeError::new(
+            position,
+            &format!("{} is not a struct.", name),
+        ));
+        None
+    }
+
+    fn struct_path(&mut self, module: usize, name: usize) -> Option<Box<str>> {
+        let (module_name, declaration, export) = self.path_export(module, name)?;
+        if let Export::Struct { .. } = export {
+            let exports = self.module_exports(module)?;
+            return Some(exports::qualify(&exports.module, &declaration));
+        }
+
+        let position = self.file_ast.nodes[name].position;
+        self.push_error(TypeError::new(
+            position,
+            &format!("{}::{} is not a struct.", module_name, declaration),
+        ));
+        None
+    }
+
+    fn check_field_access(&mut self, index: usize, object: usize, field: usize) -> ExpressionType {
+        let field_name = self.field_name(field);
+        let position = self.file_ast.nodes[field].position;
+
+        // Fields are reached through references, r.x is the field x of the struct r refers to.
+        let object_type = match self.check_expression(object) {
+            ExpressionType::Value(Type::Pointer {
+                target,
+                nullable: false,
+            }) if matches!(
+                *target,
+                Type::Struct { .. } | Type::Array { .. } | Type::Slice { .. }
+            ) =>
+            {
+                self.dereference_object(index, object);
+                ExpressionType::Value(*target)
+            }
+            t => t,
+        };
+
+        let name = match object_type {
+            ExpressionType::Value(Type::Struct { name }) => name,
+            // The length of an array is known, a.len is replaced by its value.
+            ExpressionType::Value(Type::Array { size, .. }) if *field_name == *"len" => {
+                let position = self.file_ast.nodes[index].position;
+                self.file_ast.nodes[index].kind =
+                    AstNodeKind::Expression(Expression::Literal(Literal {
+                        value: LiteralValue {
+                            llc_type: length_type(),
+                            value: Box::from(size.to_string()),
+                        },
+                        pos: position,
+                    }));
+                return ExpressionType::Value(length_type());
+            }
+            ExpressionType::Value(Type::Slice { .. }) if *field_name == *"len" => {
+                return ExpressionType::Value(length_type())
+            }
+            ExpressionType::Value(t @ (Type::Pointer { nullable: true, .. } | Type::Null)) => {
+                self.push_null_error(position, &t);
+                return ExpressionType::Invalid;
+            }
+            ExpressionType::Value(t) => {
+                self.push_error(TypeError::new(
+                    position,
+                    &format!("Value of type {} has no field {}.", t, field_name),
+                ));
+                return ExpressionType::Invalid;
+            }
+            ExpressionType::NoValue => {
+                self.push_error(TypeError::new(
+                    position,
+                    &format!("Expression has no value and no field {}.", field_name),
+                ));
+                return ExpressionType::Invalid;
+            }
+            ExpressionType::Invalid => return ExpressionType::Invalid,
+        };
+
+        match self
+            .struct_fields(&name)
+            .into_iter()
+            .find(|(n, _)| *n == field_name)
+        {
+            Some((_, t)) => ExpressionType::Value(t),
+            None => {
+                self.push_error(TypeError::new(
+                    position,
+                    &format!("Struct {} has no field {}.", name, field_name),
+                ));
+                ExpressionType::Invalid
+            }
+        }
+    }
+
+    // Makes the dereference of a reference explicit, r.x becomes (*r).x and r[i] (*r)[i].
+    fn dereference_object(&mut self, index: usize, object: usize) {
+        self.file_ast.nodes.push(AstNode {
+            kind: AstNodeKind::Expression(Expression::Unary {
+                operator: Operator::Times,
+                operand: object,
+            }),
+            parent: NodeParent::Unchecked,
+            position: self.file_ast.nodes[object].position,
+        });
+
+        let dereference = self.file_ast.nodes.len() - 1;
+        if let AstNodeKind::Expression(
+            Expression::FieldAccess { object, .. }
+            | Expression::Index { object, .. }
+            | Expression::Slicing { object, .. },
+        ) = &mut self.file_ast.nodes[index].kind
+        {
+            *object = dereference;
+        }
+    }
+
+    // Arrays cannot be empty, the type of their elements is the one every element converts to.
+    fn check_array_literal(&mut self, index: usize) -> ExpressionType {
+        let elements = match &self.file_ast.nodes[index].kind {
+            AstNodeKind::Expression(Expression::ArrayLiteral { elements }) => elements.clone(),
+            _ => panic!("Expected an array literal"),
+        };
+
+        let position = self.file_ast.nodes[index].position;
+        if elements.is_empty() {
+            self.push_error(TypeError::new(
+                position,
+                "Arrays need at least one element.",
+            ));
+            return ExpressionType::Invalid;
+        }
+
+        let mut values = vec![];
+        let mut valid = true;
+        for e in elements.iter() {
+            match self.check_expression(*e) {
+                ExpressionType::Value(t) => values.push((*e, t)),
+                ExpressionType::Invalid => valid = false,
+                ExpressionType::NoValue => {
+                    let position = self.file_ast.nodes[*e].position;
+                    self.push_error(TypeError::new(
+                        position,
+                        "Expression does not produce a value and cannot be an array element.",
+                    ));
+                    valid = false;
+                }
+            }
+        }
+
+        if !valid {
+            return ExpressionType::Invalid;
+        }
+
+        let size = values.len();
+        if let Some(candidate) = self.common_type(&values) {
+            return ExpressionType::Value(Type::Array {
+                element: Box::new(candidate),
+                size,
+            });
+        }
+
+        let first = &values[0].1;
+        let other = values.iter().map(|(_, t)| t).find(|t| *t != first).unwrap();
+        self.push_error(TypeError::new(
+            position,
+            &format!(
+                "Elements of the array have different types: {} and {}.",
+                first, other
+            ),
+        ));
+        ExpressionType::Invalid
+    }
+
+    fn check_index(&mut self, index: usize, object: usize, i: usize) -> ExpressionType {
+        let sequence = self.check_sequence(index, object);
+        let size = match &sequence {
+            Some(Type::Array { size, .. }) => Some(*size),
+            _ => None,
+        };
+        let valid_index = self.check_bound(i, size, size);
+
+        let sequence = match sequence {
+            Some(s) if valid_index => s,
+            _ => return ExpressionType::Invalid,
+        };
+
+        let element = match &sequence {
+            Type::Array { element, .. } | Type::Slice { element } => (**element).clone(),
+            _ => panic!("Expected an array or a slice"),
+        };
+
+        if let AstNodeKind::Expression(Expression::Index { sequence: s, .. }) =
+            &mut self.file_ast.nodes[index].kind
+        {
+            *s = Some(sequence);
+        }
+
+        ExpressionType::Value(element)
+    }
+
+    // Slices point to the elements of an array, which must be stored in a variable.
+    fn check_slicing(
+        &mut self,
+        index: usize,
+        object: usize,
+        start: Option<usize>,
+        end: Option<usize>,
+    ) -> ExpressionType {
+        let sequence = self.check_sequence(index, object);
+        let size = match &sequence {
+            Some(Type::Array { size, .. }) => Some(*size),
+            _ => None,
+        };
+
+        let mut valid = true;
+        for bound in start.iter().chain(end.iter()) {
+            valid &= self.check_bound(*bound, size, size.map(|s| s + 1));
+        }
+
+        let sequence = match sequence {
+            Some(s) if valid => s,
+            _ => return ExpressionType::Invalid,
+        };
+
+        // The object may have been replaced by its dereference.
+        let object = match self.file_ast.nodes[index].kind {
+            AstNodeKind::Expression(Expression::Slicing { object, .. }) => object,
+            _ => panic!("Expected a slicing"),
+        };
+
+        let element = match &sequence {
+            Type::Array { .. } if !self.is_variable_field(object) => {
+                let position = self.file_ast.nodes[object].position;
+                self.push_error(TypeError::new(
+                    position,
+                    "Only arrays stored in variables can be sliced.",
+                ));
+                return ExpressionType::Invalid;
+            }
+            Type::Array { element, .. } | Type::Slice { element } => element.clone(),
+            _ => panic!("Expected an array or a slice"),
+        };
+
+        if let AstNodeKind::Expression(Expression::Slicing { sequence: s, .. }) =
+            &mut self.file_ast.nodes[index].kind
+        {
+            *s = Some(sequence);
+        }
+
+        ExpressionType::Value(Type::Slice { element })
+    }
+
+    // Arrays and slices are indexed directly or through a reference, r[i] is (*r)[i].
+    fn check_sequence(&mut self, index: usize, object: usize) -> Option<Type> {
+        let position = self.file_ast.nodes[object].position;
+        let reason = match self.check_expression(object) {
+            ExpressionType::Value(t @ (Type::Array { .. } | Type::Slice { .. })) => return Some(t),
+            ExpressionType::Value(Type::Pointer {
+                target,
+                nullable: false,
+            }) if matches!(*target, Type::Array { .. } | Type::Slice { .. }) => {
+                self.dereference_object(index, object);
+                return Some(*target);
+            }
+            ExpressionType::Value(t @ (Type::Pointer { nullable: true, .. } | Type::Null)) => {
+                self.push_null_error(position, &t);
+                return None;
+            }
+            ExpressionType::Value(t) => format!("Values of type {} cannot be indexed.", t),
+            ExpressionType::NoValue => {
+                String::from("Expression has no value and cannot be indexed.")
+            }
+            ExpressionType::Invalid => return None,
+        };
+
+        self.push_error(TypeError::new(position, &reason));
+        None
+    }
+
+    // Indices are integers, the ones known at compile time are checked against the size of
+    // arrays. The end of a slicing can be the size itself.
+    fn check_bound(&mut self, bound: usize, size: Option<usize>, limit: Option<usize>) -> bool {
+        let position = self.file_ast.nodes[bound].position;
+        match self.check_expression(bound) {
+            ExpressionType::Value(Type::Integer { .. }) => {}
+            ExpressionType::Invalid => return false,
+            ExpressionType::Value(t) => {
+                self.push_error(TypeError::new(
+                    position,
+                    &format!("Indices must be integers but found {}.", t),
+                ));
+                return false;
+            }
+            ExpressionType::NoValue => {
+                self.push_error(TypeError::new(
+                    position,
+                    "Expression does not produce a value and cannot be an index.",
+                ));
+                return false;
+            }
+        }
+
+        let value = match self.constant_integer(bound) {
+            Some(v) => v,
+            None => return true,
+        };
+
+        let reason = match (size, limit) {
+            _ if value < 0 => format!("Index {} is negative.", value),
+            (Some(size), Some(limit)) if value >= limit as i128 => format!(
+                "Index {} is out of bounds for an array of {} elements.",
+                value, size
+            ),
+            _ => return true,
+        };
+
+        self.push_error(TypeError::new(position, &reason));
+        false
+    }
+
+    fn constant_integer(&self, index: usize) -> Option<i128> {
+        self.c_constant(index).map(|(value, _)| value)
+    }
+
+    // Constant integers are computed like C does, with 32 bits unless an operand needs 64.
+    // Returns the value and its size, None when the expression is not a constant or overflows.
+    fn c_constant(&self, index: usize) -> Option<(i128, usize)> {
+        let (value, size) = match &self.file_ast.nodes[index].kind {
+            AstNodeKind::Expression(Expression::Literal(lit)) => match lit.value.llc_type {
+                Type::Integer { .. } => {
+                    let value: i128 = lit.value.value.parse().ok()?;
+                    match integer_range(true, 32).contains(&value) {
+                        true => (value, 32),
+                        false => (value, 64),
+                    }
+                }
+                _ => return None,
+            },
+            AstNodeKind::Expression(Expression::Unary {
+                operator: Operator::Minus,
+                operand,
+            }) => {
+                let (value, size) = self.c_constant(*operand)?;
+                (-value, size)
+            }
+            AstNodeKind::Expression(Expression::Binary { operator, lhs, rhs }) => {
+                let (lhs, lhs_size) = self.c_constant(*lhs)?;
+                let (rhs, rhs_size) = self.c_constant(*rhs)?;
+                let value = match operator {
+                    Operator::Plus => lhs.checked_add(rhs),
+                    Operator::Minus => lhs.checked_sub(rhs),
+                    Operator::Times => lhs.checked_mul(rhs),
+                    Operator::Divide => lhs.checked_div(rhs),
+                    Operator::Modulus => lhs.checked_rem(rhs),
+                    _ => None,
+                }?;
+                (value, lhs_size.max(rhs_size))
+            }
+            _ => return None,
+        };
+
+        integer_range(true, size)
+            .contains(&value)
+            .then_some((value, size))
+    }
+
+    // Reason why a constant integer cannot be used where a value of the expected type is.
+    fn range_error(&self, exp_index: usize, expected: &Type) -> Option<String> {
+        let value = self.constant_integer(exp_index)?;
+        match expected {
+            Type::Integer { signed, size } if !integer_range(*signed, *size).contains(&value) => {
+                Some(format!(
+                    "Value {} is out of the range of type {}.",
+                    value, expected
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    // The parser cannot tell the names of enums from the names of structs, the types it
+    // builds all name structs until the declarations are known.
+    fn resolve_type(&self, llc_type: &Type) -> Type {
+        match llc_type {
+            Type::Struct { name }
+                if self.enums.contains_key(name)
+                    || matches!(self.external_type(name), Some(Export::Enum { .. })) =>
+            {
+                Type::Enum { name: name.clone() }
+            }
+            Type::Block {
+                input,
+                output,
+                variadic,
+            } => Type::Block {
+                input: input
+                    .as_ref()
+                    .map(|i| i.iter().map(|t| self.resolve_type(t)).collect()),
+                output: output.as_ref().map(|t| Box::new(self.resolve_type(t))),
+                variadic: *variadic,
+            },
+            Type::Pointer { target, nullable } => Type::Pointer {
+                target: Box::new(self.resolve_type(target)),
+                nullable: *nullable,
+            },
+            Type::Array { element, size } => Type::Array {
+                element: Box::new(self.resolve_type(element)),
+                size: *size,
+            },
+            Type::Slice { element } => Type::Slice {
+                element: Box::new(self.resolve_type(element)),
+            },
+            t => t.clone(),
+        }
+    }
+
+    // Looks a name up without reporting errors, used to tell variants from field accesses.
+    fn is_enum_name(&self, ident_node: usize) -> bool {
+        let index = match self.file_ast.nodes[ident_node].kind {
+            AstNodeKind::Expression(Expression::Identifier { index }) => index,
+            _ => return false,
+        };
+
+        let name = &self.file_ast.identifiers[index].name;
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.symbols.get(name))
+            .is_some_and(|symbol| self.is_enum_symbol(*symbol))
+    }
+
+    fn enum_variants(&self, name: &str) -> Vec<(Box<str>, Vec<Type>)> {
+        let definition = match (self.enums.get(name), self.external_type(name)) {
+            (Some(d), _) => *d,
+            (None, Some(Export::Enum { variants })) => return variants.clone(),
+            _ => return vec![],
+        };
+
+        let variants = match &self.file_ast.nodes[definition].kind {
+            AstNodeKind::Expression(Expression::EnumDefinition { variants }) => variants,
+            _ => panic!("Expected an enum definition"),
+        };
+
+        variants
+            .iter()
+            .map(|v| match &self.file_ast.nodes[*v].kind {
+                AstNodeKind::Statement(Statement::Variant {
+                    ident_index,
+                    payload,
+                }) => (
+                    self.field_name(*ident_index),
+                    payload.iter().map(|t| self.resolve_type(t)).collect(),
+                ),
+                _ => panic!("Expected an enum variant"),
+            })
+            .collect()
+    }
+
+    fn check_enum_definition(&mut self, symbol: usize, index: usize) {
+        let name = self.file_ast.identifiers[symbol].name.clone();
+        let variants = match &self.file_ast.nodes[index].kind {
+            AstNodeKind::Expression(Expression::EnumDefinition { variants }) => variants.clone(),
+            _ => panic!("Expected an enum definition"),
+        };
+
+        if variants.is_empty() {
+            let position = self.file_ast.nodes[index].position;
+            self.push_error(TypeError::new(
+                position,
+                &format!("Enum {} must have at least one variant.", name),
+            ));
+        }
+
+        let mut names: Vec<Box<str>> = vec![];
+        for variant in variants {
+            let (ident_index, payload) = match &self.file_ast.nodes[variant].kind {
+                AstNodeKind::Statement(Statement::Variant {
+                    ident_index,
+                    payload,
+                }) => (
+                    *ident_index,
+                    payload
+                        .iter()
+                        .map(|t| self.resolve_type(t))
+                        .collect::<Vec<Type>>(),
+                ),
+                _ => panic!("Expected an enum variant"),
+            };
+
+            let position = self.file_ast.nodes[variant].position;
+            let variant_name = self.field_name(ident_index);
+            if names.contains(&variant_name) {
+                self.push_error(
+                    TypeError::new(
+                        position,
+                        &format!(
+                            "Variant {} is already declared in enum {}.",
+                            variant_name, name
+                        ),
+                    )
+                    .with_code(diagnostic::REDECLARATION),
+                );
+            }
+            names.push(variant_name);
+
+            for llc_type in payload {
+                if self.check_type(&llc_type, position)
+                    && self.contains_type(&llc_type, &name, &mut vec![])
+                {
+                    self.push_error(TypeError::new(
+                        position,
+                        &format!("Enum {} cannot contain itself.", name),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn check_enum_variant(&mut self, index: usize) -> ExpressionType {
+        let (enum_index, variant, args) = match &self.file_ast.nodes[index].kind {
+            AstNodeKind::Expression(Expression::EnumVariant {
+                enum_index,
+                variant,
+                args,
+            }) => (*enum_index, *variant, args.clone()),
+            _ => panic!("Expected an enum variant"),
+        };
+
+        let arg_types: Vec<ExpressionType> =
+            args.iter().map(|a| self.check_expression(*a)).collect();
+
+        let name = match self.resolve(enum_index) {
+            Some(symbol) if self.is_enum_symbol(symbol) => self.type_name(symbol),
+            Some(symbol) => {
+                let position = self.file_ast.nodes[enum_index].position;
+                let name = self.file_ast.identifiers[symbol].name.clone();
+                self.push_error(TypeError::new(
+                    position,
+                    &format!("{} is not an enum.", name),
+                ));
+                return ExpressionType::Invalid;
+            }
+            None => return ExpressionType::Invalid,
+        };
+
+        let variant_name = self.field_name(variant);
+        let payload = match self
+            .enum_variants(&name)
+            .into_iter()
+            .find(|(n, _)| *n == variant_name)
+        {
+            Some((_, payload)) => payload,
+            None => {
+                let position = self.file_ast.nodes[variant].position;
+                self.push_error(TypeError::new(
+                    position,
+                    &format!("Enum {} has no variant {}.", name, variant_name),
+                ));
+                return ExpressionType::Invalid;
+            }
+        };
+
+        if payload.len() != args.len() {
+            let position = self.file_ast.nodes[index].position;
+            self.push_error(
+                TypeError::new(
+                    position,
+                    &format!(
+                        "Variant {} of {} holds {} value(s) but {} were given.",
+                        variant_name,
+                        name,
+                        payload.len(),
+                        args.len()
+                    ),
+                )
+                .with_code(diagnostic::ARITY_MISMATCH),
+            );
+        }
+
+        for ((arg, arg_type), expected) in args.iter().zip(arg_types).zip(payload.iter()) {
+            let reason = match arg_type {
+                ExpressionType::Value(t) if self.convert(*arg, &t, expected) => continue,
+                ExpressionType::Invalid => continue,
+                ExpressionType::Value(t) => {
+                    format!("Expected a value of type {} but found {}.", expected, t)
+                }
+                ExpressionType::NoValue => format!(
+                    "Expected a value of type {} but the expression has no value.",
+                    expected
+                ),
+            };
+
+            let position = self.file_ast.nodes[*arg].position;
+            self.push_error(TypeError::new(position, &reason));
+        }
+
+        ExpressionType::Value(Type::Enum { name })
+    }
+
+    // Arms are checked in order, an arm is unreachable once the previous ones cover every
+    // variant, and the match must cover all of them.
+    fn check_match(&mut self, index: usize, as_value: bool) -> ExpressionType {
+        let (value, arms) = match &self.file_ast.nodes[index].kind {
+            AstNodeKind::Expression(Expression::Match { value, arms }) => (*value, arms.clone()),
+            _ => panic!("Expected a match expression"),
+        };
+
+        if as_value {
+            self.check_branching_value(index, "Match expressions");
+        }
+
+        let name = match self.check_expression(value) {
+            ExpressionType::Value(Type::Enum { name }) => Some(name),
+            ExpressionType::Value(t) => {
+                let position = self.file_ast.nodes[value].position;
+                self.push_error(TypeError::new(
+                    position,
+                    &format!("Only enums can be matched but found a value of type {}.", t),
+                ));
+                None
+            }
+            ExpressionType::NoValue => {
+                let position = self.file_ast.nodes[value].position;
+                self.push_error(TypeError::new(
+                    position,
+                    "Matched expression does not produce a value.",
+                ));
+                None
+            }
+            ExpressionType::Invalid => None,
+        };
+
+        let variants = match &name {
+            Some(n) => self.enum_variants(n),
+            None => vec![],
+        };
+
+        let mut matched: Vec<Box<str>> = vec![];
+        let mut has_wildcard = false;
+        let mut branches = vec![];
+        let before = self.uninitialized.clone();
+        let mut arms_uninitialized = vec![];
+
+        for arm in arms {
+            let (pattern, statements) = match &self.file_ast.nodes[arm].kind {
+                AstNodeKind::Statement(Statement::MatchArm {
+                    pattern,
+                    statements,
+                }) => (
+                    match pattern {
+                        Pattern::Wildcard => None,
+                        Pattern::Variant {
+                            ident_index,
+                            bindings,
+                        } => Some((*ident_index, bindings.clone())),
+                    },
+                    statements.clone(),
+                ),
+                _ => panic!("Expected a match arm"),
+            };
+
+            let position = self.file_ast.nodes[arm].position;
+            let covered = has_wildcard
+                || (name.is_some() && variants.iter().all(|(n, _)| matched.contains(n)));
+            if covered {
+                self.push_error(TypeError::new(
+                    position,
+                    "Arm is unreachable, the previous arms already cover every variant.",
+                ));
+            }
+
+            // Bindings live in a scope around the arm body, so the body can shadow them.
+            self.scopes.push(Scope::new(false));
+
+            match pattern {
+                None => has_wildcard = true,
+                Some((ident_index, bindings)) => {
+                    let payload = self.check_pattern(arm, name.as_deref(), ident_index, &bindings);
+                    let variant_name = self.field_name(ident_index);
+                    if !covered && matched.contains(&variant_name) {
+                        self.push_error(TypeError::new(
+                            position,
+                            &format!(
+                                "Arm is unreachable, variant {} is already matched.",
+                                variant_name
+                            ),
+                        ));
+                    }
+                    matched.push(variant_name);
+
+                    for (i, binding) in bindings.iter().enumerate() {
+                        let binding = match binding {
+                            Some(b) => *b,
+                            None => continue,
+                        };
+
+                        if let Some(symbol) = self.declare(binding, arm) {
+                            self.file_ast.identifiers[symbol].type_state =
+                                match payload.as_ref().and_then(|p| p.get(i)) {
+                                    Some(t) => TypeState::Ok(t.clone()),
+                                    None => TypeState::Invalid,
+                                };
+                        }
+                    }
+                }
+            }
+
+            let arm_type = self.check_branch(&statements, as_value);
+            self.scopes.pop();
+            branches.push((self.branch_value(&statements), arm_type));
+            let uninitialized = std::mem::replace(&mut self.uninitialized, before.clone());
+            arms_uninitialized.push((statements, uninitialized));
+        }
+        self.join_branches(before, arms_uninitialized);
+
+        if let (Some(name), false) = (&name, has_wildcard) {
+            let missing: Vec<&str> = variants
+                .iter()
+                .filter(|(n, _)| !matched.contains(n))
+                .map(|(n, _)| &**n)
+                .collect();
+
+            if !missing.is_empty() {
+                let position = self.file_ast.nodes[index].position;
+                self.push_error(
+                    TypeError::new(
+                        position,
+                        &format!(
+                            "Match on {} is not exhaustive, missing variants {}.",
+                            name,
+                            missing.join(", ")
+                        ),
+                    )
+                    .with_code(diagnostic::NON_EXHAUSTIVE_MATCH),
+                );
+            }
+        }
+
+        if !as_value {
+            return ExpressionType::NoValue;
+        }
+
+        self.unify_branches(index, "match expression", branches)
+    }
+
+    // Returns the payload of the matched variant when the pattern is valid.
+    fn check_pattern(
+        &mut self,
+        arm: usize,
+        name: Option<&str>,
+        ident_index: usize,
+        bindings: &[Option<usize>],
+    ) -> Option<Vec<Type>> {
+        let name = name?;
+        let variant_name = self.field_name(ident_index);
+        let position = self.file_ast.nodes[arm].position;
+
+        let payload = match self
+            .enum_variants(name)
+            .into_iter()
+            .find(|(n, _)| *n == variant_name)
+        {
+            Some((_, payload)) => payload,
+            None => {
+                let position = self.file_ast.nodes[ident_index].position;
+                self.push_error(TypeError::new(
+                    position,
+                    &format!("Enum {} has no variant {}.", name, variant_name),
+                ));
+                return None;
+            }
+        };
+
+        if !bindings.is_empty() && bindings.len() != payload.len() {
+            self.push_error(
+                TypeError::new(
+                    position,
+                    &format!(
+                        "Variant {} of {} holds {} value(s) but the pattern binds {}.",
+                        variant_name,
+                        name,
+                        payload.len(),
+                        bindings.len()
+                    ),
+                )
+                .with_code(diagnostic::ARITY_MISMATCH),
+            );
+            return None;
+        }
+
+        Some(payload)
+    }
+
+    // The last name of the use path is bound in the root scope, to the module or to the
+    // declaration it names.
+    fn declare_import(&mut self, import: &Import<'a>) {
+        let use_index = import.use_index;
+        let ident_node = match &self.file_ast.nodes[use_index].kind {
+            AstNodeKind::Statement(Statement::Use { path }) => *path.last().unwrap(),
+            _ => panic!("Expected a use statement"),
+        };
+
+        let symbol = match self.declare(ident_node, use_index) {
+            Some(s) => s,
+            None => return,
+        };
+
+        let name = self.file_ast.identifiers[symbol].name.clone();
+        let export = match &import.declaration {
+            Some(declaration) => import.exports.declarations.get(declaration),
+            None => {
+                self.file_ast.identifiers[symbol].type_state = TypeState::Invalid;
+                self.modules.insert(symbol, import.exports);
+                return;
+            }
+        };
+
+        // The project checks that the declaration exists, it is only missing from the exports
+        // when its own module failed to type check it.
+        let export = match export {
+            Some(e) => e,
+            None => {
+                self.file_ast.identifiers[symbol].type_state = TypeState::Invalid;
+                return;
+            }
+        };
+
+        let type_name = exports::qualify(&import.exports.module, &name);
+        self.file_ast.identifiers[symbol].type_state = TypeState::Ok(match export {
+            Export::Value(t) => t.clone(),
+            Export::Struct { .. } => Type::Struct { name: type_name },
+            Export::Enum { .. } => Type::Enum { name: type_name },
+        });
+        self.imported.insert(symbol, export);
+    }
+
+    fn qualified(&self, name: &str) -> Box<str> {
+        exports::qualify(self.module, name)
+    }
+
+    // Name of the type declared by a struct or enum symbol, qualified by the path of the module
+    // declaring it.
+    fn type_name(&self, symbol: usize) -> Box<str> {
+        match &self.file_ast.identifiers[symbol].type_state {
+            TypeState::Ok(Type::Struct { name } | Type::Enum { name }) => name.clone(),
+            _ => self.qualified(&self.file_ast.identifiers[symbol].name),
+        }
+    }
+
+    fn module_exports(&self, module: usize) -> Option<&'a Exports> {
+        let symbol = self.lookup(module)?;
+        self.modules.get(&symbol).copied()
+    }
+
+    // Names of structs and enums written in the types of the source are replaced by their
+    // qualified name, so that types of different modules never share a name. Names which are
+    // not declared are kept, check_type reports them.
+    fn qualify_annotations(&mut self) {
+        for index in 0..self.file_ast.nodes.len() {
+            let types: Vec<Type> = annotations(&mut self.file_ast.nodes[index].kind)
+                .into_iter()
+                .map(|t| t.clone())
+                .collect();
+            let qualified: Vec<Type> = types.iter().map(|t| self.qualify_type(t)).collect();
+
+            for (t, q) in annotations(&mut self.file_ast.nodes[index].kind)
+                .into_iter()
+                .zip(qualified)
+            {
+                *t = q;
+            }
+        }
+    }
+
+    fn qualify_type(&self, llc_type: &Type) -> Type {
+        match llc_type {
+            Type::Struct { name } => self
+                .qualify_type_name(name)
+                .unwrap_or_else(|| llc_type.clone()),
+            Type::Block {
+                input,
+                output,
+                variadic,
+            } => Type::Block {
+                input: input
+                    .as_ref()
+                    .map(|i| i.iter().map(|t| self.qualify_type(t)).collect()),
+                output: output.as_ref().map(|t| Box::new(self.qualify_type(t))),
+                variadic: *variadic,
+            },
+            Type::Pointer { target, nullable } => Type::Pointer {
+                target: Box::new(self.qualify_type(target)),
+                nullable: *nullable,
+            },
+            Type::Array { element, size } => Type::Array {
+                element: Box::new(self.qualify_type(element)),
+                size: *size,
+            },
+            Type::Slice { element } => Type::Slice {
+                element: Box::new(self.qualify_type(element)),
+            },
+            t => t.clone(),
+        }
+    }
+
+    // Name is a struct or an enum declared or imported by the file, module::Name one declared by
+    // an imported module.
+    fn qualify_type_name(&self, name: &str) -> Option<Type> {
+        if let Some((module, name)) = name.split_once("::") {
+            let symbol = self.scopes[0].symbols.get(module)?;
+            let exports = self.modules.get(symbol)?;
+            let qualified = exports::qualify(&exports.module, name);
+            return match exports.declarations.get(name)? {
+                Export::Struct { .. } => Some(Type::Struct { name: qualified }),
+                Export::Enum { .. } => Some(Type::Enum { name: qualified }),
+                Export::Value(_) => None,
+            };
+        }
+
+        let symbol = *self.scopes[0].symbols.get(name)?;
+        if self.is_struct_symbol(symbol) {
+            Some(Type::Struct {
+                name: self.type_name(symbol),
+            })
+        } else if self.is_enum_symbol(symbol) {
+            Some(Type::Enum {
+                name: self.type_name(symbol),
+            })
+        } else {
+            None
+        }
+    }
+
+    // Types named through their module, module::Name, have to be declared by a module the file
+    // imports. The type itself is checked where it is used, like any other type.
+    fn check_type_paths(&mut self) {
+        for path in self.file_ast.type_paths.clone() {
+            let exports = self.scopes[0]
+                .symbols
+                .get(&path.module)
+                .and_then(|s| self.modules.get(s));
+
+            let reason = match exports.map(|e| e.declarations.get(&path.name)) {
+                None => format!("{} is not a module.", path.module),
+                Some(None) => format!("Module {} has no declaration {}.", path.module, path.name),
+                Some(Some(Export::Value(_))) => {
+                    format!("{}::{} is a value, not a type.", path.module, path.name)
+                }
+                Some(Some(_)) => continue,
+            };
+            self.push_error(TypeError::new(path.position, &reason));
+        }
+    }
+
+    // Declaration named by a path, along with the names of its module and of the declaration.
+    fn path_export(
+        &mut self,
+        module: usize,
+        name: usize,
+    ) -> Option<(Box<str>, Box<str>, &'a Export)> {
+        let symbol = self.resolve(module)?;
+        let module_name = self.file_ast.identifiers[symbol].name.clone();
+        let declaration = self.field_name(name);
+
+        let exports = match self.modules.get(&symbol) {
+            Some(e) => *e,
+            None => {
+                let position = self.file_ast.nodes[module].position;
+                self.push_error(TypeError::new(
+                    position,
+                    &format!("{} is not a module.", module_name),
+                ));
+                return None;
+            }
+        };
+
+        match exports.declarations.get(&declaration) {
+            Some(export) => Some((module_name, declaration, export)),
+            None => {
+                let position = self.file_ast.nodes[name].position;
+                self.push_error(TypeError::new(
+                    position,
+                    &format!("Module {} has no declaration {}.", module_name, declaration),
+                ));
+                None
+            }
+        }
+    }
+
+    // Paths in expressions name values, the types they name are only used by type expressions
+    // and struct literals.
+    fn check_path(&mut self, module: usize, name: usize) -> ExpressionType {
+        let (module_name, declaration, export) = match self.path_export(module, name) {
+            Some(p) => p,
+            None => return ExpressionType::Invalid,
+        };
+
+        let value_type = match export {
+            Export::Value(t) => t.clone(),
+            _ => {
+                let position = self.file_ast.nodes[name].position;
+                self.push_error(TypeError::new(
+                    position,
+                    &format!("{}::{} is a type, not a value.", module_name, declaration),
+                ));
+                return ExpressionType::Invalid;
+            }
+        };
+        let symbol = self.identifier_index(module);
+
+        // The name gets its own symbol, so the declaration it refers to has a type.
+        let mut identifier = Identifier::new(&declaration);
+        identifier.type_state = TypeState::Ok(value_type.clone());
+        identifier.declaration_idx = self.file_ast.identifiers[symbol].declaration_idx;
+        self.file_ast.identifiers.push(identifier);
+        let index = self.file_ast.identifiers.len() - 1;
+        self.set_identifier_index(name, index);
+
+        ExpressionType::Value(value_type)
+    }
+
+    // Structs and enums declared by the modules the file depends on.
+    // Type declared by one of the modules the file depends on, by its qualified name.
+    fn external_type(&self, name: &str) -> Option<&'a Export> {
+        let (module, name) = name.rsplit_once("::")?;
+        self.dependencies
+            .iter()
+            .find(|e| *e.module == *module)
+            .and_then(|e| e.declarations.get(name))
+            .filter(|e| !matches!(e, Export::Value(_)))
+    }
+
+    fn exports(&self, declarations: &[usize]) -> Exports {
+        let mut exports = Exports {
+            module: Box::from(self.module),
+            ..Exports::default()
+        };
+
+        for decl_index in declarations {
+            let ident_node = match self.file_ast.nodes[*decl_index].kind {
+                AstNodeKind::Statement(Statement::Declaration { ident_index, .. }) => ident_index,
+                _ => panic!("Expected a declaration"),
+            };
+
+            let symbol = self.identifier_index(ident_node);
+            let identifier = &self.file_ast.identifiers[symbol];
+            let export = if self.is_struct_symbol(symbol) {
+                Export::Struct {
+                    fields: self.struct_fields(&self.qualified(&identifier.name)),
+                }
+            } else if self.is_enum_symbol(symbol) {
+                Export::Enum {
+                    variants: self.enum_variants(&self.qualified(&identifier.name)),
+                }
+            } else {
+                match &identifier.type_state {
+                    TypeState::Ok(t) => Export::Value(t.clone()),
+                    _ => continue,
+                }
+            };
+
+            exports.declarations.insert(identifier.name.clone(), export);
+        }
+
+        exports
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        common::{diagnostic, source_map::SourceMap},
+        parser::parser::FileAst,
+    };
+
+    // Messages of the errors of a source, none when it is well typed.
+    fn errors(source: &str) -> Vec<String> {
+        let mut sources = SourceMap::new();
+        match FileAst::from_source(&mut sources, "test.llc", source) {
+            Ok(_) => vec![],
+            Err(errors) => errors
+                .iter()
+                .map(|e| e.diagnostic().message.to_string())
+                .collect(),
+        }
+    }
+
+    // Codes of the errors of a source.
+    fn codes(source: &str) -> Vec<&'static str> {
+        let mut sources = SourceMap::new();
+        let errors = FileAst::from_source(&mut sources, "test.llc", source).err();
+        errors
+            .iter()
+            .flatten()
+            .map(|e| e.diagnostic().code)
+            .collect()
+    }
+
+    // Replacements suggested by the errors of a source.
+    fn fixes(source: &str) -> Vec<String> {
+        let mut sources = SourceMap::new();
+        let errors = FileAst::from_source(&mut sources, "test.llc", source).err();
+        errors
+            .iter()
+            .flatten()
+            .flat_map(|e| e.diagnostic().fixes)
+            .map(|f| f.replacement.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn rejects_a_return_in_a_single_branch() {
+        assert_eq!(
+            errors("f := (a: i32) -> i32 { if a > 0 { => 1; }; };"),
+            ["Block must return a value of type i32 on every path, some end without a return statement."]
+        );
+    }
+
+    #[test]
+    fn accepts_returns_in_both_branches() {
+        assert!(errors("f := (a: i32) -> i32 { if a > 0 { => 1; } else { => 2; }; };").is_empty());
+        assert!(errors("f := (a: i32) -> i32 { if a > 0 { => 1; }; => 2; };").is_empty());
+    }
+
+    #[test]
+    fn rejects_a_return_in_a_loop_only() {
+        assert_eq!(
+            errors("f := (a: i32) -> i32 { while a > 0 { => 1; }; };").len(),
+            1
+        );
+    }
+
+    #[test]
+    fn checks_returns_in_every_match_arm() {
+        let shape = "Shape := enum { Circle(f64), Square(f64), };\n";
+        let all = "f := (s: Shape) -> f64 { match s { Circle(r) => { => r; }, Square(c) => { => c; }, }; };";
+        let one = "f := (s: Shape) -> f64 { match s { Circle(r) => { => r; }, _ => {}, }; };";
+
+        assert!(errors(&format!("{}{}", shape, all)).is_empty());
+        assert_eq!(errors(&format!("{}{}", shape, one)).len(), 1);
+    }
+
+    #[test]
+    fn checks_integer_literals_against_their_type() {
+        assert_eq!(
+            errors("x : i8 = 300;"),
+            ["Value 300 is out of the range of type i8."]
+        );
+        assert_eq!(
+            errors("x : u8 = -1;"),
+            ["Value -1 is out of the range of type u8."]
+        );
+        assert!(errors("x : i8 = -128;\ny : u8 = 255;").is_empty());
+    }
+
+    #[test]
+    fn converts_constant_expressions_of_literals() {
+        assert!(errors("x : i64 = 1 + 2;\ny : u8 = 300 - 100;").is_empty());
+        assert!(errors(
+            "f := (v: i64) -> i64 {\n    => 1 + 1;\n};\nmain := {\n    x := -> f(1 + 1);\n};"
+        )
+        .is_empty());
+        assert_eq!(
+            errors("x : u8 = 200 + 100;"),
+            ["Value 300 is out of the range of type u8."]
+        );
+        assert_eq!(
+            errors("x : i32 = 2147483647 + 1;"),
+            ["Constant expression overflows its C type or divides by zero."]
+        );
+    }
+
+    #[test]
+    fn rejects_reading_an_uninitialized_variable() {
+        assert_eq!(
+            errors("main := {\n    x : i32;\n    y := x;\n};"),
+            ["Variable x is read before it is initialized."]
+        );
+        assert_eq!(
+            errors(
+                "main := {\n    x : i32;\n    if true {\n        x = 1;\n    };\n    y := x;\n};"
+            )
+            .len(),
+            1
+        );
+        assert_eq!(
+            errors("main := {\n    x : i32;\n    while true {\n        x = 1;\n    };\n    y := x;\n};").len(),
+            1
+        );
+    }
+
+    #[test]
+    fn accepts_variables_initialized_on_every_path() {
+        assert!(errors("main := {\n    x : i32;\n    if true {\n        x = 1;\n    } else {\n        x = 2;\n    };\n    y := x;\n};").is_empty());
+        assert!(errors("f := (a: i32) -> i32 {\n    x : i32;\n    if a > 0 {\n        => 0;\n    } else {\n        x = 2;\n    };\n    => x;\n};").is_empty());
+        assert!(errors("main := {\n    x : i32;\n    p := &x;\n    y := x;\n};").is_empty());
+        assert!(errors("x : i32;\nmain := {\n    y := x;\n};").is_empty());
+    }
+
+    #[test]
+    fn rejects_an_inferred_return_on_some_paths() {
+        assert_eq!(errors("f := (a: i32) { if a > 0 { => 1; }; };").len(), 1);
+    }
+
+    #[test]
+    fn rejects_if_expressions_with_statements_as_arguments() {
+        assert_eq!(
+            errors("g := (v: i32) -> i32 { => v; };\nmain := {\n    d := true;\n    x := -> g(if d { y := 1; y; } else { 2; });\n};"),
+            ["If expressions with statements in their branches can only be declared, assigned or returned."]
+        );
+        assert_eq!(
+            errors("main := {\n    d := true;\n    x := 1 + if d { y := 1; y; } else { 2; };\n};")
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn accepts_if_expressions_with_statements_as_stored_values() {
+        assert!(errors("g := (v: i32) -> i32 { => v; };\nf := (d: bool) -> i32 {\n    a := if d { y := 1; y; } else { 2; };\n    a = if d { y := 1; y; } else { 2; };\n    b := -> g(if d { 1; } else { 2; });\n    => if d { a; } else { if d { y := 1; y; } else { b; }; };\n};").is_empty());
+    }
+
+    #[test]
+    fn rejects_match_expressions_as_operands() {
+        let enums =
+            "Shape := enum { Circle(i32), Square(i32), };\ng := (v: i32) -> i32 { => v; };\n";
+        assert_eq!(
+            errors(&format!("{}main := {{\n    s := Shape.Circle(2);\n    x := 1 + match s {{ Circle(r) => {{ r; }}, Square(c) => {{ c; }}, }};\n}};", enums)),
+            ["Match expressions can only be declared, assigned or returned."]
+        );
+        assert_eq!(
+            errors(&format!("{}main := {{\n    s := Shape.Circle(2);\n    x := -> g(match s {{ Circle(r) => {{ r; }}, Square(c) => {{ c; }}, }});\n}};", enums)).len(),
+            1
+        );
+        assert!(errors(&format!("{}f := (s: Shape) -> i32 {{\n    x := match s {{ Circle(r) => {{ r; }}, Square(c) => {{ c; }}, }};\n    => match s {{ Circle(r) => {{ x; }}, _ => {{ 0; }}, }};\n}};", enums)).is_empty());
+    }
+
+    #[test]
+    fn rejects_root_declarations_computed_at_run_time() {
+        let message =
+            "Root declarations are C globals, their value has to be a constant expression.";
+        assert_eq!(errors("a := 1;\nb := a + 1;"), [message]);
+        assert_eq!(
+            errors("f := () -> i32 {\n    => 1;\n};\nc := -> f();"),
+            [message]
+        );
+    }
+
+    #[test]
+    fn accepts_constant_root_declarations() {
+        assert!(errors("a := 1;\nd := -(2 * 3) + 1;\ne := &a;\ng := [1, 2, 3];\nh := if true { 1; } else { 2; };").is_empty());
+    }
+
+    #[test]
+    fn suggests_a_similar_name_for_a_typo() {
+        assert_eq!(
+            fixes("main := {\n    counter := 1;\n    x := countr;\n};"),
+            ["counter"]
+        );
+    }
+
+    #[test]
+    fn suggests_nothing_for_a_short_name() {
+        assert!(fixes("main := {\n    c := 1;\n    x := y;\n};").is_empty());
+        assert!(fixes("main := {\n    ab := 1;\n    x := ac;\n};").is_empty());
+    }
+
+    #[test]
+    fn rejects_a_match_missing_variants() {
+        assert_eq!(
+            errors("Shape := enum { Circle(i32), Square(i32), Point, };\n\nmain := {\n    s := Shape.Point;\n    match s { Circle(r) => {}, };\n};"),
+            ["Match on Shape is not exhaustive, missing variants Square, Point."]
+        );
+        assert_eq!(
+            errors("Shape := enum { Circle(i32), Square(i32), Point, };\n\nmain := {\n    s := Shape.Point;\n    x := match s { Circle(r) => { r; }, Square(c) => { c; }, };\n};"),
+            ["Match on Shape is not exhaustive, missing variants Point."]
+        );
+    }
+
+    #[test]
+    fn accepts_a_wildcard_for_the_remaining_variants() {
+        assert!(errors("Shape := enum { Circle(i32), Square(i32), Point, };\n\nmain := {\n    s := Shape.Point;\n    match s { Circle(r) => {}, _ => {}, };\n};").is_empty());
+    }
+
+    #[test]
+    fn rejects_unreachable_arms() {
+        assert_eq!(
+            errors("Shape := enum { Circle(i32), Square(i32), Point, };\n\nmain := {\n    s := Shape.Point;\n    match s { Point => {}, Point => {}, _ => {}, };\n};"),
+            ["Arm is unreachable, variant Point is already matched."]
+        );
+        assert_eq!(
+            errors("Shape := enum { Circle(i32), Square(i32), Point, };\n\nmain := {\n    s := Shape.Point;\n    match s { Circle(r) => {}, Square(c) => {}, Point => {}, _ => {}, };\n};"),
+            ["Arm is unreachable, the previous arms already cover every variant."]
+        );
+    }
+
+    #[test]
+    fn rejects_dereferencing_a_pointer_which_may_be_null() {
+        assert_eq!(
+            errors("main := {\n    x := 1;\n    p : *i32 = &x;\n    y := *p;\n};"),
+            ["Value of type *i32 may be null, compare it to null before dereferencing it."]
+        );
+    }
+
+    #[test]
+    fn narrows_pointers_compared_to_null() {
+        assert!(errors(
+            "f := (p: *i32) -> i32 {\n    if p != null {\n        => *p;\n    };\n    => 0;\n};"
+        )
+        .is_empty());
+        assert!(errors("f := (p: *i32) -> i32 {\n    if p == null {\n        => 0;\n    } else {\n        => *p;\n    };\n};").is_empty());
+        assert_eq!(
+            errors("f := (p: *i32) -> i32 {\n    if p == null {\n        => *p;\n    };\n    => 0;\n};"),
+            ["Value of type *i32 may be null, compare it to null before dereferencing it."]
+        );
+    }
+
+    #[test]
+    fn forgets_the_comparison_to_null_of_a_reassigned_pointer() {
+        assert_eq!(
+            errors("f := (p: *i32, q: *i32) -> i32 {\n    if p != null {\n        p = q;\n        => *p;\n    };\n    => 0;\n};"),
+            ["Value of type *i32 may be null, compare it to null before dereferencing it."]
+        );
+    }
+
+    #[test]
+    fn dereferences_references_and_only_pointers() {
+        assert!(errors("main := {\n    x := 1;\n    r := &x;\n    y := *r;\n};").is_empty());
+        assert_eq!(
+            errors("main := {\n    x := 1;\n    y := *x;\n};"),
+            ["Operator * cannot be applied to a value of type i32."]
+        );
+        assert_eq!(
+            errors("main := {\n    n : *i32 = null;\n    r : &i32 = n;\n};"),
+            ["Declared type &i32 does not match the value of type *i32."]
+        );
+    }
+
+    #[test]
+    fn checks_the_number_of_arguments_of_a_call() {
+        assert_eq!(
+            errors("f := (a: i32, b: bool) -> i32 {\n    => a;\n};\n\nmain := {\n    x := -> f(1);\n};"),
+            ["Block expects 2 argument(s) but 1 were given."]
+        );
+        assert_eq!(
+            errors("f := (a: i32, b: bool) -> i32 {\n    => a;\n};\n\nmain := {\n    x := -> f(1, true, 2);\n};"),
+            ["Block expects 2 argument(s) but 3 were given."]
+        );
+        assert_eq!(
+            errors(
+                "printf := extern (format: string, ...) -> i32;\n\nmain := {\n    -> printf();\n};"
+            ),
+            ["Block expects at least 1 argument(s) but 0 were given."]
+        );
+    }
+
+    #[test]
+    fn checks_the_types_of_the_arguments_of_a_call() {
+        assert_eq!(
+            errors("f := (a: i32, b: bool) -> i32 {\n    => a;\n};\n\nmain := {\n    x := -> f(true, 1);\n};"),
+            [
+                "Expected an argument of type i32 but found bool.",
+                "Expected an argument of type bool but found i32."
+            ]
+        );
+        assert_eq!(
+            errors(
+                "f := (a: i64) {};\n\nmain := {\n    -> f(1);\n    x : i32 = 2;\n    -> f(x);\n};"
+            ),
+            ["Expected an argument of type i64 but found i32."]
+        );
+    }
+
+    #[test]
+    fn rejects_calling_a_value_which_is_not_a_block() {
+        assert_eq!(
+            errors("main := {\n    x := 1;\n    -> x(1);\n};"),
+            ["Value of type i32 is not a block and cannot be called."]
+        );
+    }
+
+    #[test]
+    fn rejects_assigning_a_value_of_another_type() {
+        assert_eq!(
+            errors("main := {\n    x : i32 = 1;\n    x = true;\n    x = x + 1;\n};"),
+            ["Cannot assign a value of type bool to a variable of type i32."]
+        );
+    }
+
+    #[test]
+    fn rejects_assigning_to_undeclared_names() {
+        assert_eq!(
+            errors("main := {\n    y = 2;\n};"),
+            ["Identifier y is not declared."]
+        );
+    }
+
+    #[test]
+    fn rejects_assigning_to_blocks_and_values() {
+        assert_eq!(
+            errors("f := () {};\n\nmain := {\n    f = () {};\n    1 = 2;\n};"),
+            [
+                "Block f cannot be reassigned.",
+                "Only variables, their fields, elements and dereferenced pointers can be assigned."
+            ]
+        );
+    }
+
+    #[test]
+    fn types_comparisons_and_logical_operators_as_bool() {
+        assert!(errors(
+            "main := {\n    a := 1 == 2;\n    b : bool = !a && a || 1 <= 2;\n    c : bool = a != true;\n};"
+        )
+        .is_empty());
+        assert_eq!(
+            errors("main := {\n    a := 1 >= 2;\n    x : i32 = a;\n};"),
+            ["Declared type i32 does not match the value of type bool."]
+        );
+    }
+
+    #[test]
+    fn rejects_logical_operators_on_other_types() {
+        assert_eq!(
+            errors("main := {\n    a := 1 && true;\n    b := !1;\n    c := true < false;\n};"),
+            [
+                "Operator && cannot be applied to values of type i32 and bool.",
+                "Operator ! cannot be applied to a value of type i32.",
+                "Operator < cannot be applied to values of type bool."
+            ]
+        );
+    }
+
+    #[test]
+    fn names_operators_by_their_symbol() {
+        assert_eq!(
+            errors("main := {\n    x := true + 1;\n};"),
+            ["Operator + cannot be applied to values of type bool and i32."]
+        );
+    }
+
+    #[test]
+    fn gives_each_kind_of_error_its_own_code() {
+        assert_eq!(
+            codes("main := {\n    x := y;\n};"),
+            [diagnostic::UNDECLARED_NAME]
+        );
+        assert_eq!(
+            codes("main := {\n    x : bool = 1;\n};"),
+            [diagnostic::MISMATCHED_TYPES]
+        );
+        assert_eq!(
+            codes("Shape := enum { Circle, Square, };\n\nmain := {\n    s := Shape.Circle;\n    match s { Circle => {}, };\n};"),
+            [diagnostic::NON_EXHAUSTIVE_MATCH]
+        );
+        assert_eq!(
+            codes("f := (a: i32) {};\n\nmain := {\n    -> f(1, 2);\n};"),
+            [diagnostic::ARITY_MISMATCH]
+        );
+        assert_eq!(
+            codes("main := {\n    x := 1;\n    x := 2;\n};"),
+            [diagnostic::REDECLARATION]
+        );
+        assert_eq!(
+            codes("main := {\n    x := 1;\n    -> x();\n};"),
+            [diagnostic::TYPE_ERROR]
+        );
+    }
+}
@@ -1,6 +1,75 @@
-use crate::common::position::FileSpan;
+use std::fmt::Display;
 
+use crate::common::{
+    diagnostic::{self, Diagnostic, Fix, Label, Severity},
+    position::FileSpan,
+    source_map::{FileId, SourceSpan},
+};
+
+#[derive(Debug, Clone)]
 pub struct TypeError {
+    // Code of the kind of error, the generic type error unless a more precise one applies.
+    pub code: &'static str,
     pub position: FileSpan,
-    pub reason: Box<str>
-}
\ No newline at end of file
+    pub reason: Box<str>,
+    // Other positions explaining the error, like the previous declaration of a name.
+    pub labels: Vec<Label>,
+    pub fixes: Vec<Fix>,
+}
+
+impl TypeError {
+    pub(crate) fn new(position: FileSpan, reason: &str) -> Self {
+        TypeError {
+            code: diagnostic::TYPE_ERROR,
+            position,
+            reason: Box::from(reason),
+            labels: vec![],
+            fixes: vec![],
+        }
+    }
+
+    pub(crate) fn with_code(mut self, code: &'static str) -> Self {
+        self.code = code;
+        self
+    }
+
+    pub(crate) fn with_label(mut self, position: FileSpan, message: &str) -> Self {
+        self.labels.push(Label { position, message: Box::from(message) });
+        self
+    }
+
+    pub(crate) fn with_fix(mut self, fix: Fix) -> Self {
+        self.fixes.push(fix);
+        self
+    }
+
+    pub fn diagnostic(&self, file: FileId) -> Diagnostic {
+        let mut diagnostic = Diagnostic::new(
+            Severity::Error,
+            self.code,
+            &self.reason,
+            SourceSpan::new(file, self.position),
+        );
+        diagnostic.labels = self.labels.clone();
+        diagnostic.fixes = self.fixes.clone();
+        diagnostic
+    }
+}
+
+impl Display for TypeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let msg = if self.position.begin.row == self.position.end.row {
+            format!(
+                "At line {0}, col {1}:{2}. TypeError: {3}",
+                self.position.begin.row,
+                self.position.begin.col,
+                self.position.end.col,
+                self.reason
+            )
+        } else {
+            format!("{0}. TypeError: {1}", self.position, self.reason)
+        };
+
+        write!(f, "{}", msg)
+    }
+}